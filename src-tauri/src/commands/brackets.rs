@@ -1,9 +1,16 @@
 use crate::db::Database;
 use crate::models::{Bracket, BracketMatch, MatchWithTeams, Team};
+use crate::commands::courts::{load_court_plan, sync_courts, CourtPlan};
+use crate::commands::draws::{new_seed, record_draw, seeded_rng, DrawRng};
+use crate::commands::ends::{has_ends, Scored};
+use crate::commands::ranking::draw_ties_at_cuts;
 use crate::commands::teams::get_team_by_id;
+use crate::commands::scoring::{validate_score, ScoreError};
+use crate::commands::tournaments::{get_bye_scoring, get_forfeit_scoring, get_game_rules};
 use chrono::Utc;
 use rand::seq::SliceRandom;
 use rusqlite::params;
+use serde::{Deserialize, Serialize};
 use tauri::State;
 use uuid::Uuid;
 
@@ -52,7 +59,8 @@ pub fn get_matches_for_bracket(
         .prepare(
             r#"
             SELECT id, bracket_id, round_number, match_number, court_number, team1_id, team2_id,
-                   team1_score, team2_score, winner_id, next_match_id, is_bye
+                   team1_score, team2_score, winner_id, next_match_id, is_bye, slot_number, is_forfeit,
+                   started_at, ended_at
             FROM bracket_matches
             WHERE bracket_id = ?1
             ORDER BY round_number DESC, match_number ASC
@@ -75,6 +83,10 @@ pub fn get_matches_for_bracket(
                 winner_id: row.get(9)?,
                 next_match_id: row.get(10)?,
                 is_bye: row.get::<_, i32>(11)? != 0,
+                slot_number: row.get(12)?,
+                is_forfeit: row.get::<_, i32>(13)? != 0,
+                started_at: row.get(14)?,
+                ended_at: row.get(15)?,
             })
         })
         .map_err(|e| e.to_string())?
@@ -113,6 +125,10 @@ pub fn get_matches_for_bracket(
             winner_id: m.winner_id,
             next_match_id: m.next_match_id,
             is_bye: m.is_bye,
+            is_forfeit: m.is_forfeit,
+            slot_number: m.slot_number,
+            started_at: m.started_at,
+            ended_at: m.ended_at,
             team1,
             team2,
             winner,
@@ -174,53 +190,89 @@ pub fn delete_brackets(db: State<Database>, tournament_id: String) -> Result<(),
 #[tauri::command]
 pub fn generate_brackets(db: State<Database>, tournament_id: String) -> Result<(), String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    // Nothing is kept, not even the tiebreak draw, if any bracket fails
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
 
     // Get tournament settings
-    let (advance_all, advance_count, bracket_size, number_of_courts, has_consolante): (bool, Option<i32>, i32, i32, bool) =
-        conn.query_row(
-            "SELECT advance_all, advance_count, bracket_size, number_of_courts, has_consolante FROM tournaments WHERE id = ?1",
+    let (advance_all, advance_count, bracket_size, has_consolante, pairing_method, seeding): (bool, Option<i32>, i32, bool, String, String) =
+        tx.query_row(
+            "SELECT advance_all, advance_count, bracket_size, has_consolante, pairing_method, bracket_seeding FROM tournaments WHERE id = ?1",
             params![tournament_id],
             |row| {
                 Ok((
                     row.get::<_, i32>(0)? != 0,
                     row.get(1)?,
                     row.get(2)?,
-                    row.get(3)?,
-                    row.get::<_, i32>(4)? != 0,
+                    row.get::<_, i32>(3)? != 0,
+                    row.get(4)?,
+                    row.get(5)?,
                 ))
             },
         )
         .map_err(|e| e.to_string())?;
 
+    if pairing_method == "melee" {
+        return Err("A mêlée has no brackets: players are ranked individually.".to_string());
+    }
+
+    let poules = pairing_method == "poules";
+    if poules {
+        let unranked: i32 = tx
+            .query_row(
+                "SELECT COUNT(*) FROM pool_assignments WHERE tournament_id = ?1 AND pool_rank IS NULL",
+                params![tournament_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+
+        if unranked > 0 {
+            return Err("All poules must be completed before generating brackets.".to_string());
+        }
+    }
+
+    sync_courts(&tx, &tournament_id)?;
+    let courts = load_court_plan(&tx, &tournament_id)?;
+
     // Get ranked teams
-    let mut stmt = conn
+    let mut stmt = tx
         .prepare(
             r#"
-            SELECT t.id, t.tournament_id, t.captain, t.player2, t.player3, t.region, t.club, t.created_at
+            SELECT t.id, t.tournament_id, t.captain, t.player2, t.player3, t.region, t.club, t.created_at, ts.is_eliminated,
+                   t.is_active, t.withdrawn_at, t.seed, ts.rank
             FROM teams t
             JOIN team_standings ts ON t.id = ts.team_id AND t.tournament_id = ts.tournament_id
-            WHERE t.tournament_id = ?1
-            ORDER BY ts.rank ASC
+            WHERE t.tournament_id = ?1 AND t.is_active = 1
+            ORDER BY ts.is_eliminated ASC, ts.rank ASC, t.id ASC
             "#,
         )
         .map_err(|e| e.to_string())?;
 
-    let teams: Vec<Team> = stmt
+    let ranked: Vec<(Team, (bool, i32))> = stmt
         .query_map(params![tournament_id], |row| {
-            Ok(Team {
-                id: row.get(0)?,
-                tournament_id: row.get(1)?,
-                captain: row.get(2)?,
-                player2: row.get(3)?,
-                player3: row.get(4)?,
-                region: row.get(5)?,
-                club: row.get(6)?,
-                created_at: row.get(7)?,
-            })
+            Ok((
+                Team {
+                    id: row.get(0)?,
+                    tournament_id: row.get(1)?,
+                    captain: row.get(2)?,
+                    player2: row.get(3)?,
+                    player3: row.get(4)?,
+                    region: row.get(5)?,
+                    club: row.get(6)?,
+                    created_at: row.get(7)?,
+                    is_active: row.get::<_, i32>(9)? != 0,
+                    withdrawn_at: row.get(10)?,
+                    seed: row.get(11)?,
+                },
+                (row.get::<_, i32>(8)? != 0, row.get(12)?),
+            ))
         })
         .map_err(|e| e.to_string())?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| e.to_string())?;
+    drop(stmt);
+
+    let eliminated_count = ranked.iter().filter(|(_, (eliminated, _))| *eliminated).count();
+    let (mut teams, ranks): (Vec<Team>, Vec<(bool, i32)>) = ranked.into_iter().unzip();
 
     if teams.is_empty() {
         return Err("No teams to create brackets for".to_string());
@@ -230,20 +282,34 @@ pub fn generate_brackets(db: State<Database>, tournament_id: String) -> Result<(
 
     // Check if we should use the simultaneous bracket formation (FPUSA standard)
     // This happens when: advance_all = false AND has_consolante = true
-    if !advance_all && has_consolante {
+    // Poules always split this way: the qualifiers play the Concours, the others the Consolante
+    if poules || (!advance_all && has_consolante) {
         // FPUSA standard format: Create Concours and Consolante brackets simultaneously
         // Top bracket_size teams go to Concours, next teams go to Consolante
-        let concours_teams: Vec<&Team> = teams.iter().take(bracket_size as usize).collect();
-        let consolante_teams: Vec<&Team> = teams.iter().skip(bracket_size as usize).take(bracket_size as usize).collect();
-
-        if concours_teams.len() < 2 {
+        let (concours_count, consolante_count) = if poules {
+            let qualified = teams.len() - eliminated_count;
+            (qualified, if has_consolante { eliminated_count } else { 0 })
+        } else {
+            (bracket_size as usize, bracket_size as usize)
+        };
+        let concours_count = concours_count.min(teams.len());
+        let consolante_count = consolante_count.min(teams.len() - concours_count);
+        if concours_count < 2 {
             return Err("Not enough teams for Concours bracket".to_string());
         }
+        let cuts = cut_lines(
+            &[(0, concours_count), (concours_count, concours_count + consolante_count)],
+            &seeding,
+        );
+        draw_ties_at_cuts(&tx, &tournament_id, &mut teams, &ranks, &cuts)?;
+
+        let concours_teams: Vec<&Team> = teams.iter().take(concours_count).collect();
+        let consolante_teams: Vec<&Team> = teams.iter().skip(concours_count).take(consolante_count).collect();
 
         // Create Concours bracket
         let concours_id = Uuid::new_v4().to_string();
         let concours_power_of_2 = (concours_teams.len() as f64).log2().ceil().exp2() as i32;
-        conn.execute(
+        tx.execute(
             r#"
             INSERT INTO brackets (id, tournament_id, name, is_consolante, size, is_complete, created_at)
             VALUES (?1, ?2, 'A', 0, ?3, 0, ?4)
@@ -252,13 +318,13 @@ pub fn generate_brackets(db: State<Database>, tournament_id: String) -> Result<(
         )
         .map_err(|e| e.to_string())?;
 
-        create_bracket_matches(&conn, &concours_id, &concours_teams, number_of_courts)?;
+        create_bracket_matches(&tx, &concours_id, &concours_teams, &seeding, &courts, true, 0)?;
 
         // Create Consolante bracket if there are enough teams
         if consolante_teams.len() >= 2 {
             let consolante_id = Uuid::new_v4().to_string();
             let consolante_power_of_2 = (consolante_teams.len() as f64).log2().ceil().exp2() as i32;
-            conn.execute(
+            tx.execute(
                 r#"
                 INSERT INTO brackets (id, tournament_id, name, is_consolante, size, is_complete, created_at)
                 VALUES (?1, ?2, 'AA', 1, ?3, 0, ?4)
@@ -267,10 +333,12 @@ pub fn generate_brackets(db: State<Database>, tournament_id: String) -> Result<(
             )
             .map_err(|e| e.to_string())?;
 
-            create_bracket_matches(&conn, &consolante_id, &consolante_teams, number_of_courts)?;
+            // Both brackets start together, so the Consolante takes the courts after the Concours
+            let court_offset = concours_power_of_2 as usize / 2;
+            create_bracket_matches(&tx, &consolante_id, &consolante_teams, &seeding, &courts, false, court_offset)?;
         }
 
-        return Ok(());
+        return tx.commit().map_err(|e| e.to_string());
     }
 
     // Original behavior: advance_all or no consolante
@@ -278,18 +346,23 @@ pub fn generate_brackets(db: State<Database>, tournament_id: String) -> Result<(
     let advancing_count = if advance_all {
         teams.len()
     } else {
-        advance_count.unwrap_or(bracket_size) as usize
+        std::cmp::min(advance_count.unwrap_or(bracket_size) as usize, teams.len())
     };
 
+    // Brackets of bracket_size teams, in rank order
+    let ranges: Vec<(usize, usize)> = (0..advancing_count)
+        .step_by(bracket_size as usize)
+        .map(|start| (start, std::cmp::min(start + bracket_size as usize, advancing_count)))
+        .collect();
+    draw_ties_at_cuts(&tx, &tournament_id, &mut teams, &ranks, &cut_lines(&ranges, &seeding))?;
+
     let advancing_teams: Vec<&Team> = teams.iter().take(advancing_count).collect();
 
     // Create brackets based on bracket size
     let bracket_names = ["A", "B", "C", "D", "E", "F", "G", "H"];
-    let mut bracket_idx = 0;
-    let mut start_idx = 0;
+    let mut court_offset = 0;
 
-    while start_idx < advancing_teams.len() {
-        let end_idx = std::cmp::min(start_idx + bracket_size as usize, advancing_teams.len());
+    for (bracket_idx, &(start_idx, end_idx)) in ranges.iter().enumerate() {
         let bracket_teams: Vec<&Team> = advancing_teams[start_idx..end_idx].to_vec();
 
         let bracket_name = if bracket_idx < bracket_names.len() {
@@ -302,7 +375,7 @@ pub fn generate_brackets(db: State<Database>, tournament_id: String) -> Result<(
         let bracket_id = Uuid::new_v4().to_string();
         // Store power-of-2 bracket size for proper round calculation in UI
         let power_of_2_size = (bracket_teams.len() as f64).log2().ceil().exp2() as i32;
-        conn.execute(
+        tx.execute(
             r#"
             INSERT INTO brackets (id, tournament_id, name, is_consolante, size, is_complete, created_at)
             VALUES (?1, ?2, ?3, 0, ?4, 0, ?5)
@@ -312,20 +385,48 @@ pub fn generate_brackets(db: State<Database>, tournament_id: String) -> Result<(
         .map_err(|e| e.to_string())?;
 
         // Create matches for this bracket with random pairing and court assignment
-        create_bracket_matches(&conn, &bracket_id, &bracket_teams, number_of_courts)?;
-
-        start_idx = end_idx;
-        bracket_idx += 1;
+        // Brackets are played side by side: only the first one gets the honour courts,
+        // and each starts on the courts after the previous one
+        create_bracket_matches(&tx, &bracket_id, &bracket_teams, &seeding, &courts, bracket_idx == 0, court_offset)?;
+        court_offset += power_of_2_size as usize / 2;
     }
 
-    Ok(())
+    tx.commit().map_err(|e| e.to_string())
+}
+
+/// Where tied teams must be drawn apart when brackets are formed from the
+/// ranking: the end of each bracket, and inside it the line between the
+/// top-ranked teams that get a first-round BYE and the others. With standard
+/// seeding every seed has its own place, so every position is a cut; with a
+/// random placement only the ends of the brackets are.
+fn cut_lines(ranges: &[(usize, usize)], seeding: &str) -> Vec<usize> {
+    let mut cuts = Vec::new();
+    for &(start, end) in ranges {
+        let num_teams = end - start;
+        if num_teams == 0 {
+            continue;
+        }
+        match seeding {
+            "standard" => cuts.extend(start + 1..end),
+            "random" => {}
+            _ => {
+                let power_of_2 = (num_teams as f64).log2().ceil().exp2() as usize;
+                cuts.push(start + power_of_2 - num_teams);
+            }
+        }
+        cuts.push(end);
+    }
+    cuts
 }
 
 fn create_bracket_matches(
     conn: &rusqlite::Connection,
     bracket_id: &str,
     teams: &[&Team],
-    number_of_courts: i32,
+    seeding: &str,
+    courts: &CourtPlan,
+    honour_courts: bool,
+    court_offset: usize,
 ) -> Result<(), String> {
     let num_teams = teams.len();
 
@@ -335,19 +436,19 @@ fn create_bracket_matches(
 
     // Calculate bracket size (next power of 2)
     let bracket_size = (num_teams as f64).log2().ceil().exp2() as usize;
-    let num_byes = bracket_size - num_teams;
     let num_rounds = (bracket_size as f64).log2() as i32;
     let first_round_match_count = bracket_size / 2;
 
     // Teams are already sorted by rank (from standings)
-    // Top-ranked teams get BYEs, remaining teams play in round 1
-    let bye_teams: Vec<&Team> = teams.iter().take(num_byes).cloned().collect();
-    let playing_teams: Vec<&Team> = teams.iter().skip(num_byes).cloned().collect();
-
-    // Shuffle playing teams randomly for first round pairing
-    let mut rng = rand::thread_rng();
-    let mut shuffled_playing: Vec<&Team> = playing_teams;
-    shuffled_playing.shuffle(&mut rng);
+    // Top-ranked teams get BYEs; the others are drawn for round 1, or all
+    // placed by seed with standard seeding
+    let inputs = BracketDrawInputs {
+        team_ids: teams.iter().map(|t| t.id.clone()).collect(),
+        seeded: seeding != "random",
+        standard: seeding == "standard",
+    };
+    let seed = new_seed();
+    let first_round = draw_first_round(&inputs, &mut seeded_rng(seed));
 
     // Create match IDs for all rounds
     let mut match_ids: Vec<Vec<String>> = Vec::new();
@@ -362,34 +463,23 @@ fn create_bracket_matches(
 
     // Insert first round matches
     // Some are BYE matches (team vs BYE), some are real matches
-    let mut playing_idx = 0;
-    let mut bye_idx = 0;
-
     for match_idx in 0..first_round_match_count {
         let match_id = &match_ids[0][match_idx];
-        let court_number = (match_idx as i32 % number_of_courts) + 1;
-
-        // Determine if this is a BYE match
-        // BYE matches are distributed: first num_byes matches have a BYE
-        let is_bye_match = match_idx < num_byes;
+        let (court_number, slot_number) = bracket_court(
+            courts,
+            court_offset + match_idx,
+            first_round_match_count,
+            honour_courts,
+        );
 
-        let (team1_id, team2_id, is_bye) = if is_bye_match {
-            // BYE match: top-seeded team gets a bye
-            let team = bye_teams[bye_idx];
-            bye_idx += 1;
-            (Some(team.id.clone()), None, true)
-        } else {
-            // Real match: two teams play
-            let t1 = shuffled_playing[playing_idx];
-            let t2 = shuffled_playing[playing_idx + 1];
-            playing_idx += 2;
-            (Some(t1.id.clone()), Some(t2.id.clone()), false)
-        };
+        // One BYE match per top-seeded team
+        let (team1_id, team2_id) = &first_round[match_idx];
+        let is_bye = team2_id.is_none();
 
         conn.execute(
             r#"
-            INSERT INTO bracket_matches (id, bracket_id, round_number, match_number, court_number, team1_id, team2_id, next_match_id, is_bye)
-            VALUES (?1, ?2, 1, ?3, ?4, ?5, ?6, NULL, ?7)
+            INSERT INTO bracket_matches (id, bracket_id, round_number, match_number, court_number, team1_id, team2_id, next_match_id, is_bye, slot_number)
+            VALUES (?1, ?2, 1, ?3, ?4, ?5, ?6, NULL, ?7, ?8)
             "#,
             params![
                 match_id,
@@ -398,7 +488,8 @@ fn create_bracket_matches(
                 court_number,
                 team1_id,
                 team2_id,
-                if is_bye { 1 } else { 0 }
+                if is_bye { 1 } else { 0 },
+                slot_number
             ],
         )
         .map_err(|e| format!("Failed to insert first round match: {}", e))?;
@@ -408,11 +499,17 @@ fn create_bracket_matches(
     for round_idx in 1..match_ids.len() {
         let round_number = (round_idx + 1) as i32;
         for (match_idx, match_id) in match_ids[round_idx].iter().enumerate() {
-            let court_number = (match_idx as i32 % number_of_courts) + 1;
+            let (court_number, slot_number) =
+                bracket_court(
+                    courts,
+                    (court_offset >> round_idx) + match_idx,
+                    match_ids[round_idx].len(),
+                    honour_courts,
+                );
             conn.execute(
                 r#"
-                INSERT INTO bracket_matches (id, bracket_id, round_number, match_number, court_number, team1_id, team2_id, next_match_id, is_bye)
-                VALUES (?1, ?2, ?3, ?4, ?5, NULL, NULL, NULL, 0)
+                INSERT INTO bracket_matches (id, bracket_id, round_number, match_number, court_number, team1_id, team2_id, next_match_id, is_bye, slot_number)
+                VALUES (?1, ?2, ?3, ?4, ?5, NULL, NULL, NULL, 0, ?6)
                 "#,
                 params![
                     match_id,
@@ -420,6 +517,7 @@ fn create_bracket_matches(
                     round_number,
                     match_idx as i32 + 1,
                     court_number,
+                    slot_number,
                 ],
             )
             .map_err(|e| format!("Failed to insert round {} match: {}", round_number, e))?;
@@ -441,8 +539,20 @@ fn create_bracket_matches(
         }
     }
 
-    // Auto-advance BYE matches (score 13-7)
-    for match_idx in 0..num_byes {
+    // Auto-advance BYE matches (scored by the tournament's BYE policy)
+    let tournament_id: String = conn
+        .query_row(
+            "SELECT tournament_id FROM brackets WHERE id = ?1",
+            params![bracket_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to query bracket: {}", e))?;
+    let bye_scoring = get_bye_scoring(conn, &tournament_id)?;
+
+    record_draw(conn, &tournament_id, "bracket", Some(bracket_id), seed, &inputs, &first_round)?;
+
+    let bye_matches = first_round.iter().enumerate().filter(|(_, (_, team2_id))| team2_id.is_none());
+    for (match_idx, _) in bye_matches {
         let match_id = &match_ids[0][match_idx];
 
         let team1_id: Option<String> = conn
@@ -454,10 +564,9 @@ fn create_bracket_matches(
             .map_err(|e| format!("Failed to query BYE match: {}", e))?;
 
         if let Some(winner) = &team1_id {
-            // BYE score is 13-7
             conn.execute(
-                "UPDATE bracket_matches SET winner_id = ?2, team1_score = 13, team2_score = 7 WHERE id = ?1",
-                params![match_id, winner],
+                "UPDATE bracket_matches SET winner_id = ?2, team1_score = ?3, team2_score = ?4 WHERE id = ?1",
+                params![match_id, winner, bye_scoring.winner_score, bye_scoring.loser_score],
             )
             .map_err(|e| format!("Failed to set BYE winner: {}", e))?;
 
@@ -492,9 +601,58 @@ pub fn update_match_score(
     match_id: String,
     team1_score: i32,
     team2_score: i32,
-) -> Result<(), String> {
+) -> Result<(), ScoreError> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
+    if has_ends(&conn, &Scored::Match(match_id.clone()))? {
+        return Err("This match's score comes from its ends: record or undo an end instead.".to_string().into());
+    }
+
+    save_match_score(&conn, &match_id, team1_score, team2_score)
+}
+
+/// Checks and records the score of a bracket match, then plays the walkovers
+/// the winner's advance leads to.
+pub fn save_match_score(
+    conn: &rusqlite::Connection,
+    match_id: &str,
+    team1_score: i32,
+    team2_score: i32,
+) -> Result<(), ScoreError> {
+    let tournament_id: String = conn
+        .query_row(
+            r#"
+            SELECT b.tournament_id
+            FROM bracket_matches m
+            JOIN brackets b ON m.bracket_id = b.id
+            WHERE m.id = ?1
+            "#,
+            params![match_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    // A tie would otherwise send team 2 through
+    validate_score(&get_game_rules(conn, &tournament_id)?, team1_score, team2_score)?;
+
+    let bracket_id = record_match_result(conn, match_id, team1_score, team2_score, false)?;
+
+    // The winner may move up against a team that has withdrawn
+    apply_walkovers(conn, &bracket_id)?;
+
+    Ok(())
+}
+
+/// Scores a bracket match and advances the winner through `next_match_id`,
+/// then completes the bracket or opens the consolante when due. Returns the
+/// bracket of the match.
+fn record_match_result(
+    conn: &rusqlite::Connection,
+    match_id: &str,
+    team1_score: i32,
+    team2_score: i32,
+    is_forfeit: bool,
+) -> Result<String, String> {
     // Get match details
     let (bracket_id, team1_id, team2_id, next_match_id, match_number, round_number): (
         String,
@@ -518,10 +676,22 @@ pub fn update_match_score(
         team2_id.clone()
     };
 
-    // Update match
+    // Update match; a walkover is not played, so it has no end time
     conn.execute(
-        "UPDATE bracket_matches SET team1_score = ?2, team2_score = ?3, winner_id = ?4 WHERE id = ?1",
-        params![match_id, team1_score, team2_score, winner_id],
+        r#"
+        UPDATE bracket_matches SET
+            team1_score = ?2, team2_score = ?3, winner_id = ?4, is_forfeit = ?5,
+            ended_at = CASE WHEN ?5 = 1 THEN ended_at ELSE COALESCE(ended_at, ?6) END
+        WHERE id = ?1
+        "#,
+        params![
+            match_id,
+            team1_score,
+            team2_score,
+            winner_id,
+            if is_forfeit { 1 } else { 0 },
+            Utc::now().to_rfc3339()
+        ],
     )
     .map_err(|e| e.to_string())?;
 
@@ -566,7 +736,136 @@ pub fn update_match_score(
 
     // Check if first round of a main bracket is complete - create consolante if needed
     if round_number == 1 {
-        check_and_create_consolante(&conn, &bracket_id)?;
+        check_and_create_consolante(conn, &bracket_id)?;
+    }
+
+    Ok(bracket_id)
+}
+
+/// Gives every match against a withdrawn team to its opponent as a walkover,
+/// once both teams of the match are known. When both teams withdrew, the match
+/// is a double forfeit that nobody wins, and the team due to meet its winner
+/// moves on as on a BYE. The winner can move up against another withdrawn
+/// team, so this runs until no such match is left.
+pub fn apply_walkovers(conn: &rusqlite::Connection, bracket_id: &str) -> Result<(), String> {
+    let tournament_id: String = conn
+        .query_row(
+            "SELECT tournament_id FROM brackets WHERE id = ?1",
+            params![bracket_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    let forfeit = get_forfeit_scoring(conn, &tournament_id)?;
+    let bye_scoring = get_bye_scoring(conn, &tournament_id)?;
+
+    loop {
+        let walkover = conn.query_row(
+            r#"
+            SELECT m.id, t1.is_active, t2.is_active
+            FROM bracket_matches m
+            JOIN teams t1 ON t1.id = m.team1_id
+            JOIN teams t2 ON t2.id = m.team2_id
+            WHERE m.bracket_id = ?1 AND m.winner_id IS NULL AND m.is_forfeit = 0
+              AND (t1.is_active = 0 OR t2.is_active = 0)
+            ORDER BY m.round_number, m.match_number
+            LIMIT 1
+            "#,
+            params![bracket_id],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i32>(1)? != 0,
+                    row.get::<_, i32>(2)? != 0,
+                ))
+            },
+        );
+
+        match walkover {
+            Ok((match_id, false, false)) => {
+                void_match(conn, &match_id, Some(forfeit.loser_score))?;
+                continue;
+            }
+            Ok((match_id, team1_active, team2_active)) => {
+                let score = |active: bool| if active { forfeit.winner_score } else { forfeit.loser_score };
+                record_match_result(conn, &match_id, score(team1_active), score(team2_active), true)?;
+                continue;
+            }
+            Err(rusqlite::Error::QueryReturnedNoRows) => {}
+            Err(e) => return Err(e.to_string()),
+        }
+
+        // A match fed by a void match only ever gets one team, if any
+        let orphan = conn.query_row(
+            r#"
+            SELECT m.id, COALESCE(t.is_active, 0)
+            FROM bracket_matches m
+            LEFT JOIN teams t ON t.id = COALESCE(m.team1_id, m.team2_id)
+            WHERE m.bracket_id = ?1 AND m.winner_id IS NULL AND m.is_forfeit = 0
+              AND EXISTS (SELECT 1 FROM bracket_matches f
+                          WHERE f.next_match_id = m.id AND f.winner_id IS NULL AND f.is_forfeit = 1)
+              AND NOT EXISTS (SELECT 1 FROM bracket_matches f
+                              WHERE f.next_match_id = m.id AND f.winner_id IS NULL AND f.is_forfeit = 0)
+            ORDER BY m.round_number, m.match_number
+            LIMIT 1
+            "#,
+            params![bracket_id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, i32>(1)? != 0)),
+        );
+
+        match orphan {
+            Ok((match_id, true)) => {
+                // Laid out like a first-round BYE: the team on top, nobody below
+                conn.execute(
+                    r#"
+                    UPDATE bracket_matches SET
+                        team1_id = COALESCE(team1_id, team2_id), team2_id = NULL, is_bye = 1
+                    WHERE id = ?1
+                    "#,
+                    params![match_id],
+                )
+                .map_err(|e| e.to_string())?;
+                record_match_result(conn, &match_id, bye_scoring.winner_score, bye_scoring.loser_score, false)?;
+                conn.execute(
+                    "UPDATE bracket_matches SET ended_at = NULL WHERE id = ?1",
+                    params![match_id],
+                )
+                .map_err(|e| e.to_string())?;
+            }
+            // No team left to reach it, or only a withdrawn one
+            Ok((match_id, false)) => void_match(conn, &match_id, None)?,
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(()),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+}
+
+/// Closes a match that nobody can win, leaving its place in the next round
+/// empty: a double forfeit scored `score` each, or a match no team is left to
+/// play.
+fn void_match(conn: &rusqlite::Connection, match_id: &str, score: Option<i32>) -> Result<(), String> {
+    let (bracket_id, round_number, is_final): (String, i32, bool) = conn
+        .query_row(
+            "SELECT bracket_id, round_number, next_match_id IS NULL FROM bracket_matches WHERE id = ?1",
+            params![match_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "UPDATE bracket_matches SET team1_score = ?2, team2_score = ?2, is_forfeit = 1 WHERE id = ?1",
+        params![match_id, score],
+    )
+    .map_err(|e| e.to_string())?;
+
+    if is_final {
+        conn.execute(
+            "UPDATE brackets SET is_complete = 1 WHERE id = ?1",
+            params![bracket_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    if round_number == 1 {
+        check_and_create_consolante(conn, &bracket_id)?;
     }
 
     Ok(())
@@ -588,11 +887,11 @@ fn check_and_create_consolante(conn: &rusqlite::Connection, bracket_id: &str) ->
     }
 
     // Check if tournament has consolante enabled and get advance_all setting
-    let (has_consolante, number_of_courts, advance_all): (bool, i32, bool) = conn
+    let (has_consolante, advance_all): (bool, bool) = conn
         .query_row(
-            "SELECT has_consolante, number_of_courts, advance_all FROM tournaments WHERE id = ?1",
+            "SELECT has_consolante, advance_all FROM tournaments WHERE id = ?1",
             params![tournament_id],
-            |row| Ok((row.get::<_, i32>(0)? != 0, row.get(1)?, row.get::<_, i32>(2)? != 0)),
+            |row| Ok((row.get::<_, i32>(0)? != 0, row.get::<_, i32>(1)? != 0)),
         )
         .map_err(|e| e.to_string())?;
 
@@ -627,7 +926,7 @@ fn check_and_create_consolante(conn: &rusqlite::Connection, bracket_id: &str) ->
             r#"
             SELECT COUNT(*)
             FROM bracket_matches
-            WHERE bracket_id = ?1 AND round_number = 1 AND winner_id IS NULL AND is_bye = 0
+            WHERE bracket_id = ?1 AND round_number = 1 AND winner_id IS NULL AND is_forfeit = 0 AND is_bye = 0
             "#,
             params![bracket_id],
             |row| row.get(0),
@@ -638,14 +937,21 @@ fn check_and_create_consolante(conn: &rusqlite::Connection, bracket_id: &str) ->
         return Ok(());
     }
 
-    // Get first round losers (non-BYE matches only)
+    // Get first round losers (non-BYE matches only), leaving out withdrawn teams
     let mut stmt = conn
         .prepare(
             r#"
-            SELECT
-                CASE WHEN winner_id = team1_id THEN team2_id ELSE team1_id END as loser_id
-            FROM bracket_matches
-            WHERE bracket_id = ?1 AND round_number = 1 AND is_bye = 0 AND winner_id IS NOT NULL
+            SELECT m.loser_id
+            FROM (
+                SELECT
+                    match_number,
+                    CASE WHEN winner_id = team1_id THEN team2_id ELSE team1_id END as loser_id
+                FROM bracket_matches
+                WHERE bracket_id = ?1 AND round_number = 1 AND is_bye = 0 AND winner_id IS NOT NULL
+            ) m
+            JOIN teams t ON t.id = m.loser_id
+            WHERE t.is_active = 1
+            ORDER BY m.match_number
             "#,
         )
         .map_err(|e| e.to_string())?;
@@ -677,16 +983,100 @@ fn check_and_create_consolante(conn: &rusqlite::Connection, bracket_id: &str) ->
     .map_err(|e| e.to_string())?;
 
     // Create matches for consolante bracket with random pairing of losers
-    create_consolante_matches(conn, &consolante_id, &loser_ids, number_of_courts)?;
+    sync_courts(conn, &tournament_id)?;
+    let courts = load_court_plan(conn, &tournament_id)?;
+    create_consolante_matches(conn, &consolante_id, &loser_ids, &courts)?;
 
     Ok(())
 }
 
+/// Inputs of a bracket draw: the teams in seeding order, whether the best
+/// seeds get the BYEs or the BYEs are drawn as well, and whether every seed
+/// takes its standard position instead of being drawn.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BracketDrawInputs {
+    team_ids: Vec<String>,
+    seeded: bool,
+    #[serde(default)]
+    standard: bool,
+}
+
+/// Draws the first round of a bracket: the BYE matches first, then the drawn
+/// games. With standard seeding nothing is drawn: each match is the one of
+/// the standard layout, the BYEs going to the top seeds.
+fn draw_first_round(inputs: &BracketDrawInputs, rng: &mut DrawRng) -> Vec<(String, Option<String>)> {
+    let bracket_size = (inputs.team_ids.len() as f64).log2().ceil().exp2() as usize;
+    let num_byes = bracket_size - inputs.team_ids.len();
+
+    if inputs.standard {
+        return standard_positions(bracket_size)
+            .chunks(2)
+            .map(|pair| (inputs.team_ids[pair[0]].clone(), inputs.team_ids.get(pair[1]).cloned()))
+            .collect();
+    }
+
+    let mut team_ids: Vec<&String> = inputs.team_ids.iter().collect();
+    if inputs.seeded {
+        team_ids[num_byes..].shuffle(rng);
+    } else {
+        team_ids.shuffle(rng);
+    }
+
+    let (bye_teams, playing_teams) = team_ids.split_at(num_byes);
+    bye_teams
+        .iter()
+        .map(|id| ((*id).clone(), None))
+        .chain(
+            playing_teams
+                .chunks(2)
+                .map(|pair| (pair[0].clone(), Some(pair[1].clone()))),
+        )
+        .collect()
+}
+
+/// Seeds (0 being the best) in bracket order for a bracket of `size` teams,
+/// e.g. 1 v 8, 4 v 5, 2 v 7, 3 v 6 for eight. Each round pairs the best
+/// remaining seed with the worst, so seeds 1 and 2 can only meet in the final.
+fn standard_positions(size: usize) -> Vec<usize> {
+    let mut positions = vec![0];
+    while positions.len() < size {
+        let count = positions.len() * 2;
+        positions = positions.iter().flat_map(|&seed| [seed, count - 1 - seed]).collect();
+    }
+    positions
+}
+
+/// Draws a bracket's first round again from the inputs stored with its draw.
+pub fn replay_bracket_draw(inputs: &serde_json::Value, rng: &mut DrawRng) -> Result<serde_json::Value, String> {
+    let inputs: BracketDrawInputs = serde_json::from_value(inputs.clone()).map_err(|e| e.to_string())?;
+    serde_json::to_value(draw_first_round(&inputs, rng)).map_err(|e| e.to_string())
+}
+
+/// Court and wave (slot) for a bracket match at `position` among the matches
+/// played at the same time. With `honour_courts`, a round that fits on the
+/// reserved courts (the final, or the semi-finals with two honour courts) is
+/// played there; every other match rotates over the regular courts, spilling
+/// into later waves when there are more matches than courts.
+fn bracket_court(
+    courts: &CourtPlan,
+    position: usize,
+    matches_in_round: usize,
+    honour_courts: bool,
+) -> (i32, i32) {
+    if honour_courts && !courts.reserved.is_empty() && matches_in_round <= courts.reserved.len() {
+        return (courts.reserved[position % courts.reserved.len()], 1);
+    }
+    let rotation = courts.rotation();
+    let slot_number = (position / rotation.len()) as i32 + 1;
+    (rotation[position % rotation.len()], slot_number)
+}
+
 fn create_consolante_matches(
     conn: &rusqlite::Connection,
     bracket_id: &str,
     team_ids: &[String],
-    number_of_courts: i32,
+    courts: &CourtPlan,
 ) -> Result<(), String> {
     let num_teams = team_ids.len();
 
@@ -700,14 +1090,14 @@ fn create_consolante_matches(
     let num_rounds = (bracket_size as f64).log2() as i32;
     let first_round_match_count = bracket_size / 2;
 
-    // Consolante teams are already losers, no seeding - shuffle all
-    let mut rng = rand::thread_rng();
-    let mut shuffled: Vec<&String> = team_ids.iter().collect();
-    shuffled.shuffle(&mut rng);
-
-    // First num_byes teams get BYEs (randomly selected after shuffle)
-    let bye_teams: Vec<&String> = shuffled.iter().take(num_byes).cloned().collect();
-    let playing_teams: Vec<&String> = shuffled.iter().skip(num_byes).cloned().collect();
+    // Consolante teams are already losers, no seeding - the BYEs are drawn too
+    let inputs = BracketDrawInputs {
+        team_ids: team_ids.to_vec(),
+        seeded: false,
+        standard: false,
+    };
+    let seed = new_seed();
+    let first_round = draw_first_round(&inputs, &mut seeded_rng(seed));
 
     // Create match IDs for all rounds
     let mut match_ids: Vec<Vec<String>> = Vec::new();
@@ -721,32 +1111,20 @@ fn create_consolante_matches(
     }
 
     // Insert first round matches
-    let mut playing_idx = 0;
-    let mut bye_idx = 0;
-
     for match_idx in 0..first_round_match_count {
         let match_id = &match_ids[0][match_idx];
-        let court_number = (match_idx as i32 % number_of_courts) + 1;
-
-        let is_bye_match = match_idx < num_byes;
+        let (court_number, slot_number) =
+            bracket_court(courts, match_idx, first_round_match_count, false);
 
-        let (team1_id, team2_id, is_bye) = if is_bye_match {
-            let team = bye_teams[bye_idx];
-            bye_idx += 1;
-            (Some(team.clone()), None, true)
-        } else {
-            let t1 = playing_teams[playing_idx];
-            let t2 = playing_teams[playing_idx + 1];
-            playing_idx += 2;
-            (Some(t1.clone()), Some(t2.clone()), false)
-        };
+        let (team1_id, team2_id) = &first_round[match_idx];
+        let is_bye = team2_id.is_none();
 
         conn.execute(
             r#"
-            INSERT INTO bracket_matches (id, bracket_id, round_number, match_number, court_number, team1_id, team2_id, next_match_id, is_bye)
-            VALUES (?1, ?2, 1, ?3, ?4, ?5, ?6, NULL, ?7)
+            INSERT INTO bracket_matches (id, bracket_id, round_number, match_number, court_number, team1_id, team2_id, next_match_id, is_bye, slot_number)
+            VALUES (?1, ?2, 1, ?3, ?4, ?5, ?6, NULL, ?7, ?8)
             "#,
-            params![match_id, bracket_id, match_idx as i32 + 1, court_number, team1_id, team2_id, if is_bye { 1 } else { 0 }],
+            params![match_id, bracket_id, match_idx as i32 + 1, court_number, team1_id, team2_id, if is_bye { 1 } else { 0 }, slot_number],
         )
         .map_err(|e| e.to_string())?;
     }
@@ -755,13 +1133,14 @@ fn create_consolante_matches(
     for round_idx in 1..match_ids.len() {
         let round_number = (round_idx + 1) as i32;
         for (match_idx, match_id) in match_ids[round_idx].iter().enumerate() {
-            let court_number = (match_idx as i32 % number_of_courts) + 1;
+            let (court_number, slot_number) =
+                bracket_court(courts, match_idx, match_ids[round_idx].len(), false);
             conn.execute(
                 r#"
-                INSERT INTO bracket_matches (id, bracket_id, round_number, match_number, court_number, team1_id, team2_id, next_match_id, is_bye)
-                VALUES (?1, ?2, ?3, ?4, ?5, NULL, NULL, NULL, 0)
+                INSERT INTO bracket_matches (id, bracket_id, round_number, match_number, court_number, team1_id, team2_id, next_match_id, is_bye, slot_number)
+                VALUES (?1, ?2, ?3, ?4, ?5, NULL, NULL, NULL, 0, ?6)
                 "#,
-                params![match_id, bracket_id, round_number, match_idx as i32 + 1, court_number],
+                params![match_id, bracket_id, round_number, match_idx as i32 + 1, court_number, slot_number],
             )
             .map_err(|e| e.to_string())?;
         }
@@ -782,7 +1161,18 @@ fn create_consolante_matches(
         }
     }
 
-    // Auto-advance BYE matches (score 13-7)
+    // Auto-advance BYE matches (scored by the tournament's BYE policy)
+    let tournament_id: String = conn
+        .query_row(
+            "SELECT tournament_id FROM brackets WHERE id = ?1",
+            params![bracket_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    let bye_scoring = get_bye_scoring(conn, &tournament_id)?;
+
+    record_draw(conn, &tournament_id, "bracket", Some(bracket_id), seed, &inputs, &first_round)?;
+
     for match_idx in 0..num_byes {
         let match_id = &match_ids[0][match_idx];
 
@@ -796,8 +1186,8 @@ fn create_consolante_matches(
 
         if let Some(winner) = &team1_id {
             conn.execute(
-                "UPDATE bracket_matches SET winner_id = ?2, team1_score = 13, team2_score = 7 WHERE id = ?1",
-                params![match_id, winner],
+                "UPDATE bracket_matches SET winner_id = ?2, team1_score = ?3, team2_score = ?4 WHERE id = ?1",
+                params![match_id, winner, bye_scoring.winner_score, bye_scoring.loser_score],
             )
             .map_err(|e| e.to_string())?;
 
@@ -824,3 +1214,59 @@ fn create_consolante_matches(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::draws::seeded_rng;
+
+    /// Half of the bracket (0 top, 1 bottom) a team lands in
+    fn half(first_round: &[(String, Option<String>)], team_id: &str) -> usize {
+        let index = first_round
+            .iter()
+            .position(|(t1, t2)| t1 == team_id || t2.as_deref() == Some(team_id))
+            .unwrap();
+        index * 2 / first_round.len()
+    }
+
+    #[test]
+    fn standard_positions_put_the_top_two_seeds_in_opposite_halves() {
+        assert_eq!(standard_positions(8), vec![0, 7, 3, 4, 1, 6, 2, 5]);
+        for size in [2, 4, 8, 16, 32] {
+            let positions = standard_positions(size);
+            let mut sorted = positions.clone();
+            sorted.sort();
+            assert_eq!(sorted, (0..size).collect::<Vec<_>>());
+
+            let slot = |seed: usize| positions.iter().position(|&s| s == seed).unwrap();
+            assert!(slot(0) < size / 2 && slot(1) >= size / 2, "size {}", size);
+            // Every first-round match pairs a seed with its mirror
+            for pair in positions.chunks(2) {
+                assert_eq!(pair[0] + pair[1], size - 1);
+            }
+        }
+    }
+
+    #[test]
+    fn standard_draw_gives_the_byes_to_the_top_seeds() {
+        for teams in [5usize, 6, 7, 8, 12, 13, 16] {
+            let inputs = BracketDrawInputs {
+                team_ids: (0..teams).map(|seed| seed.to_string()).collect(),
+                seeded: true,
+                standard: true,
+            };
+            let first_round = draw_first_round(&inputs, &mut seeded_rng(1));
+            let bracket_size = teams.next_power_of_two();
+            assert_eq!(first_round.len(), bracket_size / 2);
+            assert_ne!(half(&first_round, "0"), half(&first_round, "1"), "{} teams", teams);
+
+            let mut byes: Vec<usize> = first_round
+                .iter()
+                .filter(|(_, t2)| t2.is_none())
+                .map(|(t1, _)| t1.parse().unwrap())
+                .collect();
+            byes.sort();
+            assert_eq!(byes, (0..bracket_size - teams).collect::<Vec<_>>(), "{} teams", teams);
+        }
+    }
+}
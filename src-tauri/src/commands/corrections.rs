@@ -0,0 +1,245 @@
+use crate::commands::qualifying::rebuild_standings;
+use crate::commands::scoring::{validate_score, ScoreError};
+use crate::commands::tournaments::get_game_rules;
+use crate::db::Database;
+use crate::models::{CorrectionImpact, RankChange, ScoreCorrection};
+use chrono::Utc;
+use rusqlite::params;
+use std::collections::HashMap;
+use tauri::State;
+use uuid::Uuid;
+
+fn correction_from_row(row: &rusqlite::Row) -> rusqlite::Result<ScoreCorrection> {
+    Ok(ScoreCorrection {
+        id: row.get(0)?,
+        tournament_id: row.get(1)?,
+        game_id: row.get(2)?,
+        round_number: row.get(3)?,
+        old_team1_score: row.get(4)?,
+        old_team2_score: row.get(5)?,
+        new_team1_score: row.get(6)?,
+        new_team2_score: row.get(7)?,
+        reason: row.get(8)?,
+        created_at: row.get(9)?,
+    })
+}
+
+/// Rank and elimination of every team of a tournament
+fn ranking_snapshot(
+    conn: &rusqlite::Connection,
+    tournament_id: &str,
+) -> Result<HashMap<String, (i32, bool)>, String> {
+    let mut stmt = conn
+        .prepare("SELECT team_id, rank, is_eliminated FROM team_standings WHERE tournament_id = ?1")
+        .map_err(|e| e.to_string())?;
+
+    let snapshot = stmt
+        .query_map(params![tournament_id], |row| {
+            Ok((row.get(0)?, (row.get(1)?, row.get::<_, i32>(2)? != 0)))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<HashMap<_, _>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(snapshot)
+}
+
+/// Corrects the score of a game in a completed round. The correction is
+/// logged with its reason, the standings are rebuilt, and the report tells
+/// which later pairings and bracket seeding were decided on the old score.
+/// Nothing already drawn is changed: the director decides what to redo.
+#[tauri::command]
+pub fn correct_game_score(
+    db: State<Database>,
+    game_id: String,
+    team1_score: i32,
+    team2_score: i32,
+    reason: String,
+) -> Result<CorrectionImpact, ScoreError> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let reason = reason.trim().to_string();
+    if reason.is_empty() {
+        return Err("Give the reason for the correction.".to_string().into());
+    }
+
+    let (tournament_id, pairing_method, round_number, is_complete, is_bye, old_team1_score, old_team2_score): (
+        String,
+        String,
+        i32,
+        bool,
+        bool,
+        Option<i32>,
+        Option<i32>,
+    ) = conn
+        .query_row(
+            r#"
+            SELECT r.tournament_id, t.pairing_method, r.round_number, r.is_complete, g.is_bye, g.team1_score, g.team2_score
+            FROM qualifying_games g
+            JOIN qualifying_rounds r ON g.round_id = r.id
+            JOIN tournaments t ON r.tournament_id = t.id
+            WHERE g.id = ?1
+            "#,
+            params![game_id],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get::<_, i32>(3)? != 0,
+                    row.get::<_, i32>(4)? != 0,
+                    row.get(5)?,
+                    row.get(6)?,
+                ))
+            },
+        )
+        .map_err(|e| e.to_string())?;
+
+    if is_bye {
+        return Err("A BYE has no score to correct.".to_string().into());
+    }
+    if !is_complete {
+        return Err("This round is still in play: enter the score as usual.".to_string().into());
+    }
+
+    validate_score(&get_game_rules(&conn, &tournament_id)?, team1_score, team2_score)?;
+
+    let before = ranking_snapshot(&conn, &tournament_id)?;
+
+    // Roll the score back if the correction cannot be logged or counted
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+
+    tx.execute(
+        "UPDATE qualifying_games SET team1_score = ?2, team2_score = ?3 WHERE id = ?1",
+        params![game_id, team1_score, team2_score],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let correction = ScoreCorrection {
+        id: Uuid::new_v4().to_string(),
+        tournament_id: tournament_id.clone(),
+        game_id,
+        round_number,
+        old_team1_score,
+        old_team2_score,
+        new_team1_score: team1_score,
+        new_team2_score: team2_score,
+        reason,
+        created_at: Utc::now().to_rfc3339(),
+    };
+
+    tx.execute(
+        r#"
+        INSERT INTO score_corrections (id, tournament_id, game_id, old_team1_score, old_team2_score, new_team1_score, new_team2_score, reason, created_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+        "#,
+        params![
+            correction.id,
+            correction.tournament_id,
+            correction.game_id,
+            correction.old_team1_score,
+            correction.old_team2_score,
+            correction.new_team1_score,
+            correction.new_team2_score,
+            correction.reason,
+            correction.created_at
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    rebuild_standings(&tx, &tournament_id)?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    let after = ranking_snapshot(&conn, &tournament_id)?;
+
+    let winner_changed = match (old_team1_score, old_team2_score) {
+        (Some(s1), Some(s2)) => (s1 > s2) != (team1_score > team2_score),
+        _ => true,
+    };
+
+    let mut rank_changes: Vec<RankChange> = after
+        .iter()
+        .filter_map(|(team_id, &(new_rank, _))| {
+            let (old_rank, _) = before.get(team_id).copied()?;
+            (old_rank != new_rank).then(|| RankChange {
+                team_id: team_id.clone(),
+                old_rank,
+                new_rank,
+            })
+        })
+        .collect();
+    rank_changes.sort_by_key(|c| c.new_rank);
+    let eliminations_changed = after.iter().any(|(team_id, &(_, eliminated))| {
+        before.get(team_id).map(|&(_, e)| e) != Some(eliminated)
+    });
+
+    // Swiss pairings follow the full ranking, Pool Play and Poules only who
+    // won; a round robin schedule never depends on results
+    let pairings_depend_on_it = match pairing_method.as_str() {
+        "swiss" | "swissHotel" => winner_changed || !rank_changes.is_empty(),
+        "poolPlay" | "poules" => winner_changed,
+        _ => false,
+    };
+
+    let mut affected_round_numbers = Vec::new();
+    if pairings_depend_on_it {
+        let mut stmt = conn
+            .prepare(
+                "SELECT round_number FROM qualifying_rounds WHERE tournament_id = ?1 AND round_number > ?2 ORDER BY round_number",
+            )
+            .map_err(|e| e.to_string())?;
+
+        affected_round_numbers = stmt
+            .query_map(params![tournament_id, round_number], |row| row.get(0))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<i32>, _>>()
+            .map_err(|e| e.to_string())?;
+    }
+
+    let has_brackets: bool = conn
+        .query_row(
+            "SELECT COUNT(*) > 0 FROM brackets WHERE tournament_id = ?1",
+            params![tournament_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    let brackets_affected = has_brackets && (eliminations_changed || !rank_changes.is_empty());
+
+    Ok(CorrectionImpact {
+        correction,
+        winner_changed,
+        rank_changes,
+        affected_round_numbers,
+        brackets_affected,
+    })
+}
+
+#[tauri::command]
+pub fn get_score_corrections(
+    db: State<Database>,
+    tournament_id: String,
+) -> Result<Vec<ScoreCorrection>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT c.id, c.tournament_id, c.game_id, r.round_number, c.old_team1_score, c.old_team2_score,
+                   c.new_team1_score, c.new_team2_score, c.reason, c.created_at
+            FROM score_corrections c
+            JOIN qualifying_games g ON c.game_id = g.id
+            JOIN qualifying_rounds r ON g.round_id = r.id
+            WHERE c.tournament_id = ?1
+            ORDER BY c.created_at
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let corrections = stmt
+        .query_map(params![tournament_id], correction_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(corrections)
+}
@@ -0,0 +1,200 @@
+use crate::db::Database;
+use crate::models::{Court, UpdateCourtData};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::State;
+use uuid::Uuid;
+
+/// Active courts of a tournament, split between the regular rotation and the
+/// reserved (honour) courts kept for finals and marquee matches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CourtPlan {
+    pub regular: Vec<i32>,
+    pub reserved: Vec<i32>,
+}
+
+impl CourtPlan {
+    /// Courts used for ordinary games. Falls back to the reserved courts when
+    /// every regular court is disabled.
+    pub fn rotation(&self) -> &[i32] {
+        if self.regular.is_empty() {
+            &self.reserved
+        } else {
+            &self.regular
+        }
+    }
+
+    /// Every active court, lowest number first.
+    pub fn all(&self) -> Vec<i32> {
+        let mut courts: Vec<i32> = self.regular.iter().chain(&self.reserved).copied().collect();
+        courts.sort();
+        courts
+    }
+}
+
+/// Keeps the court inventory in line with the tournament's number of courts.
+/// Missing courts are created active and unreserved, courts above the count are removed.
+pub fn sync_courts(conn: &rusqlite::Connection, tournament_id: &str) -> Result<(), String> {
+    let number_of_courts: i32 = conn
+        .query_row(
+            "SELECT number_of_courts FROM tournaments WHERE id = ?1",
+            params![tournament_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    for court_number in 1..=number_of_courts {
+        conn.execute(
+            r#"
+            INSERT INTO courts (id, tournament_id, court_number, label, is_active, is_reserved)
+            SELECT ?1, ?2, ?3, NULL, 1, 0
+            WHERE NOT EXISTS (SELECT 1 FROM courts WHERE tournament_id = ?2 AND court_number = ?3)
+            "#,
+            params![Uuid::new_v4().to_string(), tournament_id, court_number],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    conn.execute(
+        "DELETE FROM courts WHERE tournament_id = ?1 AND court_number > ?2",
+        params![tournament_id, number_of_courts],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Loads the active courts for pairing and bracket generation, without
+/// writing anything: courts of the tournament's count that are not in the
+/// inventory yet count as active and unreserved, as `sync_courts` will create
+/// them. Code that saves a round syncs the inventory itself.
+pub fn load_court_plan(
+    conn: &rusqlite::Connection,
+    tournament_id: &str,
+) -> Result<CourtPlan, String> {
+    let number_of_courts: i32 = conn
+        .query_row(
+            "SELECT number_of_courts FROM tournaments WHERE id = ?1",
+            params![tournament_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT court_number, is_active, is_reserved
+            FROM courts
+            WHERE tournament_id = ?1 AND court_number <= ?2
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let stored: HashMap<i32, (bool, bool)> = stmt
+        .query_map(params![tournament_id, number_of_courts], |row| {
+            Ok((row.get(0)?, (row.get(1)?, row.get(2)?)))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut plan = CourtPlan {
+        regular: Vec::new(),
+        reserved: Vec::new(),
+    };
+    for court_number in 1..=number_of_courts {
+        match stored.get(&court_number).copied().unwrap_or((true, false)) {
+            (false, _) => {}
+            (true, true) => plan.reserved.push(court_number),
+            (true, false) => plan.regular.push(court_number),
+        }
+    }
+
+    if plan.all().is_empty() {
+        return Err("No active courts available. Enable at least one court.".to_string());
+    }
+
+    Ok(plan)
+}
+
+#[tauri::command]
+pub fn get_courts(db: State<Database>, tournament_id: String) -> Result<Vec<Court>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    sync_courts(&conn, &tournament_id)?;
+
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT id, tournament_id, court_number, label, is_active, is_reserved
+            FROM courts
+            WHERE tournament_id = ?1
+            ORDER BY court_number
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let courts = stmt
+        .query_map(params![tournament_id], |row| {
+            Ok(Court {
+                id: row.get(0)?,
+                tournament_id: row.get(1)?,
+                court_number: row.get(2)?,
+                label: row.get(3)?,
+                is_active: row.get(4)?,
+                is_reserved: row.get(5)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(courts)
+}
+
+#[tauri::command]
+pub fn update_court(db: State<Database>, id: String, data: UpdateCourtData) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let (tournament_id, was_active): (String, bool) = conn
+        .query_row(
+            "SELECT tournament_id, is_active FROM courts WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| e.to_string())?;
+
+    // A tournament always needs somewhere to play
+    if was_active && !data.is_active {
+        let active_courts: i32 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM courts WHERE tournament_id = ?1 AND is_active = 1",
+                params![tournament_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+
+        if active_courts <= 1 {
+            return Err("Cannot disable the last active court.".to_string());
+        }
+    }
+
+    let label = data
+        .label
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty());
+
+    conn.execute(
+        "UPDATE courts SET label = ?2, is_active = ?3, is_reserved = ?4 WHERE id = ?1",
+        params![
+            id,
+            label,
+            if data.is_active { 1 } else { 0 },
+            if data.is_reserved { 1 } else { 0 }
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
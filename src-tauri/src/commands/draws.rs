@@ -0,0 +1,145 @@
+use crate::commands::brackets::replay_bracket_draw;
+use crate::commands::melee::replay_melee_draw;
+use crate::commands::qualifying::replay_round_draw;
+use crate::commands::ranking::replay_tiebreak_draw;
+use crate::db::Database;
+use crate::models::{Draw, DrawReplay};
+use chrono::Utc;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rusqlite::params;
+use serde::Serialize;
+use tauri::State;
+use uuid::Uuid;
+
+/// Random number generator used for every draw. Seeded, so that a draw can
+/// be replayed from its stored seed and inputs.
+pub type DrawRng = ChaCha8Rng;
+
+/// A fresh random seed for a draw
+pub fn new_seed() -> u64 {
+    rand::random()
+}
+
+pub fn seeded_rng(seed: u64) -> DrawRng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+/// Stores a draw with its seed, the inputs it was drawn from and what came out.
+pub fn record_draw<I: Serialize, R: Serialize>(
+    conn: &rusqlite::Connection,
+    tournament_id: &str,
+    kind: &str,
+    reference_id: Option<&str>,
+    seed: u64,
+    inputs: &I,
+    result: &R,
+) -> Result<(), String> {
+    let inputs = serde_json::to_string(inputs).map_err(|e| e.to_string())?;
+    let result = serde_json::to_string(result).map_err(|e| e.to_string())?;
+
+    conn.execute(
+        r#"
+        INSERT INTO draws (id, tournament_id, kind, reference_id, seed, inputs, result, created_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+        "#,
+        params![
+            Uuid::new_v4().to_string(),
+            tournament_id,
+            kind,
+            reference_id,
+            seed.to_string(),
+            inputs,
+            result,
+            Utc::now().to_rfc3339()
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+fn draw_from_row(row: &rusqlite::Row) -> rusqlite::Result<Draw> {
+    let json = |idx: usize| -> rusqlite::Result<serde_json::Value> {
+        let text: String = row.get(idx)?;
+        serde_json::from_str(&text).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(idx, rusqlite::types::Type::Text, Box::new(e))
+        })
+    };
+
+    Ok(Draw {
+        id: row.get(0)?,
+        tournament_id: row.get(1)?,
+        kind: row.get(2)?,
+        reference_id: row.get(3)?,
+        seed: row.get(4)?,
+        inputs: json(5)?,
+        result: json(6)?,
+        created_at: row.get(7)?,
+    })
+}
+
+#[tauri::command]
+pub fn get_draws(db: State<Database>, tournament_id: String) -> Result<Vec<Draw>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT id, tournament_id, kind, reference_id, seed, inputs, result, created_at
+            FROM draws
+            WHERE tournament_id = ?1
+            ORDER BY created_at
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let draws = stmt
+        .query_map(params![tournament_id], draw_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(draws)
+}
+
+/// Draws again from a stored seed and inputs, and tells whether the outcome
+/// is the one that was recorded.
+#[tauri::command]
+pub fn replay_draw(db: State<Database>, draw_id: String) -> Result<DrawReplay, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let draw = conn
+        .query_row(
+            r#"
+            SELECT id, tournament_id, kind, reference_id, seed, inputs, result, created_at
+            FROM draws
+            WHERE id = ?1
+            "#,
+            params![draw_id],
+            draw_from_row,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let seed: u64 = draw
+        .seed
+        .parse()
+        .map_err(|_| format!("Invalid draw seed: {}", draw.seed))?;
+    let mut rng = seeded_rng(seed);
+
+    let result = match draw.kind.as_str() {
+        "round" if draw.inputs["pairingMethod"] == "melee" => {
+            replay_melee_draw(&draw.inputs, &mut rng)?
+        }
+        "round" => replay_round_draw(&draw.inputs, &mut rng)?,
+        "bracket" => replay_bracket_draw(&draw.inputs, &mut rng)?,
+        "tiebreak" => replay_tiebreak_draw(&draw.inputs, &mut rng)?,
+        kind => return Err(format!("Unknown draw kind: {}", kind)),
+    };
+
+    Ok(DrawReplay {
+        matches: result == draw.result,
+        draw,
+        result,
+    })
+}
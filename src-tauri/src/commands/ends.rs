@@ -0,0 +1,613 @@
+use crate::commands::brackets::save_match_score;
+use crate::commands::qualifying::save_game_score;
+use crate::commands::tournaments::get_game_rules;
+use crate::db::Database;
+use crate::models::{CourtDuration, EndsSummary, GameEnd, GameRules};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::params;
+use std::collections::BTreeMap;
+use tauri::State;
+use uuid::Uuid;
+
+/// A qualifying game or a bracket match: both keep their ends in `game_ends`
+pub enum Scored {
+    Game(String),
+    Match(String),
+}
+
+impl Scored {
+    fn column(&self) -> &'static str {
+        match self {
+            Scored::Game(_) => "qualifying_game_id",
+            Scored::Match(_) => "bracket_match_id",
+        }
+    }
+
+    fn id(&self) -> &str {
+        match self {
+            Scored::Game(id) | Scored::Match(id) => id,
+        }
+    }
+}
+
+/// Where a game or match is at, as far as recording its ends goes
+struct ScoredState {
+    tournament_id: String,
+    format: String,
+    started_at: Option<String>,
+    has_score: bool,
+    has_both_teams: bool,
+    is_bye: bool,
+    is_forfeit: bool,
+    /// The result can no longer change here: the round is complete, or the
+    /// winner has moved on in the bracket
+    is_locked: bool,
+}
+
+fn load_state(conn: &rusqlite::Connection, scored: &Scored) -> Result<ScoredState, String> {
+    let sql = match scored {
+        Scored::Game(_) => {
+            r#"
+            SELECT r.tournament_id, t.format, r.started_at, g.team1_score IS NOT NULL,
+                   g.team1_id IS NOT NULL AND g.team2_id IS NOT NULL, g.is_bye, g.is_forfeit, r.is_complete
+            FROM qualifying_games g
+            JOIN qualifying_rounds r ON g.round_id = r.id
+            JOIN tournaments t ON r.tournament_id = t.id
+            WHERE g.id = ?1
+            "#
+        }
+        Scored::Match(_) => {
+            r#"
+            SELECT b.tournament_id, t.format, m.started_at, m.team1_score IS NOT NULL,
+                   m.team1_id IS NOT NULL AND m.team2_id IS NOT NULL, m.is_bye, m.is_forfeit, m.winner_id IS NOT NULL
+            FROM bracket_matches m
+            JOIN brackets b ON m.bracket_id = b.id
+            JOIN tournaments t ON b.tournament_id = t.id
+            WHERE m.id = ?1
+            "#
+        }
+    };
+
+    conn.query_row(sql, params![scored.id()], |row| {
+        Ok(ScoredState {
+            tournament_id: row.get(0)?,
+            format: row.get(1)?,
+            started_at: row.get(2)?,
+            has_score: row.get::<_, i32>(3)? != 0,
+            has_both_teams: row.get::<_, i32>(4)? != 0,
+            is_bye: row.get::<_, i32>(5)? != 0,
+            is_forfeit: row.get::<_, i32>(6)? != 0,
+            is_locked: row.get::<_, i32>(7)? != 0,
+        })
+    })
+    .map_err(|e| e.to_string())
+}
+
+fn load_ends(conn: &rusqlite::Connection, scored: &Scored) -> Result<Vec<GameEnd>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT id, end_number, team1_points, team2_points, recorded_at FROM game_ends WHERE {} = ?1 ORDER BY end_number",
+            scored.column()
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let ends = stmt
+        .query_map(params![scored.id()], |row| {
+            Ok(GameEnd {
+                id: row.get(0)?,
+                end_number: row.get(1)?,
+                team1_points: row.get(2)?,
+                team2_points: row.get(3)?,
+                recorded_at: row.get(4)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(ends)
+}
+
+/// Whether any end of a game or match has been recorded
+pub fn has_ends(conn: &rusqlite::Connection, scored: &Scored) -> Result<bool, String> {
+    conn.query_row(
+        &format!(
+            "SELECT COUNT(*) > 0 FROM game_ends WHERE {} = ?1",
+            scored.column()
+        ),
+        params![scored.id()],
+        |row| row.get(0),
+    )
+    .map_err(|e| e.to_string())
+}
+
+fn parse_time(value: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|e| e.to_string())
+}
+
+fn summarize(
+    rules: &GameRules,
+    started_at: Option<String>,
+    ends: Vec<GameEnd>,
+    is_decided: bool,
+) -> Result<EndsSummary, String> {
+    let team1_total = ends.iter().map(|e| e.team1_points).sum();
+    let team2_total = ends.iter().map(|e| e.team2_points).sum();
+
+    let time_called = match (&started_at, rules.time_limit_minutes) {
+        (Some(start), Some(minutes)) => {
+            Some(parse_time(start)? + Duration::minutes(minutes as i64))
+        }
+        _ => None,
+    };
+
+    // The end in progress when time is called finishes after it, so it
+    // counts along with the extra ends
+    let mut ends_after_time = 0;
+    if let Some(called) = time_called {
+        for end in &ends {
+            if parse_time(&end.recorded_at)? > called {
+                ends_after_time += 1;
+            }
+        }
+    }
+
+    Ok(EndsSummary {
+        ends,
+        team1_total,
+        team2_total,
+        started_at,
+        time_called_at: time_called.map(|t| t.to_rfc3339()),
+        ends_after_time,
+        is_decided,
+    })
+}
+
+/// The final score once the ends decide the game: a team has reached the
+/// target, or time was called, the end in progress and the extra ends have
+/// been played, and a team is ahead. A game tied after the extra ends goes on
+/// until an end breaks the tie. The winner's score stops at the target.
+fn final_score(rules: &GameRules, summary: &EndsSummary) -> Option<(i32, i32)> {
+    let target = rules.target_score;
+    let (team1, team2) = (summary.team1_total, summary.team2_total);
+
+    if team1.max(team2) >= target {
+        return Some((team1.min(target), team2.min(target)));
+    }
+    if summary.time_called_at.is_some()
+        && summary.ends_after_time > rules.extra_ends
+        && team1 != team2
+    {
+        return Some((team1, team2));
+    }
+
+    None
+}
+
+fn get_ends(conn: &rusqlite::Connection, scored: &Scored) -> Result<EndsSummary, String> {
+    let state = load_state(conn, scored)?;
+    let rules = get_game_rules(conn, &state.tournament_id)?;
+    let ends = load_ends(conn, scored)?;
+    summarize(&rules, state.started_at, ends, state.has_score)
+}
+
+/// Records the next end, and writes the final score when it decides the game
+fn record_end(
+    conn: &rusqlite::Connection,
+    scored: &Scored,
+    team1_points: i32,
+    team2_points: i32,
+) -> Result<EndsSummary, String> {
+    let state = load_state(conn, scored)?;
+
+    if state.is_bye || state.is_forfeit {
+        return Err("A BYE or a forfeit has no ends to record.".to_string());
+    }
+    if !state.has_both_teams {
+        return Err("Both teams must be known before the ends are recorded.".to_string());
+    }
+    if state.is_locked {
+        return Err("This result is final: it can no longer be changed end by end.".to_string());
+    }
+    if state.has_score {
+        return Err("This game already has its final score.".to_string());
+    }
+    if state.started_at.is_none() {
+        return Err(match scored {
+            Scored::Game(_) => {
+                "Start the round before recording ends: its clock decides when time is called."
+            }
+            Scored::Match(_) => {
+                "Start the match before recording ends: its clock decides when time is called."
+            }
+        }
+        .to_string());
+    }
+
+    // Three boules each in singles, six boules a side in doubles and triples
+    let max_points = if state.format == "single" { 3 } else { 6 };
+    if team1_points < 0 || team2_points < 0 {
+        return Err("End points cannot be negative.".to_string());
+    }
+    if team1_points > 0 && team2_points > 0 {
+        return Err("Only one team scores in an end.".to_string());
+    }
+    if team1_points.max(team2_points) > max_points {
+        return Err(format!(
+            "An end is worth at most {} points in this format.",
+            max_points
+        ));
+    }
+
+    let rules = get_game_rules(conn, &state.tournament_id)?;
+    let ends = load_ends(conn, scored)?;
+    let end_number = ends.len() as i32 + 1;
+
+    // An end that decides the game is only kept along with the final score
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+
+    tx.execute(
+        &format!(
+            "INSERT INTO game_ends (id, {}, end_number, team1_points, team2_points, recorded_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            scored.column()
+        ),
+        params![
+            Uuid::new_v4().to_string(),
+            scored.id(),
+            end_number,
+            team1_points,
+            team2_points,
+            Utc::now().to_rfc3339()
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let ends = load_ends(&tx, scored)?;
+    let mut summary = summarize(&rules, state.started_at, ends, false)?;
+
+    if let Some((team1_score, team2_score)) = final_score(&rules, &summary) {
+        match scored {
+            Scored::Game(id) => save_game_score(&tx, id, team1_score, team2_score),
+            Scored::Match(id) => save_match_score(&tx, id, team1_score, team2_score),
+        }
+        .map_err(|e| e.message)?;
+        summary.is_decided = true;
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(summary)
+}
+
+/// Removes the last end recorded. A game decided by that end gets its score
+/// back to empty; a bracket match that sent its winner on cannot be undone.
+fn undo_last_end(conn: &rusqlite::Connection, scored: &Scored) -> Result<EndsSummary, String> {
+    let state = load_state(conn, scored)?;
+
+    if state.is_locked {
+        return Err("This result is final: it can no longer be changed end by end.".to_string());
+    }
+
+    let ends = load_ends(conn, scored)?;
+    let last = ends.last().ok_or("No end has been recorded yet.")?;
+
+    // The end and the score it gave go together
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+
+    tx.execute("DELETE FROM game_ends WHERE id = ?1", params![last.id])
+        .map_err(|e| e.to_string())?;
+
+    if let Scored::Game(id) = scored {
+        tx.execute(
+            "UPDATE qualifying_games SET team1_score = NULL, team2_score = NULL, ended_at = NULL WHERE id = ?1",
+            params![id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    get_ends(conn, scored)
+}
+
+/// Starts the clock of a round: time is called for all its games together
+#[tauri::command]
+pub fn start_round(db: State<Database>, round_id: String) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let (is_complete, started_at): (bool, Option<String>) = conn
+        .query_row(
+            "SELECT is_complete, started_at FROM qualifying_rounds WHERE id = ?1",
+            params![round_id],
+            |row| Ok((row.get::<_, i32>(0)? != 0, row.get(1)?)),
+        )
+        .map_err(|e| e.to_string())?;
+
+    if is_complete {
+        return Err("This round is already complete.".to_string());
+    }
+    if started_at.is_some() {
+        return Err("This round has already started.".to_string());
+    }
+
+    conn.execute(
+        "UPDATE qualifying_rounds SET started_at = ?2 WHERE id = ?1",
+        params![round_id, Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Starts the clock of a bracket match, once both its teams are known
+#[tauri::command]
+pub fn start_match(db: State<Database>, match_id: String) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let state = load_state(&conn, &Scored::Match(match_id.clone()))?;
+
+    if state.is_bye || !state.has_both_teams {
+        return Err("Both teams must be known before the match starts.".to_string());
+    }
+    if state.has_score {
+        return Err("This match has already been played.".to_string());
+    }
+    if state.started_at.is_some() {
+        return Err("This match has already started.".to_string());
+    }
+
+    conn.execute(
+        "UPDATE bracket_matches SET started_at = ?2 WHERE id = ?1",
+        params![match_id, Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_game_ends(db: State<Database>, game_id: String) -> Result<EndsSummary, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    get_ends(&conn, &Scored::Game(game_id))
+}
+
+#[tauri::command]
+pub fn record_game_end(
+    db: State<Database>,
+    game_id: String,
+    team1_points: i32,
+    team2_points: i32,
+) -> Result<EndsSummary, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    record_end(&conn, &Scored::Game(game_id), team1_points, team2_points)
+}
+
+#[tauri::command]
+pub fn undo_last_game_end(db: State<Database>, game_id: String) -> Result<EndsSummary, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    undo_last_end(&conn, &Scored::Game(game_id))
+}
+
+#[tauri::command]
+pub fn get_match_ends(db: State<Database>, match_id: String) -> Result<EndsSummary, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    get_ends(&conn, &Scored::Match(match_id))
+}
+
+#[tauri::command]
+pub fn record_match_end(
+    db: State<Database>,
+    match_id: String,
+    team1_points: i32,
+    team2_points: i32,
+) -> Result<EndsSummary, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    record_end(&conn, &Scored::Match(match_id), team1_points, team2_points)
+}
+
+#[tauri::command]
+pub fn undo_last_match_end(db: State<Database>, match_id: String) -> Result<EndsSummary, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    undo_last_end(&conn, &Scored::Match(match_id))
+}
+
+/// Playing time per court over the qualifying games and bracket matches that
+/// were timed: BYEs, forfeits and walkovers are left out.
+#[tauri::command]
+pub fn get_court_durations(
+    db: State<Database>,
+    tournament_id: String,
+) -> Result<Vec<CourtDuration>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT g.court_number, r.started_at, g.ended_at
+            FROM qualifying_games g
+            JOIN qualifying_rounds r ON g.round_id = r.id
+            WHERE r.tournament_id = ?1 AND g.is_bye = 0 AND g.is_forfeit = 0
+              AND r.started_at IS NOT NULL AND g.ended_at IS NOT NULL
+            UNION ALL
+            SELECT m.court_number, m.started_at, m.ended_at
+            FROM bracket_matches m
+            JOIN brackets b ON m.bracket_id = b.id
+            WHERE b.tournament_id = ?1 AND m.is_bye = 0 AND m.is_forfeit = 0 AND m.court_number IS NOT NULL
+              AND m.started_at IS NOT NULL AND m.ended_at IS NOT NULL
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let timings = stmt
+        .query_map(params![tournament_id], |row| {
+            Ok((
+                row.get::<_, i32>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut minutes_by_court: BTreeMap<i32, Vec<f64>> = BTreeMap::new();
+    for (court_number, started_at, ended_at) in timings {
+        let seconds = (parse_time(&ended_at)? - parse_time(&started_at)?).num_seconds();
+        // A score entered before its round was started has no meaningful duration
+        if seconds >= 0 {
+            minutes_by_court
+                .entry(court_number)
+                .or_default()
+                .push(seconds as f64 / 60.0);
+        }
+    }
+
+    let durations = minutes_by_court
+        .into_iter()
+        .map(|(court_number, minutes)| CourtDuration {
+            court_number,
+            games_played: minutes.len() as i32,
+            average_minutes: minutes.iter().sum::<f64>() / minutes.len() as f64,
+            longest_minutes: minutes.iter().cloned().fold(0.0, f64::max),
+        })
+        .collect();
+
+    Ok(durations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::schema::create_tables;
+
+    fn rules(target_score: i32, time_limit_minutes: Option<i32>, extra_ends: i32) -> GameRules {
+        GameRules {
+            target_score,
+            time_limited: time_limit_minutes.is_some(),
+            time_limit_minutes,
+            extra_ends,
+        }
+    }
+
+    const START: &str = "2026-06-01T09:00:00+00:00";
+
+    /// Ends recorded the given number of minutes after the start
+    fn ends(list: &[(i32, i32, i64)]) -> Vec<GameEnd> {
+        let start = parse_time(START).unwrap();
+        list.iter()
+            .enumerate()
+            .map(|(i, &(team1_points, team2_points, minutes))| GameEnd {
+                id: format!("e{}", i),
+                end_number: i as i32 + 1,
+                team1_points,
+                team2_points,
+                recorded_at: (start + Duration::minutes(minutes)).to_rfc3339(),
+            })
+            .collect()
+    }
+
+    fn score(rules: &GameRules, list: &[(i32, i32, i64)]) -> Option<(i32, i32)> {
+        final_score(rules, &summarize(rules, Some(START.to_string()), ends(list), false).unwrap())
+    }
+
+    #[test]
+    fn reaching_the_target_decides_the_game() {
+        let to_13 = rules(13, None, 1);
+        assert_eq!(score(&to_13, &[(6, 0, 5), (0, 4, 10), (5, 0, 15)]), None);
+        // The last end overshoots: the winner's score stops at the target
+        assert_eq!(score(&to_13, &[(6, 0, 5), (0, 4, 10), (5, 0, 15), (3, 0, 20)]), Some((13, 4)));
+        assert_eq!(score(&rules(11, None, 1), &[(0, 6, 5), (0, 5, 10)]), Some((0, 11)));
+    }
+
+    #[test]
+    fn time_called_plays_the_end_in_progress_and_the_extra_ends() {
+        let timed = rules(13, Some(60), 1);
+        let before_time = [(3, 0, 20), (0, 1, 50)];
+        assert_eq!(score(&timed, &before_time), None);
+
+        // The end in progress when time is called does not decide the game
+        let mut played = before_time.to_vec();
+        played.push((2, 0, 65));
+        assert_eq!(score(&timed, &played), None);
+
+        // ... the extra end after it does
+        played.push((0, 1, 70));
+        assert_eq!(score(&timed, &played), Some((5, 2)));
+
+        // Two extra ends
+        assert_eq!(score(&rules(13, Some(60), 2), &played), None);
+    }
+
+    #[test]
+    fn a_tie_after_the_extra_ends_goes_on() {
+        let timed = rules(13, Some(60), 1);
+        let mut played = vec![(3, 0, 20), (0, 1, 65), (0, 2, 70)];
+        assert_eq!(score(&timed, &played), None);
+
+        played.push((0, 0, 75));
+        assert_eq!(score(&timed, &played), None);
+        played.push((1, 0, 80));
+        assert_eq!(score(&timed, &played), Some((4, 3)));
+    }
+
+    #[test]
+    fn end_points_are_capped_by_format() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        // A game of two teams is enough: the teams themselves are never read
+        conn.execute_batch("PRAGMA foreign_keys = OFF;").unwrap();
+        for (id, format) in [("single", "single"), ("triple", "triple")] {
+            conn.execute(
+                r#"
+                INSERT INTO tournaments (
+                    id, name, team_composition, tournament_type, start_date, end_date,
+                    director, head_umpire, format, day_type, number_of_courts,
+                    pairing_method, created_at, updated_at
+                ) VALUES (?1, 'Test', 'mixed', 'club', '', '', '', '', ?2, 'single', 4, 'swiss', '', '')
+                "#,
+                params![id, format],
+            )
+            .unwrap();
+            conn.execute(
+                "INSERT INTO qualifying_rounds (id, tournament_id, round_number, started_at, created_at) VALUES (?1, ?1, 1, ?2, '')",
+                params![id, Utc::now().to_rfc3339()],
+            )
+            .unwrap();
+            conn.execute(
+                "INSERT INTO qualifying_games (id, round_id, court_number, team1_id, team2_id) VALUES (?1, ?1, 1, 'a', 'b')",
+                params![id],
+            )
+            .unwrap();
+        }
+
+        let single = Scored::Game("single".to_string());
+        assert!(record_end(&conn, &single, 4, 0).is_err());
+        assert!(record_end(&conn, &single, 1, 1).is_err());
+        assert_eq!(record_end(&conn, &single, 3, 0).unwrap().team1_total, 3);
+
+        let triple = Scored::Game("triple".to_string());
+        assert!(record_end(&conn, &triple, 0, 7).is_err());
+        for _ in 0..2 {
+            assert!(!record_end(&conn, &triple, 0, 6).unwrap().is_decided);
+        }
+        // The third six reaches 13: the score is written, capped at the target
+        let summary = record_end(&conn, &triple, 0, 6).unwrap();
+        assert!(summary.is_decided);
+        let saved: (i32, i32) = conn
+            .query_row(
+                "SELECT team1_score, team2_score FROM qualifying_games WHERE id = 'triple'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(saved, (0, 13));
+        assert!(record_end(&conn, &triple, 1, 0).is_err());
+
+        // Undoing the deciding end clears the score again
+        let summary = undo_last_end(&conn, &triple).unwrap();
+        assert_eq!((summary.team2_total, summary.is_decided), (12, false));
+        assert!(record_end(&conn, &triple, 0, 6).unwrap().is_decided);
+    }
+}
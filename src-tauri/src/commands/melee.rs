@@ -0,0 +1,702 @@
+use crate::commands::courts::{load_court_plan, sync_courts, CourtPlan};
+use crate::commands::draws::{new_seed, record_draw, seeded_rng, DrawRng};
+use crate::commands::scoring::{validate_score, ScoreError};
+use crate::commands::tournaments::{get_bye_scoring, get_game_rules};
+use crate::db::Database;
+use crate::models::{CreatePlayerData, MeleeGame, Player, PlayerStanding, QualifyingRound};
+use crate::pairing::melee::{form_games, game_sizes};
+use chrono::Utc;
+use rand::seq::SliceRandom;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::State;
+use uuid::Uuid;
+
+fn player_from_row(row: &rusqlite::Row) -> rusqlite::Result<Player> {
+    Ok(Player {
+        id: row.get(0)?,
+        tournament_id: row.get(1)?,
+        name: row.get(2)?,
+        club: row.get(3)?,
+        created_at: row.get(4)?,
+    })
+}
+
+#[tauri::command]
+pub fn get_players(db: State<Database>, tournament_id: String) -> Result<Vec<Player>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT id, tournament_id, name, club, created_at
+            FROM players
+            WHERE tournament_id = ?1
+            ORDER BY name
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let players = stmt
+        .query_map(params![tournament_id], player_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(players)
+}
+
+/// Enters a player in a mêlée. Players can join between rounds: they are drawn
+/// from the next round on.
+#[tauri::command]
+pub fn create_player(db: State<Database>, data: CreatePlayerData) -> Result<Player, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let pairing_method: String = conn
+        .query_row(
+            "SELECT pairing_method FROM tournaments WHERE id = ?1",
+            params![data.tournament_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    if pairing_method != "melee" {
+        return Err("Individual players can only be entered in a mêlée.".to_string());
+    }
+
+    let name = data.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Player name is required.".to_string());
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now().to_rfc3339();
+
+    conn.execute(
+        r#"
+        INSERT INTO players (id, tournament_id, name, club, created_at)
+        VALUES (?1, ?2, ?3, ?4, ?5)
+        "#,
+        params![id, data.tournament_id, name, data.club, now],
+    )
+    .map_err(|e| e.to_string())?;
+
+    // Initialize player standing
+    conn.execute(
+        r#"
+        INSERT INTO player_standings (id, tournament_id, player_id, wins, losses, points_for, points_against, differential, rank)
+        VALUES (?1, ?2, ?3, 0, 0, 0, 0, 0, 0)
+        "#,
+        params![Uuid::new_v4().to_string(), data.tournament_id, id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(Player {
+        id,
+        tournament_id: data.tournament_id,
+        name,
+        club: data.club,
+        created_at: now,
+    })
+}
+
+#[tauri::command]
+pub fn delete_player(db: State<Database>, id: String) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let rounds_played: i32 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM melee_team_players WHERE player_id = ?1",
+            params![id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    if rounds_played > 0 {
+        return Err("Cannot delete a player who has already been drawn in a round.".to_string());
+    }
+
+    conn.execute("DELETE FROM players WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_player_standings(
+    db: State<Database>,
+    tournament_id: String,
+) -> Result<Vec<PlayerStanding>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT id, tournament_id, player_id, wins, losses, points_for, points_against, differential, rank
+            FROM player_standings
+            WHERE tournament_id = ?1
+            ORDER BY rank
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let standings = stmt
+        .query_map(params![tournament_id], |row| {
+            Ok(PlayerStanding {
+                id: row.get(0)?,
+                tournament_id: row.get(1)?,
+                player_id: row.get(2)?,
+                wins: row.get(3)?,
+                losses: row.get(4)?,
+                points_for: row.get(5)?,
+                points_against: row.get(6)?,
+                differential: row.get(7)?,
+                rank: row.get(8)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(standings)
+}
+
+/// Players of a team formed for a mêlée round
+fn team_players(conn: &rusqlite::Connection, team_id: &str) -> Result<Vec<Player>, String> {
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT p.id, p.tournament_id, p.name, p.club, p.created_at
+            FROM melee_team_players mtp
+            JOIN players p ON mtp.player_id = p.id
+            WHERE mtp.team_id = ?1
+            ORDER BY p.name
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let players = stmt
+        .query_map(params![team_id], player_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(players)
+}
+
+#[tauri::command]
+pub fn get_melee_games(db: State<Database>, round_id: String) -> Result<Vec<MeleeGame>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT id, round_id, court_number, slot_number, team1_id, team2_id, team1_score, team2_score, is_bye
+            FROM melee_games
+            WHERE round_id = ?1
+            ORDER BY is_bye ASC, slot_number ASC, court_number ASC
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let games: Vec<MeleeGame> = stmt
+        .query_map(params![round_id], |row| {
+            Ok(MeleeGame {
+                id: row.get(0)?,
+                round_id: row.get(1)?,
+                court_number: row.get(2)?,
+                slot_number: row.get(3)?,
+                team1_id: row.get(4)?,
+                team2_id: row.get(5)?,
+                team1_score: row.get(6)?,
+                team2_score: row.get(7)?,
+                is_bye: row.get::<_, i32>(8)? != 0,
+                team1: Vec::new(),
+                team2: Vec::new(),
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    // Fetch the players of each team
+    let mut games_with_players = Vec::with_capacity(games.len());
+    for mut game in games {
+        game.team1 = team_players(&conn, &game.team1_id)?;
+        if let Some(team2_id) = &game.team2_id {
+            game.team2 = team_players(&conn, team2_id)?;
+        }
+        games_with_players.push(game);
+    }
+
+    Ok(games_with_players)
+}
+
+#[tauri::command]
+pub fn update_melee_game_score(
+    db: State<Database>,
+    game_id: String,
+    team1_score: i32,
+    team2_score: i32,
+) -> Result<(), ScoreError> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    // A completed round is already in the player standings, and a BYE is
+    // credited by the tournament's BYE policy, not by a score
+    let (tournament_id, is_complete, is_bye): (String, bool, bool) = conn
+        .query_row(
+            r#"
+            SELECT r.tournament_id, r.is_complete, g.is_bye
+            FROM melee_games g
+            JOIN qualifying_rounds r ON g.round_id = r.id
+            WHERE g.id = ?1
+            "#,
+            params![game_id],
+            |row| Ok((row.get(0)?, row.get::<_, i32>(1)? != 0, row.get::<_, i32>(2)? != 0)),
+        )
+        .map_err(|e| e.to_string())?;
+
+    if is_complete {
+        return Err("This round is complete: its scores are already in the standings.".to_string().into());
+    }
+    if is_bye {
+        return Err("A BYE has no score to enter.".to_string().into());
+    }
+
+    validate_score(&get_game_rules(&conn, &tournament_id)?, team1_score, team2_score)?;
+
+    conn.execute(
+        "UPDATE melee_games SET team1_score = ?2, team2_score = ?3 WHERE id = ?1",
+        params![game_id, team1_score, team2_score],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Players per team for a tournament format
+fn team_size(format: &str) -> usize {
+    match format {
+        "single" => 1,
+        "triple" => 3,
+        _ => 2,
+    }
+}
+
+/// Everything a mêlée round draw depends on. Stored with the seed of the draw,
+/// so that the round can be drawn again exactly as it came out.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MeleeDrawInputs {
+    /// Always "melee"; tells a mêlée round draw from a team round draw
+    pairing_method: String,
+    round_number: i32,
+    team_size: usize,
+    players: Vec<String>,
+    bye_counts: Vec<(String, i32)>,
+    /// One entry per round two players played together
+    teammates: Vec<(String, String)>,
+    /// One entry per game two players played against each other
+    opponents: Vec<(String, String)>,
+    courts: CourtPlan,
+}
+
+/// What a mêlée round draw produced
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MeleeDraw {
+    sitting_out: Vec<String>,
+    games: Vec<MeleeGameDraw>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MeleeGameDraw {
+    court_number: i32,
+    slot_number: i32,
+    team1: Vec<String>,
+    team2: Vec<String>,
+}
+
+/// Draws the teams and games of a mêlée round. The players who sat out least
+/// often sit out when the field cannot be split evenly.
+fn draw_melee_round(inputs: &MeleeDrawInputs, rng: &mut DrawRng) -> Result<MeleeDraw, String> {
+    let (sizes, sitting_out_count) = game_sizes(inputs.players.len(), inputs.team_size);
+    if sizes.is_empty() {
+        return Err("Not enough players to form two teams.".to_string());
+    }
+
+    let bye_counts: HashMap<&str, i32> = inputs
+        .bye_counts
+        .iter()
+        .map(|(player_id, count)| (player_id.as_str(), *count))
+        .collect();
+
+    let mut players = inputs.players.clone();
+    players.shuffle(rng);
+    players.sort_by_key(|player_id| bye_counts.get(player_id.as_str()).copied().unwrap_or(0));
+    let sitting_out: Vec<String> = players.drain(..sitting_out_count).collect();
+
+    let meetings = |pairs: &[(String, String)]| -> HashMap<(String, String), i64> {
+        let mut counts: HashMap<(String, String), i64> = HashMap::new();
+        for (p1, p2) in pairs {
+            *counts.entry((p1.clone(), p2.clone())).or_default() += 1;
+            *counts.entry((p2.clone(), p1.clone())).or_default() += 1;
+        }
+        counts
+    };
+    let teams = form_games(
+        &players,
+        &sizes,
+        &meetings(&inputs.teammates),
+        &meetings(&inputs.opponents),
+    );
+
+    // Games rotate over the courts, in waves when there are more games than courts
+    let rotation = inputs.courts.rotation();
+    let games = teams
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (team1, team2))| MeleeGameDraw {
+            court_number: rotation[idx % rotation.len()],
+            slot_number: (idx / rotation.len()) as i32 + 1,
+            team1,
+            team2,
+        })
+        .collect();
+
+    Ok(MeleeDraw { sitting_out, games })
+}
+
+/// Draws a mêlée round again from the inputs stored with its draw.
+pub fn replay_melee_draw(
+    inputs: &serde_json::Value,
+    rng: &mut DrawRng,
+) -> Result<serde_json::Value, String> {
+    let inputs: MeleeDrawInputs =
+        serde_json::from_value(inputs.clone()).map_err(|e| e.to_string())?;
+    let draw = draw_melee_round(&inputs, rng)?;
+    serde_json::to_value(&draw).map_err(|e| e.to_string())
+}
+
+fn insert_melee_team(
+    conn: &rusqlite::Connection,
+    round_id: &str,
+    player_ids: &[String],
+) -> Result<String, String> {
+    let team_id = Uuid::new_v4().to_string();
+    conn.execute(
+        "INSERT INTO melee_teams (id, round_id) VALUES (?1, ?2)",
+        params![team_id, round_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    for player_id in player_ids {
+        conn.execute(
+            "INSERT INTO melee_team_players (team_id, player_id) VALUES (?1, ?2)",
+            params![team_id, player_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(team_id)
+}
+
+/// Generates the next round of a mêlée: new teams are drawn from every entered
+/// player, avoiding former teammates first and former opponents second.
+pub fn generate_melee_round(
+    conn: &rusqlite::Connection,
+    tournament_id: &str,
+    round_number: i32,
+) -> Result<QualifyingRound, String> {
+    sync_courts(conn, tournament_id)?;
+
+    let format: String = conn
+        .query_row(
+            "SELECT format FROM tournaments WHERE id = ?1",
+            params![tournament_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare("SELECT id FROM players WHERE tournament_id = ?1 ORDER BY created_at, id")
+        .map_err(|e| e.to_string())?;
+    let players: Vec<String> = stmt
+        .query_map(params![tournament_id], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    if players.is_empty() {
+        return Err("No players registered for this mêlée".to_string());
+    }
+
+    // Rounds each player sat out
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT mtp.player_id, COUNT(*)
+            FROM melee_games g
+            JOIN melee_team_players mtp ON mtp.team_id = g.team1_id
+            JOIN qualifying_rounds r ON g.round_id = r.id
+            WHERE r.tournament_id = ?1 AND g.is_bye = 1
+            GROUP BY mtp.player_id
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+    let bye_counts: Vec<(String, i32)> = stmt
+        .query_map(params![tournament_id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    // Players who already played in the same team
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT a.player_id, b.player_id
+            FROM melee_team_players a
+            JOIN melee_team_players b ON a.team_id = b.team_id AND a.player_id < b.player_id
+            JOIN melee_teams t ON a.team_id = t.id
+            JOIN qualifying_rounds r ON t.round_id = r.id
+            WHERE r.tournament_id = ?1
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+    let teammates: Vec<(String, String)> = stmt
+        .query_map(params![tournament_id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    // Players who already played against each other
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT a.player_id, b.player_id
+            FROM melee_games g
+            JOIN melee_team_players a ON a.team_id = g.team1_id
+            JOIN melee_team_players b ON b.team_id = g.team2_id
+            JOIN qualifying_rounds r ON g.round_id = r.id
+            WHERE r.tournament_id = ?1
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+    let opponents: Vec<(String, String)> = stmt
+        .query_map(params![tournament_id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let inputs = MeleeDrawInputs {
+        pairing_method: "melee".to_string(),
+        round_number,
+        team_size: team_size(&format),
+        players,
+        bye_counts,
+        teammates,
+        opponents,
+        courts: load_court_plan(conn, tournament_id)?,
+    };
+
+    let seed = new_seed();
+    let draw = draw_melee_round(&inputs, &mut seeded_rng(seed))?;
+
+    // Create the round
+    let round_id = Uuid::new_v4().to_string();
+    let now = Utc::now().to_rfc3339();
+
+    conn.execute(
+        r#"
+        INSERT INTO qualifying_rounds (id, tournament_id, round_number, is_complete, created_at)
+        VALUES (?1, ?2, ?3, 0, ?4)
+        "#,
+        params![round_id, tournament_id, round_number, now],
+    )
+    .map_err(|e| e.to_string())?;
+
+    for game in &draw.games {
+        let team1_id = insert_melee_team(conn, &round_id, &game.team1)?;
+        let team2_id = insert_melee_team(conn, &round_id, &game.team2)?;
+        conn.execute(
+            r#"
+            INSERT INTO melee_games (id, round_id, court_number, slot_number, team1_id, team2_id, is_bye)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0)
+            "#,
+            params![
+                Uuid::new_v4().to_string(),
+                round_id,
+                game.court_number,
+                game.slot_number,
+                team1_id,
+                team2_id
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    // Each player sitting out gets a BYE of their own
+    for player_id in &draw.sitting_out {
+        let team_id = insert_melee_team(conn, &round_id, std::slice::from_ref(player_id))?;
+        conn.execute(
+            r#"
+            INSERT INTO melee_games (id, round_id, court_number, slot_number, team1_id, team2_id, is_bye)
+            VALUES (?1, ?2, NULL, 1, ?3, NULL, 1)
+            "#,
+            params![Uuid::new_v4().to_string(), round_id, team_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    record_draw(
+        conn,
+        tournament_id,
+        "round",
+        Some(&round_id),
+        seed,
+        &inputs,
+        &draw,
+    )?;
+
+    Ok(QualifyingRound {
+        id: round_id,
+        tournament_id: tournament_id.to_string(),
+        round_number,
+        is_complete: false,
+        draw_method: None,
+        started_at: None,
+        ended_at: None,
+        created_at: now,
+    })
+}
+
+/// A game of a completed mêlée round, as the standings need it
+struct PlayedGame {
+    team1_id: String,
+    team2_id: Option<String>,
+    team1_score: Option<i32>,
+    team2_score: Option<i32>,
+    is_bye: bool,
+}
+
+/// Rebuilds the player standings of a mêlée from its completed rounds, each
+/// player credited with the result of the team they played in, then re-ranks
+/// the players.
+pub fn rebuild_player_standings(conn: &rusqlite::Connection, tournament_id: &str) -> Result<(), String> {
+    let bye_scoring = get_bye_scoring(conn, tournament_id)?;
+
+    conn.execute(
+        r#"
+        UPDATE player_standings SET
+            wins = 0,
+            losses = 0,
+            points_for = 0,
+            points_against = 0,
+            differential = 0
+        WHERE tournament_id = ?1
+        "#,
+        params![tournament_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT g.team1_id, g.team2_id, g.team1_score, g.team2_score, g.is_bye
+            FROM melee_games g
+            JOIN qualifying_rounds r ON g.round_id = r.id
+            WHERE r.tournament_id = ?1 AND r.is_complete = 1
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let games: Vec<PlayedGame> = stmt
+        .query_map(params![tournament_id], |row| {
+            Ok(PlayedGame {
+                team1_id: row.get(0)?,
+                team2_id: row.get(1)?,
+                team1_score: row.get(2)?,
+                team2_score: row.get(3)?,
+                is_bye: row.get::<_, i32>(4)? != 0,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let credit =
+        |team_id: &str, won: bool, points_for: i32, points_against: i32| -> Result<(), String> {
+            conn.execute(
+                r#"
+            UPDATE player_standings SET
+                wins = wins + ?3,
+                losses = losses + ?4,
+                points_for = points_for + ?5,
+                points_against = points_against + ?6,
+                differential = differential + ?7
+            WHERE tournament_id = ?1
+              AND player_id IN (SELECT player_id FROM melee_team_players WHERE team_id = ?2)
+            "#,
+                params![
+                    tournament_id,
+                    team_id,
+                    if won { 1 } else { 0 },
+                    if won { 0 } else { 1 },
+                    points_for,
+                    points_against,
+                    points_for - points_against
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+            Ok(())
+        };
+
+    for PlayedGame { team1_id, team2_id, team1_score, team2_score, is_bye } in games {
+        if is_bye {
+            // Sitting out counts as a win scored by the tournament's BYE policy
+            let (points_for, points_against) = if bye_scoring.counts_in_tiebreaks {
+                (bye_scoring.winner_score, bye_scoring.loser_score)
+            } else {
+                (0, 0)
+            };
+            credit(&team1_id, true, points_for, points_against)?;
+        } else if let (Some(team2_id), Some(s1), Some(s2)) = (team2_id, team1_score, team2_score) {
+            credit(&team1_id, s1 > s2, s1, s2)?;
+            credit(&team2_id, s2 > s1, s2, s1)?;
+        }
+    }
+
+    rank_players(conn, tournament_id)
+}
+
+/// Ranks the players of a mêlée on wins, then differential and points for.
+fn rank_players(conn: &rusqlite::Connection, tournament_id: &str) -> Result<(), String> {
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT player_id
+            FROM player_standings
+            WHERE tournament_id = ?1
+            ORDER BY wins DESC, differential DESC, points_for DESC
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let ranked: Vec<String> = stmt
+        .query_map(params![tournament_id], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    for (idx, player_id) in ranked.iter().enumerate() {
+        conn.execute(
+            "UPDATE player_standings SET rank = ?3 WHERE tournament_id = ?1 AND player_id = ?2",
+            params![tournament_id, player_id, idx as i32 + 1],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
@@ -1,9 +1,25 @@
 pub mod tournaments;
+pub mod courts;
 pub mod teams;
 pub mod qualifying;
+pub mod poules;
+pub mod melee;
+pub mod ranking;
+pub mod scoring;
 pub mod brackets;
+pub mod draws;
+pub mod corrections;
+pub mod ends;
+pub mod overrides;
 
 pub use tournaments::*;
+pub use courts::*;
 pub use teams::*;
 pub use qualifying::*;
+pub use poules::*;
+pub use melee::*;
 pub use brackets::*;
+pub use draws::*;
+pub use corrections::*;
+pub use ends::*;
+pub use overrides::*;
@@ -0,0 +1,546 @@
+use crate::commands::courts::load_court_plan;
+use crate::commands::ends::{has_ends, Scored};
+use crate::commands::teams::get_team_by_id;
+use crate::db::Database;
+use crate::models::{OverrideResult, OverrideWarning, OverrideWarningKind, PairingOverride, Team};
+use crate::pairing::avoidance::AvoidancePolicy;
+use chrono::Utc;
+use rusqlite::params;
+use tauri::State;
+use uuid::Uuid;
+
+/// A game of a round still in play, with what the checks need of its round
+struct OpenGame {
+    id: String,
+    round_id: String,
+    court_number: i32,
+    slot_number: i32,
+    team1_id: Option<String>,
+    team2_id: Option<String>,
+    is_bye: bool,
+    tournament_id: String,
+    avoidance: AvoidancePolicy,
+}
+
+/// Loads a game whose pairing can still be changed by hand: its round is not
+/// complete and it has not been played yet.
+fn load_open_game(conn: &rusqlite::Connection, game_id: &str) -> Result<OpenGame, String> {
+    let (game, pairing_method, is_complete, is_played): (OpenGame, String, bool, bool) = conn
+        .query_row(
+            r#"
+            SELECT g.id, g.round_id, g.court_number, g.slot_number, g.team1_id, g.team2_id, g.is_bye,
+                   r.tournament_id, r.round_number, t.avoidance_policy, t.avoidance_rounds,
+                   t.pairing_method, r.is_complete,
+                   g.is_bye = 0 AND (g.team1_score IS NOT NULL OR g.is_forfeit = 1)
+            FROM qualifying_games g
+            JOIN qualifying_rounds r ON g.round_id = r.id
+            JOIN tournaments t ON r.tournament_id = t.id
+            WHERE g.id = ?1
+            "#,
+            params![game_id],
+            |row| {
+                let policy: String = row.get(9)?;
+                let round_number: i32 = row.get(8)?;
+                let avoidance = AvoidancePolicy::parse(&policy)
+                    .unwrap_or(AvoidancePolicy::None)
+                    .for_round(row.get(10)?, round_number);
+                Ok((
+                    OpenGame {
+                        id: row.get(0)?,
+                        round_id: row.get(1)?,
+                        court_number: row.get(2)?,
+                        slot_number: row.get(3)?,
+                        team1_id: row.get(4)?,
+                        team2_id: row.get(5)?,
+                        is_bye: row.get::<_, i32>(6)? != 0,
+                        tournament_id: row.get(7)?,
+                        avoidance,
+                    },
+                    row.get(11)?,
+                    row.get::<_, i32>(12)? != 0,
+                    row.get::<_, i32>(13)? != 0,
+                ))
+            },
+        )
+        .map_err(|e| e.to_string())?;
+
+    if pairing_method == "poules" || pairing_method == "roundRobin" {
+        return Err("Poules and round robin rounds follow a fixed schedule: their pairings cannot be changed.".to_string());
+    }
+    if is_complete {
+        return Err("This round is complete: its pairings can no longer be changed.".to_string());
+    }
+    if is_played || has_ends(conn, &Scored::Game(game.id.clone()))? {
+        return Err("This game has already been played.".to_string());
+    }
+
+    Ok(game)
+}
+
+fn load_team(conn: &rusqlite::Connection, team_id: &str) -> Result<Team, String> {
+    get_team_by_id(conn, team_id)?.ok_or_else(|| "Team not found.".to_string())
+}
+
+/// Whether a team shows up in a round other than the one being changed
+fn seen_in_other_rounds(
+    conn: &rusqlite::Connection,
+    sql: &str,
+    round_id: &str,
+    args: &[&dyn rusqlite::ToSql],
+) -> Result<bool, String> {
+    let mut values: Vec<&dyn rusqlite::ToSql> = vec![&round_id];
+    values.extend_from_slice(args);
+    conn.query_row(sql, values.as_slice(), |row| row.get(0))
+        .map_err(|e| e.to_string())
+}
+
+/// Warnings for two teams about to play each other
+fn pairing_warnings(
+    conn: &rusqlite::Connection,
+    game: &OpenGame,
+    team1: &Team,
+    team2: &Team,
+    warnings: &mut Vec<OverrideWarning>,
+) -> Result<(), String> {
+    let rematch = seen_in_other_rounds(
+        conn,
+        r#"
+        SELECT COUNT(*) > 0 FROM pairing_history
+        WHERE round_id != ?1 AND tournament_id = ?2
+          AND ((team1_id = ?3 AND team2_id = ?4) OR (team1_id = ?4 AND team2_id = ?3))
+        "#,
+        &game.round_id,
+        &[&game.tournament_id, &team1.id, &team2.id],
+    )?;
+    if rematch {
+        warnings.push(OverrideWarning {
+            kind: OverrideWarningKind::Rematch,
+            team_ids: vec![team1.id.clone(), team2.id.clone()],
+            court_number: None,
+            message: format!(
+                "{} and {} have already played each other.",
+                team1.captain, team2.captain
+            ),
+        });
+    }
+
+    if game.avoidance.clash(team1, team2) > 0 {
+        warnings.push(OverrideWarning {
+            kind: OverrideWarningKind::SameAffiliation,
+            team_ids: vec![team1.id.clone(), team2.id.clone()],
+            court_number: None,
+            message: format!(
+                "{} and {} share a region or club the pairings keep apart.",
+                team1.captain, team2.captain
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+/// Warning for a team about to get the BYE of the round
+fn bye_warning(
+    conn: &rusqlite::Connection,
+    game: &OpenGame,
+    team: &Team,
+    warnings: &mut Vec<OverrideWarning>,
+) -> Result<(), String> {
+    let had_bye = seen_in_other_rounds(
+        conn,
+        "SELECT COUNT(*) > 0 FROM bye_history WHERE round_id != ?1 AND tournament_id = ?2 AND team_id = ?3",
+        &game.round_id,
+        &[&game.tournament_id, &team.id],
+    )?;
+    if had_bye {
+        warnings.push(OverrideWarning {
+            kind: OverrideWarningKind::RepeatBye,
+            team_ids: vec![team.id.clone()],
+            court_number: None,
+            message: format!("{} has already had a BYE.", team.captain),
+        });
+    }
+
+    Ok(())
+}
+
+/// Writes again the pairing, court and BYE history of games whose teams
+/// changed. A float describes the draw, so the teams swapped by hand lose theirs.
+fn rewrite_history(conn: &rusqlite::Connection, games: &[&OpenGame]) -> Result<(), String> {
+    for game in games {
+        for team_id in [&game.team1_id, &game.team2_id].into_iter().flatten() {
+            for sql in [
+                "DELETE FROM pairing_history WHERE round_id = ?1 AND (team1_id = ?2 OR team2_id = ?2)",
+                "DELETE FROM court_history WHERE round_id = ?1 AND team_id = ?2",
+                "DELETE FROM bye_history WHERE round_id = ?1 AND team_id = ?2",
+                "DELETE FROM pairing_floats WHERE round_id = ?1 AND team_id = ?2",
+            ] {
+                conn.execute(sql, params![game.round_id, team_id])
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+    }
+
+    for game in games {
+        match (&game.team1_id, &game.team2_id) {
+            (Some(team1_id), Some(team2_id)) => {
+                conn.execute(
+                    "INSERT INTO pairing_history (id, tournament_id, team1_id, team2_id, round_id) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![Uuid::new_v4().to_string(), game.tournament_id, team1_id, team2_id, game.round_id],
+                )
+                .map_err(|e| e.to_string())?;
+
+                for team_id in [team1_id, team2_id] {
+                    conn.execute(
+                        "INSERT INTO court_history (id, tournament_id, team_id, court_number, round_id) VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![Uuid::new_v4().to_string(), game.tournament_id, team_id, game.court_number, game.round_id],
+                    )
+                    .map_err(|e| e.to_string())?;
+                }
+            }
+            (Some(team_id), None) => {
+                conn.execute(
+                    "INSERT INTO bye_history (id, tournament_id, team_id, round_id) VALUES (?1, ?2, ?3, ?4)",
+                    params![Uuid::new_v4().to_string(), game.tournament_id, team_id, game.round_id],
+                )
+                .map_err(|e| e.to_string())?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn log_override(
+    conn: &rusqlite::Connection,
+    game: &OpenGame,
+    kind: &str,
+    other_game_id: Option<&str>,
+    teams: (Option<&str>, Option<&str>),
+    courts: (Option<i32>, Option<i32>),
+    reason: Option<String>,
+    warnings: &[OverrideWarning],
+) -> Result<(), String> {
+    let reason = reason
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty());
+    let warnings = serde_json::to_string(warnings).map_err(|e| e.to_string())?;
+
+    conn.execute(
+        r#"
+        INSERT INTO pairing_overrides (id, tournament_id, round_id, kind, game1_id, game2_id, team1_id, team2_id,
+                                       old_court, new_court, reason, warnings, created_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+        "#,
+        params![
+            Uuid::new_v4().to_string(),
+            game.tournament_id,
+            game.round_id,
+            kind,
+            game.id,
+            other_game_id,
+            teams.0,
+            teams.1,
+            courts.0,
+            courts.1,
+            reason,
+            warnings,
+            Utc::now().to_rfc3339()
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Swaps a team of one game with a team of another game of the same round.
+/// The new pairings are checked against the games already played, the
+/// avoidance policy and the BYEs already given; unless `confirm` is set,
+/// nothing changes while there are warnings, which are returned instead.
+#[tauri::command]
+pub fn swap_teams(
+    db: State<Database>,
+    game1_id: String,
+    team1_id: String,
+    game2_id: String,
+    team2_id: String,
+    reason: Option<String>,
+    confirm: bool,
+) -> Result<OverrideResult, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    if game1_id == game2_id {
+        return Err("Pick teams from two different games.".to_string());
+    }
+
+    let mut game1 = load_open_game(&conn, &game1_id)?;
+    let mut game2 = load_open_game(&conn, &game2_id)?;
+
+    if game1.round_id != game2.round_id {
+        return Err("Teams can only be swapped between games of the same round.".to_string());
+    }
+    if game1.is_bye && game2.is_bye {
+        return Err("Both games are BYEs: swapping their teams changes nothing.".to_string());
+    }
+
+    // Put each team in the other's place
+    for (game, from, to) in [
+        (&mut game1, &team1_id, &team2_id),
+        (&mut game2, &team2_id, &team1_id),
+    ] {
+        if game.team1_id.as_ref() == Some(from) {
+            game.team1_id = Some(to.clone());
+        } else if game.team2_id.as_ref() == Some(from) {
+            game.team2_id = Some(to.clone());
+        } else {
+            return Err("The team is not in the game it was picked from.".to_string());
+        }
+    }
+
+    let mut warnings = Vec::new();
+    for game in [&game1, &game2] {
+        match (&game.team1_id, &game.team2_id) {
+            (Some(a), Some(b)) => pairing_warnings(
+                &conn,
+                game,
+                &load_team(&conn, a)?,
+                &load_team(&conn, b)?,
+                &mut warnings,
+            )?,
+            (Some(a), None) => bye_warning(&conn, game, &load_team(&conn, a)?, &mut warnings)?,
+            _ => {}
+        }
+    }
+
+    if !warnings.is_empty() && !confirm {
+        return Ok(OverrideResult {
+            applied: false,
+            warnings,
+        });
+    }
+
+    // Both games, their history and the log entry change together
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+
+    for game in [&game1, &game2] {
+        tx.execute(
+            "UPDATE qualifying_games SET team1_id = ?2, team2_id = ?3 WHERE id = ?1",
+            params![game.id, game.team1_id, game.team2_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    rewrite_history(&tx, &[&game1, &game2])?;
+
+    log_override(
+        &tx,
+        &game1,
+        "swap",
+        Some(&game2.id),
+        (Some(&team1_id), Some(&team2_id)),
+        (None, None),
+        reason,
+        &warnings,
+    )?;
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(OverrideResult {
+        applied: true,
+        warnings,
+    })
+}
+
+/// Moves a game to another active court of its wave. A game already on that
+/// court takes the court the moved game leaves. Teams that already played on
+/// the court are warned about, and like a swap, nothing changes while there
+/// are warnings unless `confirm` is set.
+#[tauri::command]
+pub fn move_game_to_court(
+    db: State<Database>,
+    game_id: String,
+    court_number: i32,
+    reason: Option<String>,
+    confirm: bool,
+) -> Result<OverrideResult, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let game = load_open_game(&conn, &game_id)?;
+
+    if game.is_bye {
+        return Err("A BYE is not played on a court.".to_string());
+    }
+    if game.court_number == court_number {
+        return Err(format!("The game is already on court {}.", court_number));
+    }
+    if !load_court_plan(&conn, &game.tournament_id)?
+        .all()
+        .contains(&court_number)
+    {
+        return Err(format!("Court {} is not an active court.", court_number));
+    }
+
+    let occupant: Option<(String, bool, Option<String>, Option<String>)> = match conn.query_row(
+        r#"
+        SELECT id, is_bye, team1_id, team2_id, is_bye = 0 AND (team1_score IS NOT NULL OR is_forfeit = 1)
+        FROM qualifying_games
+        WHERE round_id = ?1 AND slot_number = ?2 AND court_number = ?3 AND id != ?4
+        "#,
+        params![game.round_id, game.slot_number, court_number, game.id],
+        |row| {
+            Ok((
+                (row.get(0)?, row.get::<_, i32>(1)? != 0, row.get(2)?, row.get(3)?),
+                row.get::<_, i32>(4)? != 0,
+            ))
+        },
+    ) {
+        Ok((_, true)) => {
+            return Err(format!("The game on court {} has already been played.", court_number))
+        }
+        Ok((occupant, false)) => Some(occupant),
+        Err(rusqlite::Error::QueryReturnedNoRows) => None,
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let mut warnings = Vec::new();
+
+    if let Some((_, false, team1_id, team2_id)) = &occupant {
+        warnings.push(OverrideWarning {
+            kind: OverrideWarningKind::CourtTaken,
+            team_ids: [team1_id, team2_id]
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect(),
+            court_number: Some(court_number),
+            message: format!(
+                "Court {} is taken: the game on it moves to court {}.",
+                court_number, game.court_number
+            ),
+        });
+    }
+
+    for team_id in [&game.team1_id, &game.team2_id].into_iter().flatten() {
+        let played_there = seen_in_other_rounds(
+            &conn,
+            "SELECT COUNT(*) > 0 FROM court_history WHERE round_id != ?1 AND tournament_id = ?2 AND team_id = ?3 AND court_number = ?4",
+            &game.round_id,
+            &[&game.tournament_id, team_id, &court_number],
+        )?;
+        if played_there {
+            let team = load_team(&conn, team_id)?;
+            warnings.push(OverrideWarning {
+                kind: OverrideWarningKind::CourtRepeat,
+                team_ids: vec![team.id.clone()],
+                court_number: Some(court_number),
+                message: format!(
+                    "{} has already played on court {}.",
+                    team.captain, court_number
+                ),
+            });
+        }
+    }
+
+    if !warnings.is_empty() && !confirm {
+        return Ok(OverrideResult {
+            applied: false,
+            warnings,
+        });
+    }
+
+    // The game and its occupant never end up sharing a court
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+
+    let mut moves = vec![(game.id.clone(), court_number)];
+    if let Some((occupant_id, _, _, _)) = &occupant {
+        moves.push((occupant_id.clone(), game.court_number));
+    }
+    for (id, court) in &moves {
+        tx.execute(
+            "UPDATE qualifying_games SET court_number = ?2 WHERE id = ?1",
+            params![id, court],
+        )
+        .map_err(|e| e.to_string())?;
+        tx.execute(
+            r#"
+            UPDATE court_history SET court_number = ?3
+            WHERE round_id = ?1 AND team_id IN (SELECT team1_id FROM qualifying_games WHERE id = ?2
+                                                UNION SELECT team2_id FROM qualifying_games WHERE id = ?2)
+            "#,
+            params![game.round_id, id, court],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    log_override(
+        &tx,
+        &game,
+        "court",
+        occupant.as_ref().map(|(id, _, _, _)| id.as_str()),
+        (None, None),
+        (Some(game.court_number), Some(court_number)),
+        reason,
+        &warnings,
+    )?;
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(OverrideResult {
+        applied: true,
+        warnings,
+    })
+}
+
+fn override_from_row(row: &rusqlite::Row) -> rusqlite::Result<PairingOverride> {
+    let warnings: String = row.get(12)?;
+
+    Ok(PairingOverride {
+        id: row.get(0)?,
+        tournament_id: row.get(1)?,
+        round_id: row.get(2)?,
+        round_number: row.get(3)?,
+        kind: row.get(4)?,
+        game1_id: row.get(5)?,
+        game2_id: row.get(6)?,
+        team1_id: row.get(7)?,
+        team2_id: row.get(8)?,
+        old_court: row.get(9)?,
+        new_court: row.get(10)?,
+        reason: row.get(11)?,
+        warnings: serde_json::from_str(&warnings).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(12, rusqlite::types::Type::Text, Box::new(e))
+        })?,
+        created_at: row.get(13)?,
+    })
+}
+
+#[tauri::command]
+pub fn get_pairing_overrides(
+    db: State<Database>,
+    tournament_id: String,
+) -> Result<Vec<PairingOverride>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT o.id, o.tournament_id, o.round_id, r.round_number, o.kind, o.game1_id, o.game2_id,
+                   o.team1_id, o.team2_id, o.old_court, o.new_court, o.reason, o.warnings, o.created_at
+            FROM pairing_overrides o
+            JOIN qualifying_rounds r ON o.round_id = r.id
+            WHERE o.tournament_id = ?1
+            ORDER BY o.created_at
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let overrides = stmt
+        .query_map(params![tournament_id], override_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(overrides)
+}
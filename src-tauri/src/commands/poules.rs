@@ -0,0 +1,475 @@
+use crate::commands::draws::DrawRng;
+use crate::db::Database;
+use crate::models::{PoolAssignment, Team, TeamStanding};
+use crate::pairing::avoidance::AvoidancePolicy;
+use rand::seq::SliceRandom;
+use rusqlite::params;
+use std::collections::{HashMap, HashSet};
+use tauri::State;
+use uuid::Uuid;
+
+/// Number of teams per pool that qualify for the brackets
+const QUALIFIERS_PER_POOL: i32 = 2;
+
+#[tauri::command]
+pub fn get_pools(
+    db: State<Database>,
+    tournament_id: String,
+) -> Result<Vec<PoolAssignment>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT id, tournament_id, team_id, pool_number, position, pool_rank
+            FROM pool_assignments
+            WHERE tournament_id = ?1
+            ORDER BY pool_number, position
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let pools = stmt
+        .query_map(params![tournament_id], |row| {
+            Ok(PoolAssignment {
+                id: row.get(0)?,
+                tournament_id: row.get(1)?,
+                team_id: row.get(2)?,
+                pool_number: row.get(3)?,
+                position: row.get(4)?,
+                pool_rank: row.get(5)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(pools)
+}
+
+/// Pool sizes for a field: pools of 4, with up to three pools of 3 absorbing the remainder.
+fn pool_sizes(team_count: usize) -> Result<Vec<usize>, String> {
+    let threes = match team_count % 4 {
+        0 => 0,
+        3 => 1,
+        2 => 2,
+        _ => 3,
+    };
+
+    if team_count < 3 || team_count < threes * 3 {
+        return Err(format!(
+            "Cannot split {} teams into poules of 4 or 3.",
+            team_count
+        ));
+    }
+
+    let fours = (team_count - threes * 3) / 4;
+    let mut sizes = vec![4; fours];
+    sizes.extend(vec![3; threes]);
+    Ok(sizes)
+}
+
+/// Draws the teams into poules.
+///
+/// With an avoidance policy, teams of the same club or region are dealt into
+/// different poules for as long as there are poules left to spread them over.
+pub fn draw_pools(
+    teams: &[Team],
+    avoidance: AvoidancePolicy,
+    rng: &mut DrawRng,
+) -> Result<Vec<Vec<String>>, String> {
+    let sizes = pool_sizes(teams.len())?;
+
+    let mut shuffled: Vec<&Team> = teams.iter().collect();
+    shuffled.shuffle(rng);
+    avoidance.spread_order(&mut shuffled);
+
+    // Deal the teams over the poules like cards, skipping poules that are full
+    let mut pools: Vec<Vec<String>> = sizes.iter().map(|&size| Vec::with_capacity(size)).collect();
+    let mut pool_idx = 0;
+    for team in shuffled {
+        while pools[pool_idx].len() >= sizes[pool_idx] {
+            pool_idx = (pool_idx + 1) % pools.len();
+        }
+        pools[pool_idx].push(team.id.clone());
+        pool_idx = (pool_idx + 1) % pools.len();
+    }
+
+    // The position inside a poule decides the round 1 games (A-B, C-D)
+    for pool in &mut pools {
+        pool.shuffle(rng);
+    }
+
+    Ok(pools)
+}
+
+/// Stores the poules drawn for a tournament, replacing any earlier draw.
+pub fn save_pools(
+    conn: &rusqlite::Connection,
+    tournament_id: &str,
+    pools: &[Vec<String>],
+) -> Result<(), String> {
+    conn.execute(
+        "DELETE FROM pool_assignments WHERE tournament_id = ?1",
+        params![tournament_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    for (pool_idx, pool) in pools.iter().enumerate() {
+        for (position, team_id) in pool.iter().enumerate() {
+            conn.execute(
+                r#"
+                INSERT INTO pool_assignments (id, tournament_id, team_id, pool_number, position, pool_rank)
+                VALUES (?1, ?2, ?3, ?4, ?5, NULL)
+                "#,
+                params![
+                    Uuid::new_v4().to_string(),
+                    tournament_id,
+                    team_id,
+                    pool_idx as i32 + 1,
+                    position as i32 + 1
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Loads the poules drawn for a tournament, each in draw position order.
+pub fn load_pools(
+    conn: &rusqlite::Connection,
+    tournament_id: &str,
+) -> Result<Vec<Vec<String>>, String> {
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT pool_number, team_id
+            FROM pool_assignments
+            WHERE tournament_id = ?1
+            ORDER BY pool_number, position
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let rows: Vec<(i32, String)> = stmt
+        .query_map(params![tournament_id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut pools: Vec<Vec<String>> = Vec::new();
+    let mut current_pool = None;
+    for (pool_number, team_id) in rows {
+        if current_pool != Some(pool_number) {
+            pools.push(Vec::new());
+            current_pool = Some(pool_number);
+        }
+        if let Some(pool) = pools.last_mut() {
+            pool.push(team_id);
+        }
+    }
+
+    Ok(pools)
+}
+
+/// Pairings for one round of poules.
+///
+/// Poules of 4 play A-B and C-D, then winners vs winners and losers vs losers;
+/// the 2-0 team qualifies first, the 0-2 team is out, and the two 1-1 teams
+/// play a barrage for the second place, even when they already met in round 1.
+/// Poules of 3 play a mini round-robin: A-B, then C against the loser, then C
+/// against the winner.
+pub fn generate_poules_pairings(
+    pools: &[Vec<String>],
+    standings: &HashMap<String, TeamStanding>,
+    pairing_history: &HashSet<(String, String)>,
+    round_number: i32,
+) -> Result<Vec<(String, Option<String>)>, String> {
+    let wins = |id: &String| standings.get(id).map(|s| s.wins).unwrap_or(0);
+    let losses = |id: &String| standings.get(id).map(|s| s.losses).unwrap_or(0);
+
+    let mut pairings: Vec<(String, Option<String>)> = Vec::new();
+
+    for (pool_idx, pool) in pools.iter().enumerate() {
+        let incomplete = || format!("Poule {} results are incomplete.", pool_idx + 1);
+
+        match (pool.len(), round_number) {
+            (4, 1) => {
+                pairings.push((pool[0].clone(), Some(pool[1].clone())));
+                pairings.push((pool[2].clone(), Some(pool[3].clone())));
+            }
+            (4, 2) => {
+                let winners: Vec<&String> = pool.iter().filter(|id| wins(id) == 1).collect();
+                let losers: Vec<&String> = pool.iter().filter(|id| wins(id) == 0).collect();
+                if winners.len() != 2 || losers.len() != 2 {
+                    return Err(incomplete());
+                }
+                pairings.push((winners[0].clone(), Some(winners[1].clone())));
+                pairings.push((losers[0].clone(), Some(losers[1].clone())));
+            }
+            (4, 3) => {
+                // Barrage between the two 1-1 teams
+                let barrage: Vec<&String> = pool
+                    .iter()
+                    .filter(|id| wins(id) == 1 && losses(id) == 1)
+                    .collect();
+                if barrage.len() != 2 {
+                    return Err(incomplete());
+                }
+                pairings.push((barrage[0].clone(), Some(barrage[1].clone())));
+            }
+            (3, 1) => {
+                pairings.push((pool[0].clone(), Some(pool[1].clone())));
+            }
+            (3, 2) => {
+                let loser = pool[..2]
+                    .iter()
+                    .find(|id| wins(id) == 0 && losses(id) == 1)
+                    .ok_or_else(incomplete)?;
+                pairings.push((pool[2].clone(), Some(loser.clone())));
+            }
+            (3, 3) => {
+                let opponent = pool[..2]
+                    .iter()
+                    .find(|id| !pairing_history.contains(&(pool[2].clone(), (*id).clone())))
+                    .ok_or_else(incomplete)?;
+                pairings.push((pool[2].clone(), Some(opponent.clone())));
+            }
+            (_, round) if round > 3 => {
+                return Err("Poules only have 3 rounds.".to_string());
+            }
+            (size, _) => {
+                return Err(format!("Poule {} has {} teams.", pool_idx + 1, size));
+            }
+        }
+    }
+
+    Ok(pairings)
+}
+
+/// Ranks every poule once its last round is complete: wins, then fewest losses,
+/// differential and points for. The top two of each poule qualify, the others
+/// are marked eliminated.
+pub fn rank_pools(conn: &rusqlite::Connection, tournament_id: &str) -> Result<(), String> {
+    let pools = load_pools(conn, tournament_id)?;
+
+    for pool in pools {
+        let mut records: Vec<(String, i32, i32, i32, i32)> = Vec::new();
+        for team_id in pool {
+            let (wins, losses, differential, points_for): (i32, i32, i32, i32) = conn
+                .query_row(
+                    r#"
+                    SELECT wins, losses, differential, points_for
+                    FROM team_standings
+                    WHERE tournament_id = ?1 AND team_id = ?2
+                    "#,
+                    params![tournament_id, team_id],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+                )
+                .map_err(|e| e.to_string())?;
+            records.push((team_id, wins, losses, differential, points_for));
+        }
+
+        records.sort_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then_with(|| a.2.cmp(&b.2))
+                .then_with(|| b.3.cmp(&a.3))
+                .then_with(|| b.4.cmp(&a.4))
+        });
+
+        for (idx, (team_id, ..)) in records.iter().enumerate() {
+            let pool_rank = idx as i32 + 1;
+            conn.execute(
+                "UPDATE pool_assignments SET pool_rank = ?3 WHERE tournament_id = ?1 AND team_id = ?2",
+                params![tournament_id, team_id, pool_rank],
+            )
+            .map_err(|e| e.to_string())?;
+            conn.execute(
+                "UPDATE team_standings SET is_eliminated = ?3 WHERE tournament_id = ?1 AND team_id = ?2",
+                params![
+                    tournament_id,
+                    team_id,
+                    if pool_rank > QUALIFIERS_PER_POOL { 1 } else { 0 }
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::draws::seeded_rng;
+    use crate::db::schema::create_tables;
+
+    fn team(id: &str) -> Team {
+        Team {
+            id: id.to_string(),
+            tournament_id: "t".to_string(),
+            captain: id.to_string(),
+            player2: String::new(),
+            player3: None,
+            region: None,
+            club: None,
+            seed: None,
+            created_at: String::new(),
+            is_active: true,
+            withdrawn_at: None,
+        }
+    }
+
+    fn standing(id: &str, wins: i32, losses: i32) -> TeamStanding {
+        TeamStanding {
+            id: id.to_string(),
+            tournament_id: "t".to_string(),
+            team_id: id.to_string(),
+            wins,
+            losses,
+            points_for: 0,
+            points_against: 0,
+            differential: 0,
+            buchholz_score: 0.0,
+            median_buchholz_score: 0.0,
+            cut_buchholz_score: 0.0,
+            fine_buchholz_score: 0.0,
+            sonneborn_berger_score: 0.0,
+            point_quotient: 0.0,
+            head_to_head_wins: 0,
+            is_eliminated: false,
+            rank: 0,
+            is_tied: false,
+            pool_number: None,
+            pool_rank: None,
+        }
+    }
+
+    fn records(list: &[(&str, i32, i32)]) -> HashMap<String, TeamStanding> {
+        list.iter()
+            .map(|&(id, wins, losses)| (id.to_string(), standing(id, wins, losses)))
+            .collect()
+    }
+
+    fn ids(pool: &[&str]) -> Vec<String> {
+        pool.iter().map(|id| id.to_string()).collect()
+    }
+
+    fn game(a: &str, b: &str) -> (String, Option<String>) {
+        (a.to_string(), Some(b.to_string()))
+    }
+
+    #[test]
+    fn pool_sizes_for_small_fields() {
+        assert!(pool_sizes(5).is_err());
+        assert_eq!(pool_sizes(6).unwrap(), vec![3, 3]);
+        assert_eq!(pool_sizes(7).unwrap(), vec![4, 3]);
+        assert_eq!(pool_sizes(8).unwrap(), vec![4, 4]);
+        assert_eq!(pool_sizes(9).unwrap(), vec![3, 3, 3]);
+        assert_eq!(pool_sizes(10).unwrap(), vec![4, 3, 3]);
+        assert_eq!(pool_sizes(11).unwrap(), vec![4, 4, 3]);
+        assert_eq!(pool_sizes(12).unwrap(), vec![4, 4, 4]);
+        assert_eq!(pool_sizes(13).unwrap(), vec![4, 3, 3, 3]);
+    }
+
+    #[test]
+    fn draw_pools_places_every_team_once() {
+        let teams: Vec<Team> = (0..11).map(|i| team(&format!("t{}", i))).collect();
+        let pools = draw_pools(&teams, AvoidancePolicy::None, &mut seeded_rng(3)).unwrap();
+        let sizes: Vec<usize> = pools.iter().map(|p| p.len()).collect();
+        assert_eq!(sizes, vec![4, 4, 3]);
+        let mut drawn: Vec<&String> = pools.iter().flatten().collect();
+        drawn.sort();
+        drawn.dedup();
+        assert_eq!(drawn.len(), 11);
+        assert_eq!(pools, draw_pools(&teams, AvoidancePolicy::None, &mut seeded_rng(3)).unwrap());
+    }
+
+    #[test]
+    fn poule_of_four_pairs_winners_then_plays_the_barrage() {
+        let pool = vec![ids(&["a", "b", "c", "d"])];
+        let history = HashSet::new();
+
+        let round1 = generate_poules_pairings(&pool, &HashMap::new(), &history, 1).unwrap();
+        assert_eq!(round1, vec![game("a", "b"), game("c", "d")]);
+
+        // b and c won their first game
+        let after1 = records(&[("a", 0, 1), ("b", 1, 0), ("c", 1, 0), ("d", 0, 1)]);
+        let round2 = generate_poules_pairings(&pool, &after1, &history, 2).unwrap();
+        assert_eq!(round2, vec![game("b", "c"), game("a", "d")]);
+
+        // c is 2-0 and d 0-2: a and b, both 1-1, play the barrage although they met
+        let after2 = records(&[("a", 1, 1), ("b", 1, 1), ("c", 2, 0), ("d", 0, 2)]);
+        let round3 = generate_poules_pairings(&pool, &after2, &history, 3).unwrap();
+        assert_eq!(round3, vec![game("a", "b")]);
+
+        // Results missing
+        assert!(generate_poules_pairings(&pool, &after1, &history, 3).is_err());
+    }
+
+    #[test]
+    fn poule_of_three_plays_a_mini_round_robin() {
+        let pool = vec![ids(&["a", "b", "c"])];
+        let mut history = HashSet::new();
+
+        assert_eq!(generate_poules_pairings(&pool, &HashMap::new(), &history, 1).unwrap(), vec![game("a", "b")]);
+
+        // a beat b: c plays the loser first
+        history.insert(("a".to_string(), "b".to_string()));
+        history.insert(("b".to_string(), "a".to_string()));
+        let after1 = records(&[("a", 1, 0), ("b", 0, 1), ("c", 0, 0)]);
+        assert_eq!(generate_poules_pairings(&pool, &after1, &history, 2).unwrap(), vec![game("c", "b")]);
+
+        // then the team it has not met yet
+        history.insert(("c".to_string(), "b".to_string()));
+        history.insert(("b".to_string(), "c".to_string()));
+        let after2 = records(&[("a", 1, 0), ("b", 0, 2), ("c", 1, 0)]);
+        assert_eq!(generate_poules_pairings(&pool, &after2, &history, 3).unwrap(), vec![game("c", "a")]);
+    }
+
+    #[test]
+    fn rank_pools_orders_by_record() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        // Only the poules and the standings matter here, not the tournament and its teams
+        conn.execute_batch("PRAGMA foreign_keys = OFF;").unwrap();
+        save_pools(&conn, "t", &[ids(&["loser", "out", "first", "winner"])]).unwrap();
+        for (id, wins, losses) in [("first", 2, 0), ("winner", 2, 1), ("loser", 1, 2), ("out", 0, 2)] {
+            conn.execute(
+                "INSERT INTO team_standings (id, tournament_id, team_id, wins, losses) VALUES (?1, 't', ?1, ?2, ?3)",
+                params![id, wins, losses],
+            )
+            .unwrap();
+        }
+
+        rank_pools(&conn, "t").unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT p.team_id, s.is_eliminated
+                FROM pool_assignments p
+                JOIN team_standings s ON s.team_id = p.team_id
+                ORDER BY p.pool_rank
+                "#,
+            )
+            .unwrap();
+        let ranked: Vec<(String, bool)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get::<_, i32>(1)? != 0)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            ranked,
+            vec![
+                ("first".to_string(), false),
+                ("winner".to_string(), false),
+                ("loser".to_string(), true),
+                ("out".to_string(), true),
+            ]
+        );
+    }
+}
@@ -1,10 +1,21 @@
 use crate::db::Database;
-use crate::models::{GameWithTeams, QualifyingGame, QualifyingRound, Team, TeamStanding};
+use crate::models::{ByeScoring, GameWithTeams, PairingPreview, PairingQualityReport, PreviewGame, QualifyingGame, QualifyingRound, Team, TeamStanding};
+use crate::commands::courts::{load_court_plan, sync_courts, CourtPlan};
+use crate::commands::draws::{new_seed, record_draw, seeded_rng, DrawRng};
+use crate::commands::ends::{has_ends, Scored};
+use crate::commands::melee::{generate_melee_round, rebuild_player_standings};
+use crate::commands::poules::{draw_pools, generate_poules_pairings, load_pools, rank_pools, save_pools};
+use crate::commands::ranking::rank_teams;
 use crate::commands::teams::get_team_by_id;
+use crate::commands::scoring::{validate_score, ScoreError};
+use crate::commands::tournaments::{get_bye_scoring, get_forfeit_scoring, get_game_rules};
+use crate::pairing::assignment::min_cost_assignment;
+use crate::pairing::avoidance::{pair_avoiding, AvoidancePolicy};
+use crate::pairing::matching::min_cost_perfect_matching;
 use chrono::Utc;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
 use rusqlite::params;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use tauri::State;
 use uuid::Uuid;
@@ -19,7 +30,7 @@ pub fn get_qualifying_rounds(
     let mut stmt = conn
         .prepare(
             r#"
-            SELECT id, tournament_id, round_number, is_complete, created_at
+            SELECT id, tournament_id, round_number, is_complete, started_at, ended_at, created_at, draw_method
             FROM qualifying_rounds
             WHERE tournament_id = ?1
             ORDER BY round_number ASC
@@ -34,7 +45,10 @@ pub fn get_qualifying_rounds(
                 tournament_id: row.get(1)?,
                 round_number: row.get(2)?,
                 is_complete: row.get::<_, i32>(3)? != 0,
-                created_at: row.get(4)?,
+                draw_method: row.get(7)?,
+                started_at: row.get(4)?,
+                ended_at: row.get(5)?,
+                created_at: row.get(6)?,
             })
         })
         .map_err(|e| e.to_string())?
@@ -54,10 +68,10 @@ pub fn get_games_for_round(
     let mut stmt = conn
         .prepare(
             r#"
-            SELECT id, round_id, court_number, team1_id, team2_id, team1_score, team2_score, is_bye
+            SELECT id, round_id, court_number, team1_id, team2_id, team1_score, team2_score, is_bye, slot_number, is_forfeit, ended_at
             FROM qualifying_games
             WHERE round_id = ?1
-            ORDER BY court_number ASC
+            ORDER BY slot_number ASC, court_number ASC
             "#,
         )
         .map_err(|e| e.to_string())?;
@@ -73,6 +87,9 @@ pub fn get_games_for_round(
                 team1_score: row.get(5)?,
                 team2_score: row.get(6)?,
                 is_bye: row.get::<_, i32>(7)? != 0,
+                slot_number: row.get(8)?,
+                is_forfeit: row.get::<_, i32>(9)? != 0,
+                ended_at: row.get(10)?,
             })
         })
         .map_err(|e| e.to_string())?
@@ -102,6 +119,9 @@ pub fn get_games_for_round(
             team1_score: game.team1_score,
             team2_score: game.team2_score,
             is_bye: game.is_bye,
+            is_forfeit: game.is_forfeit,
+            slot_number: game.slot_number,
+            ended_at: game.ended_at,
             team1,
             team2,
         });
@@ -119,6 +139,119 @@ pub fn generate_pairings(
     generate_single_round(&conn, &tournament_id)
 }
 
+/// Draws the next round without saving anything, with a report on how good
+/// its pairings are. Previewing again rerolls the draw.
+#[tauri::command]
+pub fn preview_pairings(db: State<Database>, tournament_id: String) -> Result<PairingPreview, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let (pairing_method, round_number) = next_round(&conn, &tournament_id)?;
+    if pairing_method == "melee" {
+        return Err("A mêlée is drawn with the players present and cannot be previewed.".to_string());
+    }
+
+    let inputs = load_round_inputs(&conn, &tournament_id, pairing_method, round_number)?;
+    let seed = new_seed();
+    let draw = draw_round(&inputs, &mut seeded_rng(seed))?;
+
+    Ok(preview_of(&tournament_id, &inputs, seed, &draw))
+}
+
+/// Saves a previewed round. It is drawn again from the preview's seed, and
+/// refused if the teams, results or courts changed since and the round no
+/// longer comes out as previewed.
+#[tauri::command]
+pub fn accept_pairing_preview(db: State<Database>, preview: PairingPreview) -> Result<QualifyingRound, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let (pairing_method, round_number) = next_round(&conn, &preview.tournament_id)?;
+    if round_number != preview.round_number {
+        return Err(format!("Round {} has already been drawn.", preview.round_number));
+    }
+
+    let seed: u64 = preview
+        .seed
+        .parse()
+        .map_err(|_| format!("Invalid draw seed: {}", preview.seed))?;
+    let inputs = load_round_inputs(&conn, &preview.tournament_id, pairing_method, round_number)?;
+    let draw = draw_round(&inputs, &mut seeded_rng(seed))?;
+
+    if preview_of(&preview.tournament_id, &inputs, seed, &draw).games != preview.games {
+        return Err("The teams or results changed since the preview: preview the round again.".to_string());
+    }
+
+    save_round(&conn, &preview.tournament_id, &inputs, seed, &draw)
+}
+
+/// Describes a drawn round: its games with what is wrong with each pairing,
+/// and the totals for the round.
+fn preview_of(tournament_id: &str, inputs: &RoundDrawInputs, seed: u64, draw: &RoundDraw) -> PairingPreview {
+    let teams: HashMap<&str, &Team> = inputs.teams.iter().map(|t| (t.id.as_str(), t)).collect();
+    let wins: HashMap<&str, i32> = inputs
+        .standings
+        .iter()
+        .map(|s| (s.team_id.as_str(), s.wins))
+        .collect();
+    let played: HashSet<(&str, &str)> = inputs
+        .pairing_history
+        .iter()
+        .flat_map(|(t1, t2)| [(t1.as_str(), t2.as_str()), (t2.as_str(), t1.as_str())])
+        .collect();
+    let region = |team_id: &str| {
+        teams
+            .get(team_id)
+            .and_then(|t| t.region.as_deref())
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+    };
+
+    let games: Vec<PreviewGame> = draw
+        .games
+        .iter()
+        .map(|game| {
+            let (is_repeat, same_region, wins_gap) = match &game.team2_id {
+                Some(team2_id) => {
+                    let (t1, t2) = (game.team1_id.as_str(), team2_id.as_str());
+                    (
+                        played.contains(&(t1, t2)),
+                        region(t1).is_some() && region(t1) == region(t2),
+                        (wins.get(t1).copied().unwrap_or(0) - wins.get(t2).copied().unwrap_or(0)).abs(),
+                    )
+                }
+                None => (false, false, 0),
+            };
+            PreviewGame {
+                slot_number: game.slot_number,
+                court_number: game.court_number,
+                team1_id: game.team1_id.clone(),
+                team2_id: game.team2_id.clone(),
+                is_repeat,
+                same_region,
+                wins_gap,
+            }
+        })
+        .collect();
+
+    let report = PairingQualityReport {
+        repeats: games.iter().filter(|g| g.is_repeat).count() as i32,
+        same_region_pairs: games.iter().filter(|g| g.same_region).count() as i32,
+        max_wins_gap: games.iter().map(|g| g.wins_gap).max().unwrap_or(0),
+        total_wins_gap: games.iter().map(|g| g.wins_gap).sum(),
+        bye_team_id: games
+            .iter()
+            .find(|g| g.team2_id.is_none())
+            .map(|g| g.team1_id.clone()),
+    };
+
+    PairingPreview {
+        tournament_id: tournament_id.to_string(),
+        round_number: inputs.round_number,
+        seed: seed.to_string(),
+        games,
+        report,
+    }
+}
+
 #[tauri::command]
 pub fn generate_all_qualifying_rounds(
     db: State<Database>,
@@ -142,6 +275,12 @@ pub fn generate_all_qualifying_rounds(
     if pairing_method == "poolPlay" {
         return Err("Pool Play requires round-by-round generation. Use 'Generate Next Round' instead.".to_string());
     }
+    if pairing_method == "poules" {
+        return Err("Poules require round-by-round generation. Use 'Generate Next Round' instead.".to_string());
+    }
+    if pairing_method == "melee" {
+        return Err("A mêlée is drawn round by round, with the players present. Use 'Generate Next Round' instead.".to_string());
+    }
 
     // Get current round number
     let current_round: i32 = conn
@@ -177,12 +316,27 @@ fn generate_single_round(
     conn: &rusqlite::Connection,
     tournament_id: &str,
 ) -> Result<QualifyingRound, String> {
-    // Get tournament info
-    let (pairing_method, number_of_courts, region_avoidance): (String, i32, bool) = conn
+    let (pairing_method, round_number) = next_round(conn, tournament_id)?;
+
+    // Mêlée: players, not teams, are drawn
+    if pairing_method == "melee" {
+        return generate_melee_round(conn, tournament_id, round_number);
+    }
+
+    let inputs = load_round_inputs(conn, tournament_id, pairing_method, round_number)?;
+    let seed = new_seed();
+    let draw = draw_round(&inputs, &mut seeded_rng(seed))?;
+
+    save_round(conn, tournament_id, &inputs, seed, &draw)
+}
+
+/// Pairing method and number of the next round, once the rounds so far allow it
+fn next_round(conn: &rusqlite::Connection, tournament_id: &str) -> Result<(String, i32), String> {
+    let pairing_method: String = conn
         .query_row(
-            "SELECT pairing_method, number_of_courts, region_avoidance FROM tournaments WHERE id = ?1",
+            "SELECT pairing_method FROM tournaments WHERE id = ?1",
             params![tournament_id],
-            |row| Ok((row.get(0)?, row.get(1)?, row.get::<_, i32>(2)? != 0)),
+            |row| row.get(0),
         )
         .map_err(|e| e.to_string())?;
 
@@ -197,8 +351,8 @@ fn generate_single_round(
 
     let new_round_number = current_round + 1;
 
-    // Swiss and Pool Play: verify prior round is complete before generating next
-    if (pairing_method == "swiss" || pairing_method == "poolPlay") && current_round > 0 {
+    // Swiss, Pool Play, Poules and Mêlée: verify prior round is complete before generating next
+    if matches!(pairing_method.as_str(), "swiss" | "poolPlay" | "poules" | "melee") && current_round > 0 {
         let prior_round_complete: bool = conn
             .query_row(
                 "SELECT is_complete FROM qualifying_rounds WHERE tournament_id = ?1 AND round_number = ?2",
@@ -217,19 +371,54 @@ fn generate_single_round(
         return Err("Pool Play format only has 3 rounds.".to_string());
     }
 
-    // Get all teams
+    // Poules: max 3 rounds
+    if pairing_method == "poules" && new_round_number > 3 {
+        return Err("Poules format only has 3 rounds.".to_string());
+    }
+
+    Ok((pairing_method, new_round_number))
+}
+
+/// Loads everything the next round of teams is drawn from
+fn load_round_inputs(
+    conn: &rusqlite::Connection,
+    tournament_id: &str,
+    pairing_method: String,
+    round_number: i32,
+) -> Result<RoundDrawInputs, String> {
+    let (avoidance_policy, avoidance_rounds, first_round_draw): (String, Option<i32>, String) = conn
+        .query_row(
+            "SELECT avoidance_policy, avoidance_rounds, first_round_draw FROM tournaments WHERE id = ?1",
+            params![tournament_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .map_err(|e| e.to_string())?;
+
+    // Get the teams still in play. A round-robin or Pool Play schedule is fixed
+    // once its first round is drawn: a team withdrawn since keeps its place,
+    // and its games are forfeited when the round is saved
+    let keeps_withdrawn = matches!(pairing_method.as_str(), "roundRobin" | "poolPlay");
     let mut stmt = conn
         .prepare(
             r#"
-            SELECT id, tournament_id, captain, player2, player3, region, club, created_at
+            SELECT id, tournament_id, captain, player2, player3, region, club, created_at, is_active, withdrawn_at, seed
             FROM teams
             WHERE tournament_id = ?1
+              AND (is_active = 1 OR (?2 AND id IN (
+                  SELECT g.team1_id FROM qualifying_games g
+                  JOIN qualifying_rounds r ON g.round_id = r.id
+                  WHERE r.tournament_id = ?1
+                  UNION
+                  SELECT g.team2_id FROM qualifying_games g
+                  JOIN qualifying_rounds r ON g.round_id = r.id
+                  WHERE r.tournament_id = ?1
+              )))
             "#,
         )
         .map_err(|e| e.to_string())?;
 
     let teams: Vec<Team> = stmt
-        .query_map(params![tournament_id], |row| {
+        .query_map(params![tournament_id, keeps_withdrawn], |row| {
             Ok(Team {
                 id: row.get(0)?,
                 tournament_id: row.get(1)?,
@@ -239,6 +428,9 @@ fn generate_single_round(
                 region: row.get(5)?,
                 club: row.get(6)?,
                 created_at: row.get(7)?,
+                is_active: row.get::<_, i32>(8)? != 0,
+                withdrawn_at: row.get(9)?,
+                seed: row.get(10)?,
             })
         })
         .map_err(|e| e.to_string())?
@@ -254,15 +446,10 @@ fn generate_single_round(
         .prepare("SELECT team1_id, team2_id FROM pairing_history WHERE tournament_id = ?1")
         .map_err(|e| e.to_string())?;
 
-    let pairing_history: HashSet<(String, String)> = pairing_stmt
-        .query_map(params![tournament_id], |row| {
-            let t1: String = row.get(0)?;
-            let t2: String = row.get(1)?;
-            Ok((t1, t2))
-        })
+    let pairing_history: Vec<(String, String)> = pairing_stmt
+        .query_map(params![tournament_id], |row| Ok((row.get(0)?, row.get(1)?)))
         .map_err(|e| e.to_string())?
         .filter_map(|r| r.ok())
-        .flat_map(|(t1, t2)| vec![(t1.clone(), t2.clone()), (t2, t1)])
         .collect();
 
     // Get court history for rotation
@@ -270,30 +457,19 @@ fn generate_single_round(
         .prepare("SELECT team_id, court_number FROM court_history WHERE tournament_id = ?1")
         .map_err(|e| e.to_string())?;
 
-    let court_history: HashMap<String, Vec<i32>> = {
-        let mut map: HashMap<String, Vec<i32>> = HashMap::new();
-        let rows = court_stmt
-            .query_map(params![tournament_id], |row| {
-                let team_id: String = row.get(0)?;
-                let court: i32 = row.get(1)?;
-                Ok((team_id, court))
-            })
-            .map_err(|e| e.to_string())?;
-
-        for row in rows {
-            if let Ok((team_id, court)) = row {
-                map.entry(team_id).or_default().push(court);
-            }
-        }
-        map
-    };
+    let court_history: Vec<(String, i32)> = court_stmt
+        .query_map(params![tournament_id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| e.to_string())?
+        .filter_map(|r| r.ok())
+        .collect();
 
     // Get standings for Swiss pairing
     let tournament_id_owned = tournament_id.to_string();
     let mut standings_stmt = conn
         .prepare(
             r#"
-            SELECT team_id, wins, losses, points_for, points_against, differential, buchholz_score, fine_buchholz_score, point_quotient, is_eliminated
+            SELECT team_id, wins, losses, points_for, points_against, differential, buchholz_score, fine_buchholz_score, point_quotient, is_eliminated,
+                   median_buchholz_score, cut_buchholz_score, sonneborn_berger_score, head_to_head_wins
             FROM team_standings
             WHERE tournament_id = ?1
             ORDER BY wins DESC, buchholz_score DESC, fine_buchholz_score DESC, differential DESC
@@ -301,7 +477,7 @@ fn generate_single_round(
         )
         .map_err(|e| e.to_string())?;
 
-    let standings: HashMap<String, TeamStanding> = standings_stmt
+    let standings: Vec<TeamStanding> = standings_stmt
         .query_map(params![tournament_id], |row| {
             Ok(TeamStanding {
                 id: String::new(),
@@ -313,28 +489,94 @@ fn generate_single_round(
                 points_against: row.get(4)?,
                 differential: row.get(5)?,
                 buchholz_score: row.get(6)?,
+                median_buchholz_score: row.get(10)?,
+                cut_buchholz_score: row.get(11)?,
                 fine_buchholz_score: row.get(7)?,
+                sonneborn_berger_score: row.get(12)?,
                 point_quotient: row.get(8)?,
+                head_to_head_wins: row.get(13)?,
                 is_eliminated: row.get::<_, i32>(9)? != 0,
                 rank: 0,
+                is_tied: false,
+                pool_number: None,
+                pool_rank: None,
             })
         })
         .map_err(|e| e.to_string())?
         .filter_map(|r| r.ok())
-        .map(|s| (s.team_id.clone(), s))
         .collect();
 
-    // Generate pairings based on method
-    let pairings = match pairing_method.as_str() {
-        "swiss" => generate_swiss_pairings(&teams, &standings, &pairing_history, region_avoidance)?,
-        "swissHotel" => generate_swiss_hotel_pairings(&teams, &pairing_history, region_avoidance, new_round_number)?,
-        "roundRobin" => generate_round_robin_pairings(&teams, new_round_number)?,
-        "poolPlay" => generate_pool_play_round(&teams, &standings, &pairing_history, region_avoidance, new_round_number)?,
-        _ => return Err(format!("Unknown pairing method: {}", pairing_method)),
+    // Get teams that floated in the previous round (Swiss score groups)
+    let mut floats_stmt = conn
+        .prepare(
+            r#"
+            SELECT pf.team_id
+            FROM pairing_floats pf
+            JOIN qualifying_rounds qr ON pf.round_id = qr.id
+            WHERE pf.tournament_id = ?1 AND qr.round_number = ?2
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let previous_floaters: Vec<String> = floats_stmt
+        .query_map(params![tournament_id, round_number - 1], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    // Get teams that already received a BYE
+    let mut bye_stmt = conn
+        .prepare("SELECT team_id FROM bye_history WHERE tournament_id = ?1")
+        .map_err(|e| e.to_string())?;
+
+    let bye_history: Vec<String> = bye_stmt
+        .query_map(params![tournament_id], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    // The poules are drawn once, with the first round
+    let pools = if pairing_method == "poules" && round_number > 1 {
+        load_pools(conn, tournament_id)?
+    } else {
+        Vec::new()
     };
 
-    // Assign courts with rotation
-    let games = assign_courts(pairings, number_of_courts, &court_history)?;
+    Ok(RoundDrawInputs {
+        pairing_method,
+        round_number,
+        avoidance_policy,
+        avoidance_rounds,
+        teams,
+        standings,
+        pairing_history,
+        previous_floaters,
+        bye_history,
+        pools,
+        courts: load_court_plan(conn, tournament_id)?,
+        court_history,
+        first_round_draw: Some(first_round_draw),
+    })
+}
+
+/// Writes a drawn round: its games, their pairing, court and BYE history, the
+/// floats and the draw itself
+fn save_round(
+    conn: &rusqlite::Connection,
+    tournament_id: &str,
+    inputs: &RoundDrawInputs,
+    seed: u64,
+    draw: &RoundDraw,
+) -> Result<QualifyingRound, String> {
+    let new_round_number = inputs.round_number;
+    let draw_method = first_round_draw(inputs);
+
+    // The round goes on courts of the inventory
+    sync_courts(conn, tournament_id)?;
+
+    if !draw.pools.is_empty() {
+        save_pools(conn, tournament_id, &draw.pools)?;
+    }
 
     // Create the round
     let round_id = Uuid::new_v4().to_string();
@@ -342,23 +584,25 @@ fn generate_single_round(
 
     conn.execute(
         r#"
-        INSERT INTO qualifying_rounds (id, tournament_id, round_number, is_complete, created_at)
-        VALUES (?1, ?2, ?3, 0, ?4)
+        INSERT INTO qualifying_rounds (id, tournament_id, round_number, is_complete, draw_method, created_at)
+        VALUES (?1, ?2, ?3, 0, ?4, ?5)
         "#,
-        params![round_id, tournament_id, new_round_number, now],
+        params![round_id, tournament_id, new_round_number, draw_method, now],
     )
     .map_err(|e| e.to_string())?;
 
     // Insert games and track history
-    for (court_number, (team1_id, team2_id)) in games.iter().enumerate() {
+    for game in &draw.games {
         let game_id = Uuid::new_v4().to_string();
-        let court = (court_number as i32) + 1;
+        let court = game.court_number;
+        let team1_id = &game.team1_id;
+        let team2_id = &game.team2_id;
         let is_bye = team2_id.is_none();
 
         conn.execute(
             r#"
-            INSERT INTO qualifying_games (id, round_id, court_number, team1_id, team2_id, is_bye)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            INSERT INTO qualifying_games (id, round_id, court_number, team1_id, team2_id, is_bye, slot_number)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
             "#,
             params![
                 game_id,
@@ -366,167 +610,452 @@ fn generate_single_round(
                 court,
                 team1_id,
                 team2_id,
-                if is_bye { 1 } else { 0 }
+                if is_bye { 1 } else { 0 },
+                game.slot_number
             ],
         )
         .map_err(|e| e.to_string())?;
 
-        // Record pairing history
-        if let (Some(t1), Some(t2)) = (team1_id, team2_id) {
+        // Record BYE history
+        if is_bye {
             let history_id = Uuid::new_v4().to_string();
             conn.execute(
-                "INSERT INTO pairing_history (id, tournament_id, team1_id, team2_id, round_id) VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![history_id, tournament_id, t1, t2, round_id],
+                "INSERT INTO bye_history (id, tournament_id, team_id, round_id) VALUES (?1, ?2, ?3, ?4)",
+                params![history_id, tournament_id, team1_id, round_id],
             )
             .map_err(|e| e.to_string())?;
         }
 
-        // Record court history
-        if let Some(t1) = team1_id {
+        // Record pairing history
+        if let Some(t2) = team2_id {
             let history_id = Uuid::new_v4().to_string();
             conn.execute(
-                "INSERT INTO court_history (id, tournament_id, team_id, court_number, round_id) VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![history_id, tournament_id, t1, court, round_id],
+                "INSERT INTO pairing_history (id, tournament_id, team1_id, team2_id, round_id) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![history_id, tournament_id, team1_id, t2, round_id],
             )
             .map_err(|e| e.to_string())?;
         }
+
+        // Record court history (a BYE is not played on a court)
         if let Some(t2) = team2_id {
-            let history_id = Uuid::new_v4().to_string();
-            conn.execute(
-                "INSERT INTO court_history (id, tournament_id, team_id, court_number, round_id) VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![history_id, tournament_id, t2, court, round_id],
-            )
-            .map_err(|e| e.to_string())?;
+            for team_id in [team1_id, t2] {
+                let history_id = Uuid::new_v4().to_string();
+                conn.execute(
+                    "INSERT INTO court_history (id, tournament_id, team_id, court_number, round_id) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![history_id, tournament_id, team_id, court, round_id],
+                )
+                .map_err(|e| e.to_string())?;
+            }
         }
     }
 
+    // Record float history
+    for (team_id, direction) in &draw.floats {
+        let float_id = Uuid::new_v4().to_string();
+        conn.execute(
+            "INSERT INTO pairing_floats (id, tournament_id, team_id, round_id, direction) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![float_id, tournament_id, team_id, round_id, direction],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    // Poules, round-robins and Pool Play keep the teams they were drawn with:
+    // a withdrawn team forfeits
+    forfeit_withdrawn_games(conn, tournament_id)?;
+
+    record_draw(conn, tournament_id, "round", Some(&round_id), seed, inputs, draw)?;
+
     Ok(QualifyingRound {
         id: round_id,
         tournament_id: tournament_id.to_string(),
         round_number: new_round_number,
         is_complete: false,
+        draw_method: draw_method.map(String::from),
+        started_at: None,
+        ended_at: None,
         created_at: now,
     })
 }
 
+/// Everything a round draw depends on. Stored with the seed of the draw, so
+/// that the round can be drawn again exactly as it came out.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RoundDrawInputs {
+    pairing_method: String,
+    round_number: i32,
+    avoidance_policy: String,
+    avoidance_rounds: Option<i32>,
+    teams: Vec<Team>,
+    standings: Vec<TeamStanding>,
+    pairing_history: Vec<(String, String)>,
+    previous_floaters: Vec<String>,
+    bye_history: Vec<String>,
+    /// Poules drawn with the first round, for the later rounds of poules
+    pools: Vec<Vec<String>>,
+    courts: CourtPlan,
+    court_history: Vec<(String, i32)>,
+    /// "random" or "seeded"; absent from draws recorded before it could be
+    /// chosen, whose first Swiss round paired the teams in their stored order
+    #[serde(default)]
+    first_round_draw: Option<String>,
+}
+
+/// What a round draw produced
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RoundDraw {
+    /// Poules drawn with the first round of poules
+    pools: Vec<Vec<String>>,
+    games: Vec<CourtAssignment>,
+    floats: Vec<(String, &'static str)>,
+}
+
+/// Pairs a round and puts its games on courts. Every random choice comes from
+/// `rng`, so the same inputs and seed always give the same round.
+fn draw_round(inputs: &RoundDrawInputs, rng: &mut DrawRng) -> Result<RoundDraw, String> {
+    let round_number = inputs.round_number;
+    let avoidance = AvoidancePolicy::parse(&inputs.avoidance_policy)?.for_round(inputs.avoidance_rounds, round_number);
+    // Every team is on 0 wins in the first Swiss round: the order the teams
+    // are paired in is the draw itself
+    let first_round_teams;
+    let teams = match first_round_draw(inputs) {
+        Some(method) => {
+            first_round_teams = first_round_order(&inputs.teams, method, rng);
+            &first_round_teams
+        }
+        None => &inputs.teams,
+    };
+
+    let standings: HashMap<String, TeamStanding> = inputs
+        .standings
+        .iter()
+        .map(|s| (s.team_id.clone(), s.clone()))
+        .collect();
+    let pairing_history: HashSet<(String, String)> = inputs
+        .pairing_history
+        .iter()
+        .flat_map(|(t1, t2)| vec![(t1.clone(), t2.clone()), (t2.clone(), t1.clone())])
+        .collect();
+    let previous_floaters: HashSet<String> = inputs.previous_floaters.iter().cloned().collect();
+    let bye_history: HashSet<String> = inputs.bye_history.iter().cloned().collect();
+    let mut court_history: HashMap<String, Vec<i32>> = HashMap::new();
+    for (team_id, court) in &inputs.court_history {
+        court_history.entry(team_id.clone()).or_default().push(*court);
+    }
+
+    // Generate pairings based on method
+    let mut pools = Vec::new();
+    let mut pairings = match inputs.pairing_method.as_str() {
+        "swiss" => generate_swiss_pairings(teams, &standings, &pairing_history, &previous_floaters, &bye_history, avoidance, rng)?,
+        "swissHotel" => generate_swiss_hotel_pairings(teams, &standings, &pairing_history, &bye_history, avoidance, round_number, rng)?,
+        "roundRobin" => generate_round_robin_pairings(teams, round_number)?,
+        "poolPlay" => generate_pool_play_round(teams, &standings, &pairing_history, &bye_history, avoidance, round_number, rng)?,
+        "poules" => {
+            if round_number == 1 {
+                pools = draw_pools(teams, avoidance, rng)?;
+                generate_poules_pairings(&pools, &standings, &pairing_history, round_number)?
+            } else {
+                generate_poules_pairings(&inputs.pools, &standings, &pairing_history, round_number)?
+            }
+        }
+        method => return Err(format!("Unknown pairing method: {}", method)),
+    };
+
+    // Never hand out a second BYE while another team is still waiting for its first.
+    // Round-robin byes follow the Berger table and are already one per team per cycle,
+    // and poules never have byes: teams without a game wait for the next round.
+    if inputs.pairing_method != "roundRobin" && inputs.pairing_method != "poules" {
+        ensure_fair_byes(&mut pairings, &standings, &pairing_history, &bye_history)?;
+    }
+
+    // Floats are only meaningful for score-group pairing
+    let floats = if inputs.pairing_method == "swiss" {
+        detect_floats(&pairings, &standings)
+    } else {
+        Vec::new()
+    };
+
+    // Assign courts with rotation
+    let games = assign_courts(pairings, &inputs.courts, &court_history, &standings);
+
+    Ok(RoundDraw { pools, games, floats })
+}
+
+/// How the round is drawn when it is the first round of a Swiss system
+fn first_round_draw(inputs: &RoundDrawInputs) -> Option<&str> {
+    if inputs.pairing_method == "swiss" && inputs.round_number == 1 {
+        inputs.first_round_draw.as_deref()
+    } else {
+        None
+    }
+}
+
+/// Order of the teams for the first Swiss round, whose top half is paired
+/// against its bottom half. The teams are shuffled; a seeded draw then puts
+/// the protected seeds first, in seed order, so that they meet unseeded teams.
+fn first_round_order(teams: &[Team], method: &str, rng: &mut DrawRng) -> Vec<Team> {
+    let mut order = teams.to_vec();
+    order.shuffle(rng);
+    if method == "seeded" {
+        // A stable sort: the unseeded teams stay shuffled
+        order.sort_by_key(|team| team.seed.unwrap_or(i32::MAX));
+    }
+    order
+}
+
+/// Draws a round again from the inputs stored with its draw.
+pub fn replay_round_draw(inputs: &serde_json::Value, rng: &mut DrawRng) -> Result<serde_json::Value, String> {
+    let inputs: RoundDrawInputs = serde_json::from_value(inputs.clone()).map_err(|e| e.to_string())?;
+    let draw = draw_round(&inputs, rng)?;
+    serde_json::to_value(&draw).map_err(|e| e.to_string())
+}
+
+/// Cost of a rematch. Large enough that a repeat only survives when no
+/// repeat-free perfect matching exists.
+const REPEAT_PAIRING_COST: i64 = 1_000_000;
+/// Cost per clash level of two teams sharing a club or region (see `AvoidancePolicy::clash`)
+const CLASH_COST: i64 = 1_250;
+/// Cost per squared difference in wins between two opponents
+const WINS_GAP_COST: i64 = 1_000;
+/// Cost of a team floating again right after floating in the previous round
+const REPEAT_FLOAT_COST: i64 = 2_000;
+/// Cost of giving a second BYE to a team. Same weight as a rematch, so it only
+/// happens when every team has already had one.
+const REPEAT_BYE_COST: i64 = REPEAT_PAIRING_COST;
+/// Cost of a team playing again on a court it already used (grows with the square of uses)
+const REPEAT_COURT_COST: i64 = 1_000;
+/// Cost per court of staying close to a team's usual part of the field
+const COURT_SPREAD_COST: i64 = 1;
+
+/// Dutch-style Swiss pairing as a minimum-cost perfect matching over all teams.
+///
+/// Teams are grouped by wins. Inside a score group the top half is paired
+/// against the bottom half (1st vs first of the bottom half, and so on); any
+/// pairing across groups is a float, preferably taking the lowest team of the
+/// higher group and the highest team of the lower group, and never a team that
+/// floated in the previous round if it can be helped. Rematches, club or region
+/// clashes and gaps in wins are all costed, and the matching minimises the
+/// total, so a rematch only happens when no rematch-free pairing exists.
+/// With an odd number of teams a virtual BYE opponent is added; it is
+/// cheapest for the lowest-ranked team that has not had a BYE yet.
 fn generate_swiss_pairings(
     teams: &[Team],
     standings: &HashMap<String, TeamStanding>,
     pairing_history: &HashSet<(String, String)>,
-    region_avoidance: bool,
+    previous_floaters: &HashSet<String>,
+    bye_history: &HashSet<String>,
+    avoidance: AvoidancePolicy,
+    rng: &mut DrawRng,
 ) -> Result<Vec<(String, Option<String>)>, String> {
-    let mut rng = thread_rng();
-
     // Sort teams by standings
     let mut sorted_teams: Vec<&Team> = teams.iter().collect();
-    sorted_teams.sort_by(|a, b| {
-        let sa = standings.get(&a.id);
-        let sb = standings.get(&b.id);
-
-        match (sa, sb) {
-            (Some(sa), Some(sb)) => {
-                sb.wins
-                    .cmp(&sa.wins)
-                    .then(sb.differential.cmp(&sa.differential))
-                    .then(sb.points_for.cmp(&sa.points_for))
+    sorted_teams.sort_by(|a, b| compare_standings(&a.id, &b.id, standings));
+
+    let team_count = sorted_teams.len();
+    let wins = |team: &Team| standings.get(&team.id).map(|s| s.wins).unwrap_or(0) as i64;
+
+    // Position of each team inside its score group, and the size of that group
+    let mut group_position: Vec<usize> = Vec::with_capacity(team_count);
+    let mut group_size: Vec<usize> = Vec::with_capacity(team_count);
+    let mut group_start = 0;
+    while group_start < team_count {
+        let group_wins = wins(sorted_teams[group_start]);
+        let mut group_end = group_start;
+        while group_end < team_count && wins(sorted_teams[group_end]) == group_wins {
+            group_end += 1;
+        }
+        for position in 0..(group_end - group_start) {
+            group_position.push(position);
+            group_size.push(group_end - group_start);
+        }
+        group_start = group_end;
+    }
+
+    // With an odd number of teams the last node is the BYE
+    let needs_bye = team_count % 2 == 1;
+    let node_count = if needs_bye { team_count + 1 } else { team_count };
+    let min_wins = sorted_teams.iter().map(|t| wins(t)).min().unwrap_or(0);
+
+    let matched = min_cost_perfect_matching(node_count, |i, j| {
+        if j == team_count {
+            // BYE: cheapest for teams with the fewest wins, then the lowest ranked,
+            // and only for a team that already had one if nobody else is left
+            let team = sorted_teams[i];
+            let repeat_bye = if bye_history.contains(&team.id) { REPEAT_BYE_COST } else { 0 };
+            return repeat_bye + (wins(team) - min_wins) * WINS_GAP_COST + (team_count - 1 - i) as i64;
+        }
+
+        let (t1, t2) = (sorted_teams[i], sorted_teams[j]);
+        let mut cost = 0;
+        if pairing_history.contains(&(t1.id.clone(), t2.id.clone())) {
+            cost += REPEAT_PAIRING_COST;
+        }
+        cost += avoidance.clash(t1, t2) as i64 * CLASH_COST;
+
+        let gap = wins(t1) - wins(t2);
+        if gap == 0 {
+            // Same score group: top half plays bottom half, 1st vs (half + 1)th
+            let half = group_size[i] / 2;
+            let (p1, p2) = (group_position[i], group_position[j]);
+            cost += (p2 as i64 - p1 as i64 - half as i64).abs();
+            if (p1 < half) == (p2 < half) {
+                cost += group_size[i] as i64;
+            }
+        } else {
+            // Float: t1 drops down, t2 moves up
+            cost += gap * gap * WINS_GAP_COST;
+            cost += (group_size[i] - 1 - group_position[i]) as i64 + group_position[j] as i64;
+            if previous_floaters.contains(&t1.id) {
+                cost += REPEAT_FLOAT_COST;
+            }
+            if previous_floaters.contains(&t2.id) {
+                cost += REPEAT_FLOAT_COST;
             }
-            _ => std::cmp::Ordering::Equal,
         }
+        cost
     });
 
-    let mut pairings: Vec<(String, Option<String>)> = Vec::new();
-    let mut paired: HashSet<String> = HashSet::new();
+    let mut pairings: Vec<(String, Option<String>)> = matched
+        .into_iter()
+        .map(|(i, j)| {
+            if j == team_count {
+                (sorted_teams[i].id.clone(), None)
+            } else {
+                (sorted_teams[i].id.clone(), Some(sorted_teams[j].id.clone()))
+            }
+        })
+        .collect();
+
+    // Shuffle pairings to randomize court assignment
+    pairings.shuffle(rng);
 
-    // If odd number of teams, handle BYE
-    let needs_bye = sorted_teams.len() % 2 == 1;
+    Ok(pairings)
+}
 
-    // Helper to check if two teams are from the same region
-    let same_region = |t1: &Team, t2: &Team| -> bool {
-        if let (Some(r1), Some(r2)) = (&t1.region, &t2.region) {
-            !r1.is_empty() && !r2.is_empty() && r1 == r2
-        } else {
-            false
-        }
-    };
+/// Order two teams by standings, best first, using the rank the tournament's
+/// tiebreaker chain stored. Teams not ranked yet (rank 0, e.g. a late entry)
+/// come after the ranked ones; teams sharing a rank fall back to
+/// wins → differential → points for.
+fn compare_standings(
+    a: &str,
+    b: &str,
+    standings: &HashMap<String, TeamStanding>,
+) -> std::cmp::Ordering {
+    let rank = |s: &TeamStanding| if s.rank > 0 { s.rank } else { i32::MAX };
+    match (standings.get(a), standings.get(b)) {
+        (Some(sa), Some(sb)) => rank(sa)
+            .cmp(&rank(sb))
+            .then(sb.wins.cmp(&sa.wins))
+            .then(sb.differential.cmp(&sa.differential))
+            .then(sb.points_for.cmp(&sa.points_for)),
+        _ => std::cmp::Ordering::Equal,
+    }
+}
+
+/// Pick the BYE recipient for an odd group of teams: the lowest-ranked team that
+/// has not had a BYE yet, or the lowest-ranked team if everyone already had one.
+fn select_bye_team<'a>(
+    teams: &[&'a Team],
+    standings: &HashMap<String, TeamStanding>,
+    bye_history: &HashSet<String>,
+) -> Option<&'a Team> {
+    let mut ranked: Vec<&Team> = teams.to_vec();
+    ranked.sort_by(|a, b| compare_standings(&a.id, &b.id, standings));
+
+    ranked
+        .iter()
+        .rev()
+        .find(|t| !bye_history.contains(&t.id))
+        .or_else(|| ranked.last())
+        .copied()
+}
+
+/// Check that no team receives a second BYE while a team without one is playing.
+///
+/// An offending BYE is repaired by swapping its team with the lowest-ranked team
+/// that has not had a BYE, choosing one whose opponent does not cause a rematch.
+/// If no such swap exists the round is rejected.
+fn ensure_fair_byes(
+    pairings: &mut [(String, Option<String>)],
+    standings: &HashMap<String, TeamStanding>,
+    pairing_history: &HashSet<(String, String)>,
+    bye_history: &HashSet<String>,
+) -> Result<(), String> {
+    let bye_indices: Vec<usize> = pairings
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, t2))| t2.is_none())
+        .map(|(i, _)| i)
+        .collect();
 
-    // Try to pair teams from similar score groups
-    for i in 0..sorted_teams.len() {
-        let team = sorted_teams[i];
-        if paired.contains(&team.id) {
+    for bye_idx in bye_indices {
+        let bye_team = pairings[bye_idx].0.clone();
+        if !bye_history.contains(&bye_team) {
             continue;
         }
 
-        // Find best opponent with graduated fallback:
-        // Pass 1: Respect both pairing history AND region avoidance
-        // Pass 2: Respect pairing history only (relax region avoidance)
-        // Pass 3: Any unpaired opponent (relax all constraints)
-        let mut best_opponent: Option<&Team> = None;
-
-        // Pass 1: Full constraints (no repeat matchups, avoid same region)
-        if region_avoidance {
-            for j in (i + 1)..sorted_teams.len() {
-                let opponent = sorted_teams[j];
-                if paired.contains(&opponent.id) {
-                    continue;
+        // Teams playing this round that are still owed their first BYE, lowest ranked first
+        let mut candidates: Vec<(usize, String, String)> = Vec::new();
+        for (game_idx, (t1, t2)) in pairings.iter().enumerate() {
+            if let Some(t2) = t2 {
+                if !bye_history.contains(t1) {
+                    candidates.push((game_idx, t1.clone(), t2.clone()));
                 }
-                if pairing_history.contains(&(team.id.clone(), opponent.id.clone())) {
-                    continue;
+                if !bye_history.contains(t2) {
+                    candidates.push((game_idx, t2.clone(), t1.clone()));
                 }
-                if same_region(team, opponent) {
-                    continue;
-                }
-                best_opponent = Some(opponent);
-                break;
             }
         }
 
-        // Pass 2: Relax region avoidance, but still avoid repeat matchups
-        if best_opponent.is_none() {
-            for j in (i + 1)..sorted_teams.len() {
-                let opponent = sorted_teams[j];
-                if paired.contains(&opponent.id) {
-                    continue;
-                }
-                if pairing_history.contains(&(team.id.clone(), opponent.id.clone())) {
-                    continue;
-                }
-                best_opponent = Some(opponent);
-                break;
-            }
+        if candidates.is_empty() {
+            // Every team in this round has already had a BYE
+            continue;
         }
 
-        // Pass 3: Relax all constraints - just find any unpaired opponent
-        if best_opponent.is_none() {
-            for j in (i + 1)..sorted_teams.len() {
-                let opponent = sorted_teams[j];
-                if !paired.contains(&opponent.id) {
-                    best_opponent = Some(opponent);
-                    break;
-                }
-            }
-        }
+        candidates.sort_by(|a, b| compare_standings(&b.1, &a.1, standings));
 
-        if let Some(opponent) = best_opponent {
-            pairings.push((team.id.clone(), Some(opponent.id.clone())));
-            paired.insert(team.id.clone());
-            paired.insert(opponent.id.clone());
+        let swap = candidates
+            .iter()
+            .find(|(_, _, opponent)| !pairing_history.contains(&(bye_team.clone(), opponent.clone())));
+
+        match swap {
+            Some((game_idx, candidate, opponent)) => {
+                pairings[*game_idx] = (bye_team.clone(), Some(opponent.clone()));
+                pairings[bye_idx] = (candidate.clone(), None);
+            }
+            None => {
+                return Err("Cannot generate round: the BYE would go to a team that already had one, and no swap avoids a rematch.".to_string());
+            }
         }
     }
 
-    // Handle BYE
-    if needs_bye {
-        for team in &sorted_teams {
-            if !paired.contains(&team.id) {
-                pairings.push((team.id.clone(), None));
-                break;
+    Ok(())
+}
+
+/// Find the teams that floated in a set of Swiss pairings.
+/// The team with more wins floated down, its opponent floated up.
+fn detect_floats(
+    pairings: &[(String, Option<String>)],
+    standings: &HashMap<String, TeamStanding>,
+) -> Vec<(String, &'static str)> {
+    let wins = |id: &String| standings.get(id).map(|s| s.wins).unwrap_or(0);
+    let mut floats = Vec::new();
+
+    for (t1, t2) in pairings {
+        if let Some(t2) = t2 {
+            let (w1, w2) = (wins(t1), wins(t2));
+            if w1 > w2 {
+                floats.push((t1.clone(), "down"));
+                floats.push((t2.clone(), "up"));
+            } else if w2 > w1 {
+                floats.push((t2.clone(), "down"));
+                floats.push((t1.clone(), "up"));
             }
         }
     }
 
-    // Shuffle pairings to randomize court assignment
-    pairings.shuffle(&mut rng);
-
-    Ok(pairings)
+    floats
 }
 
 fn generate_round_robin_pairings(
@@ -579,102 +1108,37 @@ fn generate_round_robin_pairings(
     Ok(pairings)
 }
 
-/// Swiss Hotel pairing: random pairing with graduated constraints (avoid repeats, region avoidance)
+/// Swiss Hotel pairing: random pairing that avoids repeats, then club/region clashes
 /// All rounds are pre-generated upfront.
 fn generate_swiss_hotel_pairings(
     teams: &[Team],
+    standings: &HashMap<String, TeamStanding>,
     pairing_history: &HashSet<(String, String)>,
-    region_avoidance: bool,
+    bye_history: &HashSet<String>,
+    avoidance: AvoidancePolicy,
     _round_number: i32,
+    rng: &mut DrawRng,
 ) -> Result<Vec<(String, Option<String>)>, String> {
-    let mut rng = thread_rng();
-
     // Shuffle teams randomly
     let mut shuffled_teams: Vec<&Team> = teams.iter().collect();
-    shuffled_teams.shuffle(&mut rng);
-
-    let mut pairings: Vec<(String, Option<String>)> = Vec::new();
-    let mut paired: HashSet<String> = HashSet::new();
-
-    let needs_bye = shuffled_teams.len() % 2 == 1;
+    shuffled_teams.shuffle(rng);
 
-    // Helper to check if two teams are from the same region
-    let same_region = |t1: &Team, t2: &Team| -> bool {
-        if let (Some(r1), Some(r2)) = (&t1.region, &t2.region) {
-            !r1.is_empty() && !r2.is_empty() && r1 == r2
-        } else {
-            false
-        }
+    // Set the BYE aside first so it goes to a team that has not had one
+    let bye_team = if shuffled_teams.len() % 2 == 1 {
+        select_bye_team(&shuffled_teams, standings, bye_history)
+    } else {
+        None
     };
-
-    // Try to pair teams with graduated fallback
-    for i in 0..shuffled_teams.len() {
-        let team = shuffled_teams[i];
-        if paired.contains(&team.id) {
-            continue;
-        }
-
-        let mut best_opponent: Option<&Team> = None;
-
-        // Pass 1: Full constraints (no repeat matchups, avoid same region)
-        if region_avoidance {
-            for j in (i + 1)..shuffled_teams.len() {
-                let opponent = shuffled_teams[j];
-                if paired.contains(&opponent.id) {
-                    continue;
-                }
-                if pairing_history.contains(&(team.id.clone(), opponent.id.clone())) {
-                    continue;
-                }
-                if same_region(team, opponent) {
-                    continue;
-                }
-                best_opponent = Some(opponent);
-                break;
-            }
-        }
-
-        // Pass 2: Relax region avoidance, but still avoid repeat matchups
-        if best_opponent.is_none() {
-            for j in (i + 1)..shuffled_teams.len() {
-                let opponent = shuffled_teams[j];
-                if paired.contains(&opponent.id) {
-                    continue;
-                }
-                if pairing_history.contains(&(team.id.clone(), opponent.id.clone())) {
-                    continue;
-                }
-                best_opponent = Some(opponent);
-                break;
-            }
-        }
-
-        // Pass 3: Relax all constraints - just find any unpaired opponent
-        if best_opponent.is_none() {
-            for j in (i + 1)..shuffled_teams.len() {
-                let opponent = shuffled_teams[j];
-                if !paired.contains(&opponent.id) {
-                    best_opponent = Some(opponent);
-                    break;
-                }
-            }
-        }
-
-        if let Some(opponent) = best_opponent {
-            pairings.push((team.id.clone(), Some(opponent.id.clone())));
-            paired.insert(team.id.clone());
-            paired.insert(opponent.id.clone());
-        }
+    if let Some(bye) = bye_team {
+        shuffled_teams.retain(|t| t.id != bye.id);
     }
 
+    // Pair the rest, avoiding rematches first and club/region clashes second
+    let mut pairings = pair_avoiding(&shuffled_teams, pairing_history, avoidance);
+
     // Handle BYE
-    if needs_bye {
-        for team in &shuffled_teams {
-            if !paired.contains(&team.id) {
-                pairings.push((team.id.clone(), None));
-                break;
-            }
-        }
+    if let Some(bye) = bye_team {
+        pairings.push((bye.id.clone(), None));
     }
 
     Ok(pairings)
@@ -688,15 +1152,15 @@ fn generate_pool_play_round(
     teams: &[Team],
     standings: &HashMap<String, TeamStanding>,
     pairing_history: &HashSet<(String, String)>,
-    region_avoidance: bool,
+    bye_history: &HashSet<String>,
+    avoidance: AvoidancePolicy,
     round_number: i32,
+    rng: &mut DrawRng,
 ) -> Result<Vec<(String, Option<String>)>, String> {
-    let mut rng = thread_rng();
-
     match round_number {
         1 => {
             // Round 1: Random pairings (same as Swiss Hotel round 1)
-            generate_swiss_hotel_pairings(teams, pairing_history, region_avoidance, round_number)
+            generate_swiss_hotel_pairings(teams, standings, pairing_history, bye_history, avoidance, round_number, rng)
         }
         2 => {
             // Round 2: Winners play winners, losers play losers
@@ -716,38 +1180,30 @@ fn generate_pool_play_round(
                 }
             }
 
-            winners.shuffle(&mut rng);
-            losers.shuffle(&mut rng);
+            winners.shuffle(rng);
+            losers.shuffle(rng);
 
             let mut pairings: Vec<(String, Option<String>)> = Vec::new();
 
-            // Pair winners
-            pair_teams_with_constraints(&mut pairings, &winners, pairing_history, region_avoidance);
-
-            // Pair losers
-            pair_teams_with_constraints(&mut pairings, &losers, pairing_history, region_avoidance);
-
-            // Handle any odd team out (give them a bye)
-            let paired: HashSet<String> = pairings
-                .iter()
-                .flat_map(|(t1, t2)| {
-                    let mut ids = vec![t1.clone()];
-                    if let Some(t2_id) = t2 {
-                        ids.push(t2_id.clone());
+            // An odd group sets its BYE aside before pairing
+            for group in [&mut winners, &mut losers] {
+                if group.len() % 2 == 1 {
+                    if let Some(bye) = select_bye_team(group, standings, bye_history) {
+                        group.retain(|t| t.id != bye.id);
+                        pairings.push((bye.id.clone(), None));
                     }
-                    ids
-                })
-                .collect();
-
-            for team in teams {
-                if !paired.contains(&team.id) {
-                    pairings.push((team.id.clone(), None));
                 }
             }
 
-            Ok(pairings)
-        }
-        3 => {
+            // Pair winners
+            pairings.extend(pair_avoiding(&winners, pairing_history, avoidance));
+
+            // Pair losers
+            pairings.extend(pair_avoiding(&losers, pairing_history, avoidance));
+
+            Ok(pairings)
+        }
+        3 => {
             // Round 3: Teams with 2 losses are eliminated
             // Teams with 2 wins sit out (already qualified)
             // Teams with 1-1 play each other
@@ -762,138 +1218,155 @@ fn generate_pool_play_round(
                 }
             }
 
-            one_win_teams.shuffle(&mut rng);
+            one_win_teams.shuffle(rng);
 
             let mut pairings: Vec<(String, Option<String>)> = Vec::new();
 
-            // Pair 1-1 teams
-            pair_teams_with_constraints(&mut pairings, &one_win_teams, pairing_history, region_avoidance);
-
             // Handle odd team out
-            let paired: HashSet<String> = pairings
-                .iter()
-                .flat_map(|(t1, t2)| {
-                    let mut ids = vec![t1.clone()];
-                    if let Some(t2_id) = t2 {
-                        ids.push(t2_id.clone());
-                    }
-                    ids
-                })
-                .collect();
-
-            for team in &one_win_teams {
-                if !paired.contains(&team.id) {
-                    pairings.push((team.id.clone(), None));
+            if one_win_teams.len() % 2 == 1 {
+                if let Some(bye) = select_bye_team(&one_win_teams, standings, bye_history) {
+                    one_win_teams.retain(|t| t.id != bye.id);
+                    pairings.push((bye.id.clone(), None));
                 }
             }
 
+            // Pair 1-1 teams
+            pairings.extend(pair_avoiding(&one_win_teams, pairing_history, avoidance));
+
             Ok(pairings)
         }
         _ => Err("Pool Play format only has 3 rounds.".to_string()),
     }
 }
 
-/// Helper function to pair teams with graduated constraint relaxation
-fn pair_teams_with_constraints(
-    pairings: &mut Vec<(String, Option<String>)>,
-    teams: &[&Team],
-    pairing_history: &HashSet<(String, String)>,
-    region_avoidance: bool,
-) {
-    let mut paired: HashSet<String> = HashSet::new();
+/// A game placed on a court, in the wave (slot) it is played in.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CourtAssignment {
+    slot_number: i32,
+    court_number: i32,
+    team1_id: String,
+    team2_id: Option<String>,
+}
 
-    let same_region = |t1: &Team, t2: &Team| -> bool {
-        if let (Some(r1), Some(r2)) = (&t1.region, &t2.region) {
-            !r1.is_empty() && !r2.is_empty() && r1 == r2
-        } else {
-            false
+/// Assigns a court to every game, rotating teams across the active courts.
+///
+/// The top games (by the better-ranked team) are put on the reserved honour
+/// courts. The others are placed with a minimum-cost assignment of games ×
+/// courts: each past use of a court by either team is penalised, and teams are
+/// pushed away from the part of the field they have mostly played on so they
+/// see both good and bad terrains. When there are more games than courts, the
+/// extra games are played in later waves on the same courts. BYE games take
+/// the courts left over in the first wave, numbered after the real courts if
+/// none are free.
+fn assign_courts(
+    pairings: Vec<(String, Option<String>)>,
+    courts: &CourtPlan,
+    court_history: &HashMap<String, Vec<i32>>,
+    standings: &HashMap<String, TeamStanding>,
+) -> Vec<CourtAssignment> {
+    let all_courts = courts.all();
+    let field_size = all_courts.last().copied().unwrap_or(0);
+    let (mut matches, byes): (Vec<_>, Vec<_>) = pairings.into_iter().partition(|(_, t2)| t2.is_some());
+
+    let mut games: Vec<CourtAssignment> = Vec::new();
+
+    // Marquee games go on the honour courts when there are regular courts for the rest
+    if !courts.regular.is_empty() && !courts.reserved.is_empty() {
+        let best_team = |(t1, t2): &(String, Option<String>)| -> String {
+            match t2 {
+                Some(t2) if compare_standings(t2, t1, standings).is_lt() => t2.clone(),
+                _ => t1.clone(),
+            }
+        };
+        matches.sort_by(|a, b| compare_standings(&best_team(a), &best_team(b), standings));
+
+        let marquee_count = courts.reserved.len().min(matches.len());
+        for ((t1, t2), &court) in matches.drain(..marquee_count).zip(&courts.reserved) {
+            games.push(CourtAssignment {
+                slot_number: 1,
+                court_number: court,
+                team1_id: t1,
+                team2_id: t2,
+            });
         }
-    };
+    }
 
-    for i in 0..teams.len() {
-        let team = teams[i];
-        if paired.contains(&team.id) {
-            continue;
-        }
+    let rotation = courts.rotation();
 
-        let mut best_opponent: Option<&Team> = None;
+    // Each wave fills the courts once and is solved separately
+    for (wave, batch) in matches.chunks(rotation.len()).enumerate() {
+        let costs: Vec<Vec<i64>> = batch
+            .iter()
+            .map(|(t1, t2)| {
+                rotation
+                    .iter()
+                    .map(|&court| {
+                        let mut cost = court_cost(court_history.get(t1), court, field_size);
+                        if let Some(t2) = t2 {
+                            cost += court_cost(court_history.get(t2), court, field_size);
+                        }
+                        cost
+                    })
+                    .collect()
+            })
+            .collect();
 
-        // Pass 1: Full constraints
-        if region_avoidance {
-            for j in (i + 1)..teams.len() {
-                let opponent = teams[j];
-                if paired.contains(&opponent.id) {
-                    continue;
-                }
-                if pairing_history.contains(&(team.id.clone(), opponent.id.clone())) {
-                    continue;
-                }
-                if same_region(team, opponent) {
-                    continue;
-                }
-                best_opponent = Some(opponent);
-                break;
-            }
-        }
-
-        // Pass 2: Relax region avoidance
-        if best_opponent.is_none() {
-            for j in (i + 1)..teams.len() {
-                let opponent = teams[j];
-                if paired.contains(&opponent.id) {
-                    continue;
-                }
-                if pairing_history.contains(&(team.id.clone(), opponent.id.clone())) {
-                    continue;
-                }
-                best_opponent = Some(opponent);
-                break;
-            }
+        let assignment = min_cost_assignment(&costs);
+        for ((t1, t2), court_index) in batch.iter().zip(assignment) {
+            games.push(CourtAssignment {
+                slot_number: wave as i32 + 1,
+                court_number: rotation[court_index],
+                team1_id: t1.clone(),
+                team2_id: t2.clone(),
+            });
         }
+    }
 
-        // Pass 3: Any unpaired opponent
-        if best_opponent.is_none() {
-            for j in (i + 1)..teams.len() {
-                let opponent = teams[j];
-                if !paired.contains(&opponent.id) {
-                    best_opponent = Some(opponent);
-                    break;
-                }
-            }
-        }
+    games.sort_by_key(|g| (g.slot_number, g.court_number));
 
-        if let Some(opponent) = best_opponent {
-            pairings.push((team.id.clone(), Some(opponent.id.clone())));
-            paired.insert(team.id.clone());
-            paired.insert(opponent.id.clone());
-        }
+    let used: HashSet<i32> = games
+        .iter()
+        .filter(|g| g.slot_number == 1)
+        .map(|g| g.court_number)
+        .collect();
+    let mut free_courts = all_courts.iter().copied().filter(|c| !used.contains(c));
+    let mut next_extra = field_size + 1;
+    for (t1, _) in byes {
+        let court = free_courts.next().unwrap_or_else(|| {
+            next_extra += 1;
+            next_extra - 1
+        });
+        games.push(CourtAssignment {
+            slot_number: 1,
+            court_number: court,
+            team1_id: t1,
+            team2_id: None,
+        });
     }
+
+    games
 }
 
-fn assign_courts(
-    pairings: Vec<(String, Option<String>)>,
-    number_of_courts: i32,
-    court_history: &HashMap<String, Vec<i32>>,
-) -> Result<Vec<(Option<String>, Option<String>)>, String> {
-    let mut games: Vec<(Option<String>, Option<String>)> = Vec::new();
-
-    for (i, (t1, t2)) in pairings.iter().enumerate() {
-        let court = ((i as i32) % number_of_courts) + 1;
-
-        // Try to avoid courts teams have used recently
-        // This is a simplified version - could be optimized
-        let t1_courts = court_history.get(t1).cloned().unwrap_or_default();
-        let t2_courts = t2
-            .as_ref()
-            .and_then(|id| court_history.get(id))
-            .cloned()
-            .unwrap_or_default();
-
-        // For now, just use sequential assignment with shuffle from Swiss
-        games.push((Some(t1.clone()), t2.clone()));
+/// Cost of putting a team on a court given the courts it already played on.
+fn court_cost(history: Option<&Vec<i32>>, court: i32, field_size: i32) -> i64 {
+    let history = match history {
+        Some(h) if !h.is_empty() => h,
+        _ => return 0,
+    };
+
+    let uses = history.iter().filter(|&&c| c == court).count() as i64;
+    let mut cost = uses * uses * REPEAT_COURT_COST;
+    if history.last() == Some(&court) {
+        cost += REPEAT_COURT_COST;
     }
 
-    Ok(games)
+    // Prefer courts far from the team's average position on the field
+    let average = history.iter().map(|&c| c as f64).sum::<f64>() / history.len() as f64;
+    let distance = (court as f64 - average).abs();
+    cost += ((field_size as f64 - distance) * COURT_SPREAD_COST as f64).round() as i64;
+
+    cost
 }
 
 #[tauri::command]
@@ -902,12 +1375,84 @@ pub fn update_game_score(
     game_id: String,
     team1_score: i32,
     team2_score: i32,
-) -> Result<(), String> {
+) -> Result<(), ScoreError> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
+    // Saving the score its ends gave is a no-op, changing it is not allowed
+    if has_ends(&conn, &Scored::Game(game_id.clone()))? {
+        let saved: (Option<i32>, Option<i32>) = conn
+            .query_row(
+                "SELECT team1_score, team2_score FROM qualifying_games WHERE id = ?1",
+                params![game_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(|e| e.to_string())?;
+        if saved == (Some(team1_score), Some(team2_score)) {
+            return Ok(());
+        }
+        return Err("This game's score comes from its ends: record or undo an end instead.".to_string().into());
+    }
+
+    save_game_score(&conn, &game_id, team1_score, team2_score)
+}
+
+/// Saves the score of a game in a round still in play. The first score
+/// entered marks the end of the game for the court timings.
+pub fn save_game_score(
+    conn: &rusqlite::Connection,
+    game_id: &str,
+    team1_score: i32,
+    team2_score: i32,
+) -> Result<(), ScoreError> {
+    // A completed round is already in the standings: its scores go through corrections
+    let (tournament_id, is_complete): (String, bool) = conn
+        .query_row(
+            r#"
+            SELECT r.tournament_id, r.is_complete
+            FROM qualifying_games g
+            JOIN qualifying_rounds r ON g.round_id = r.id
+            WHERE g.id = ?1
+            "#,
+            params![game_id],
+            |row| Ok((row.get(0)?, row.get::<_, i32>(1)? != 0)),
+        )
+        .map_err(|e| e.to_string())?;
+
+    if is_complete {
+        return Err("This round is complete: correct the score with a reason instead.".to_string().into());
+    }
+
+    validate_score(&get_game_rules(conn, &tournament_id)?, team1_score, team2_score)?;
+
+    conn.execute(
+        "UPDATE qualifying_games SET team1_score = ?2, team2_score = ?3, ended_at = COALESCE(ended_at, ?4) WHERE id = ?1",
+        params![game_id, team1_score, team2_score, Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Scores the unplayed games of withdrawn teams in rounds that are not complete
+/// as forfeits: the team present wins by the tournament's forfeit score, and a
+/// game between two withdrawn teams is lost by both, with no winner. A forfeit
+/// already recorded is scored again, as its winner may have withdrawn since.
+pub fn forfeit_withdrawn_games(conn: &rusqlite::Connection, tournament_id: &str) -> Result<(), String> {
+    let forfeit = get_forfeit_scoring(conn, tournament_id)?;
+
     conn.execute(
-        "UPDATE qualifying_games SET team1_score = ?2, team2_score = ?3 WHERE id = ?1",
-        params![game_id, team1_score, team2_score],
+        r#"
+        UPDATE qualifying_games SET
+            team1_score = CASE WHEN team1_id IN (SELECT id FROM teams WHERE is_active = 1) THEN ?2 ELSE ?3 END,
+            team2_score = CASE WHEN team2_id IN (SELECT id FROM teams WHERE is_active = 1) THEN ?2 ELSE ?3 END,
+            is_forfeit = 1
+        WHERE is_bye = 0
+          AND (team1_score IS NULL OR team2_score IS NULL OR is_forfeit = 1)
+          AND round_id IN (SELECT id FROM qualifying_rounds WHERE tournament_id = ?1 AND is_complete = 0)
+          AND (team1_id IN (SELECT id FROM teams WHERE is_active = 0)
+               OR team2_id IN (SELECT id FROM teams WHERE is_active = 0))
+        "#,
+        params![tournament_id, forfeit.winner_score, forfeit.loser_score],
     )
     .map_err(|e| e.to_string())?;
 
@@ -918,33 +1463,82 @@ pub fn update_game_score(
 pub fn complete_round(db: State<Database>, round_id: String) -> Result<(), String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
-    // Get tournament ID and pairing method
-    let (tournament_id, pairing_method): (String, String) = conn
+    // Get tournament ID
+    let tournament_id: String = conn
         .query_row(
-            r#"
-            SELECT qr.tournament_id, t.pairing_method
-            FROM qualifying_rounds qr
-            JOIN tournaments t ON qr.tournament_id = t.id
-            WHERE qr.id = ?1
-            "#,
+            "SELECT tournament_id FROM qualifying_rounds WHERE id = ?1",
             params![round_id],
-            |row| Ok((row.get(0)?, row.get(1)?)),
+            |row| row.get(0),
         )
         .map_err(|e| e.to_string())?;
 
-    // Get all games for this round
+    // Mark round as complete, then count it in the standings. Completing a
+    // round again only rebuilds the same standings.
+    conn.execute(
+        "UPDATE qualifying_rounds SET is_complete = 1, ended_at = COALESCE(ended_at, ?2) WHERE id = ?1",
+        params![round_id, Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    rebuild_standings(&conn, &tournament_id)
+}
+
+/// Rebuilds the standings from the games played, for instance after a score
+/// of a completed round was corrected.
+#[tauri::command]
+pub fn recalculate_standings(db: State<Database>, tournament_id: String) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    rebuild_standings(&conn, &tournament_id)
+}
+
+/// Rebuilds a tournament's standings from the results of its completed rounds
+/// and ranks the teams again. Nothing is ever added onto the previous totals,
+/// so the standings always match the games however often they are rebuilt.
+pub fn rebuild_standings(conn: &rusqlite::Connection, tournament_id: &str) -> Result<(), String> {
+    let pairing_method: String = conn
+        .query_row(
+            "SELECT pairing_method FROM tournaments WHERE id = ?1",
+            params![tournament_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    // Mêlée results go to the players
+    if pairing_method == "melee" {
+        return rebuild_player_standings(conn, tournament_id);
+    }
+
+    let bye_scoring = get_bye_scoring(conn, tournament_id)?;
+
+    conn.execute(
+        r#"
+        UPDATE team_standings SET
+            wins = 0,
+            losses = 0,
+            points_for = 0,
+            points_against = 0,
+            differential = 0,
+            is_eliminated = 0
+        WHERE tournament_id = ?1
+        "#,
+        params![tournament_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    // Get all games of the completed rounds
     let mut stmt = conn
         .prepare(
             r#"
-            SELECT team1_id, team2_id, team1_score, team2_score, is_bye
-            FROM qualifying_games
-            WHERE round_id = ?1
+            SELECT g.team1_id, g.team2_id, g.team1_score, g.team2_score, g.is_bye
+            FROM qualifying_games g
+            JOIN qualifying_rounds r ON g.round_id = r.id
+            WHERE r.tournament_id = ?1 AND r.is_complete = 1
             "#,
         )
         .map_err(|e| e.to_string())?;
 
     let games: Vec<(Option<String>, Option<String>, Option<i32>, Option<i32>, bool)> = stmt
-        .query_map(params![round_id], |row| {
+        .query_map(params![tournament_id], |row| {
             Ok((
                 row.get(0)?,
                 row.get(1)?,
@@ -954,26 +1548,14 @@ pub fn complete_round(db: State<Database>, round_id: String) -> Result<(), Strin
             ))
         })
         .map_err(|e| e.to_string())?
-        .filter_map(|r| r.ok())
-        .collect();
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
 
     // Update standings for each game
     for (team1_id, team2_id, team1_score, team2_score, is_bye) in games {
         if is_bye {
-            // BYE: team gets a win with 13-7 score (FPUSA rules)
             if let Some(t1) = team1_id {
-                conn.execute(
-                    r#"
-                    UPDATE team_standings SET
-                        wins = wins + 1,
-                        points_for = points_for + 13,
-                        points_against = points_against + 7,
-                        differential = differential + 6
-                    WHERE tournament_id = ?1 AND team_id = ?2
-                    "#,
-                    params![tournament_id, t1],
-                )
-                .map_err(|e| e.to_string())?;
+                credit_bye(conn, tournament_id, &t1, &bye_scoring)?;
             }
         } else if let (Some(t1), Some(t2), Some(s1), Some(s2)) =
             (team1_id, team2_id, team1_score, team2_score)
@@ -1012,33 +1594,20 @@ pub fn complete_round(db: State<Database>, round_id: String) -> Result<(), Strin
         }
     }
 
-    // Get round number to check for Pool Play elimination
-    let round_number: i32 = conn
+    // Rank with the tournament's tiebreaker chain
+    rank_teams(conn, tournament_id)?;
+
+    // Pool Play and Poules decide who is out once their third and last round is complete
+    let final_round_complete: bool = conn
         .query_row(
-            "SELECT round_number FROM qualifying_rounds WHERE id = ?1",
-            params![round_id],
+            "SELECT COUNT(*) > 0 FROM qualifying_rounds WHERE tournament_id = ?1 AND round_number = 3 AND is_complete = 1",
+            params![tournament_id],
             |row| row.get(0),
         )
         .map_err(|e| e.to_string())?;
 
-    // Calculate rankings based on pairing method
-    match pairing_method.as_str() {
-        "swiss" => {
-            // Swiss uses Buchholz tiebreaker
-            calculate_buchholz_and_ranks(&conn, &tournament_id)?;
-        }
-        "swissHotel" | "roundRobin" | "poolPlay" => {
-            // These use point quotient tiebreaker
-            calculate_point_quotient_ranks(&conn, &tournament_id)?;
-        }
-        _ => {
-            // Default to Buchholz
-            calculate_buchholz_and_ranks(&conn, &tournament_id)?;
-        }
-    }
-
     // For Pool Play, mark teams with 2 losses as eliminated after round 3
-    if pairing_method == "poolPlay" && round_number == 3 {
+    if pairing_method == "poolPlay" && final_round_complete {
         conn.execute(
             r#"
             UPDATE team_standings
@@ -1050,10 +1619,38 @@ pub fn complete_round(db: State<Database>, round_id: String) -> Result<(), Strin
         .map_err(|e| e.to_string())?;
     }
 
-    // Mark round as complete
+    // For Poules, the top two of each poule qualify after round 3
+    if pairing_method == "poules" && final_round_complete {
+        rank_pools(conn, tournament_id)?;
+    }
+
+    Ok(())
+}
+
+/// Credits a BYE to a team: a win scored by the tournament's BYE policy.
+/// When the BYE doesn't count in tiebreaks only the win is credited.
+fn credit_bye(
+    conn: &rusqlite::Connection,
+    tournament_id: &str,
+    team_id: &str,
+    bye_scoring: &ByeScoring,
+) -> Result<(), String> {
+    let (points_for, points_against) = if bye_scoring.counts_in_tiebreaks {
+        (bye_scoring.winner_score, bye_scoring.loser_score)
+    } else {
+        (0, 0)
+    };
+
     conn.execute(
-        "UPDATE qualifying_rounds SET is_complete = 1 WHERE id = ?1",
-        params![round_id],
+        r#"
+        UPDATE team_standings SET
+            wins = wins + 1,
+            points_for = points_for + ?3,
+            points_against = points_against + ?4,
+            differential = differential + ?5
+        WHERE tournament_id = ?1 AND team_id = ?2
+        "#,
+        params![tournament_id, team_id, points_for, points_against, points_for - points_against],
     )
     .map_err(|e| e.to_string())?;
 
@@ -1071,9 +1668,13 @@ pub fn delete_all_qualifying_rounds(
     let scored_games: i32 = conn
         .query_row(
             r#"
-            SELECT COUNT(*) FROM qualifying_games g
-            JOIN qualifying_rounds r ON g.round_id = r.id
-            WHERE r.tournament_id = ?1 AND (g.team1_score IS NOT NULL OR g.team2_score IS NOT NULL)
+            SELECT
+                (SELECT COUNT(*) FROM qualifying_games g
+                 JOIN qualifying_rounds r ON g.round_id = r.id
+                 WHERE r.tournament_id = ?1 AND (g.team1_score IS NOT NULL OR g.team2_score IS NOT NULL))
+              + (SELECT COUNT(*) FROM melee_games g
+                 JOIN qualifying_rounds r ON g.round_id = r.id
+                 WHERE r.tournament_id = ?1 AND (g.team1_score IS NOT NULL OR g.team2_score IS NOT NULL))
             "#,
             params![tournament_id],
             |row| row.get(0),
@@ -1084,22 +1685,46 @@ pub fn delete_all_qualifying_rounds(
         return Err("Cannot delete qualifying rounds after scores have been entered.".to_string());
     }
 
+    // Every round goes, or none does
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+
     // Delete court history
-    conn.execute(
+    tx.execute(
         "DELETE FROM court_history WHERE tournament_id = ?1",
         params![tournament_id],
     )
     .map_err(|e| e.to_string())?;
 
     // Delete pairing history
-    conn.execute(
+    tx.execute(
         "DELETE FROM pairing_history WHERE tournament_id = ?1",
         params![tournament_id],
     )
     .map_err(|e| e.to_string())?;
 
+    // Delete float history
+    tx.execute(
+        "DELETE FROM pairing_floats WHERE tournament_id = ?1",
+        params![tournament_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    // Delete BYE history
+    tx.execute(
+        "DELETE FROM bye_history WHERE tournament_id = ?1",
+        params![tournament_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    // Delete the poule draw
+    tx.execute(
+        "DELETE FROM pool_assignments WHERE tournament_id = ?1",
+        params![tournament_id],
+    )
+    .map_err(|e| e.to_string())?;
+
     // Delete games (via cascade or explicit)
-    conn.execute(
+    tx.execute(
         r#"
         DELETE FROM qualifying_games WHERE round_id IN (
             SELECT id FROM qualifying_rounds WHERE tournament_id = ?1
@@ -1110,219 +1735,169 @@ pub fn delete_all_qualifying_rounds(
     .map_err(|e| e.to_string())?;
 
     // Delete rounds
-    conn.execute(
+    tx.execute(
         "DELETE FROM qualifying_rounds WHERE tournament_id = ?1",
         params![tournament_id],
     )
     .map_err(|e| e.to_string())?;
 
+    // Delete the draws the rounds (and the poules) came from
+    tx.execute(
+        "DELETE FROM draws WHERE tournament_id = ?1 AND kind = 'round'",
+        params![tournament_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    tx.commit().map_err(|e| e.to_string())?;
+
     Ok(())
 }
 
-fn calculate_buchholz_and_ranks(conn: &rusqlite::Connection, tournament_id: &str) -> Result<(), String> {
-    // Get all standings
-    let mut stmt = conn
-        .prepare(
+/// Deletes the latest round, as long as none of its games has been scored, so
+/// that it can be drawn again. Earlier rounds, and the standings built from
+/// them, are left as they are.
+#[tauri::command]
+pub fn delete_last_qualifying_round(
+    db: State<Database>,
+    tournament_id: String,
+) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let (round_id, round_number, is_complete): (String, i32, bool) = conn
+        .query_row(
             r#"
-            SELECT team_id, wins, differential, points_for
-            FROM team_standings
+            SELECT id, round_number, is_complete
+            FROM qualifying_rounds
             WHERE tournament_id = ?1
+            ORDER BY round_number DESC
+            LIMIT 1
             "#,
+            params![tournament_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get::<_, i32>(2)? != 0)),
         )
-        .map_err(|e| e.to_string())?;
-
-    let standings: Vec<(String, i32, i32, i32)> = stmt
-        .query_map(params![tournament_id], |row| {
-            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
-        })
-        .map_err(|e| e.to_string())?
-        .filter_map(|r| r.ok())
-        .collect();
-
-    // Get opponents for each team (for Buchholz calculations)
-    let mut team_opponents: HashMap<String, Vec<String>> = HashMap::new();
-
-    for (team_id, _, _, _) in &standings {
-        let mut stmt = conn
-            .prepare(
-                r#"
-                SELECT CASE
-                    WHEN ph.team1_id = ?2 THEN ph.team2_id
-                    ELSE ph.team1_id
-                END as opponent_id
-                FROM pairing_history ph
-                WHERE ph.tournament_id = ?1
-                AND (ph.team1_id = ?2 OR ph.team2_id = ?2)
-                "#,
-            )
-            .map_err(|e| e.to_string())?;
+        .map_err(|_| "There is no qualifying round to delete.".to_string())?;
 
-        let opponents: Vec<String> = stmt
-            .query_map(params![tournament_id, team_id], |row| row.get(0))
-            .map_err(|e| e.to_string())?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        team_opponents.insert(team_id.clone(), opponents);
+    if is_complete {
+        return Err(format!("Round {} is complete and cannot be deleted.", round_number));
     }
 
-    // Build a map of team_id -> wins for quick lookup
-    let team_wins: HashMap<String, i32> = standings
-        .iter()
-        .map(|(id, wins, _, _)| (id.clone(), *wins))
-        .collect();
-
-    // Calculate Buchholz scores (sum of opponent wins)
-    let mut buchholz_scores: HashMap<String, f64> = HashMap::new();
+    // Forfeits of withdrawn teams are scored by the draw itself, not played
+    let scored_games: i32 = conn
+        .query_row(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM qualifying_games g
+                 WHERE g.round_id = ?1 AND g.is_forfeit = 0
+                   AND (g.team1_score IS NOT NULL OR g.team2_score IS NOT NULL
+                        OR EXISTS (SELECT 1 FROM game_ends e WHERE e.qualifying_game_id = g.id)))
+              + (SELECT COUNT(*) FROM melee_games g
+                 WHERE g.round_id = ?1 AND (g.team1_score IS NOT NULL OR g.team2_score IS NOT NULL))
+            "#,
+            params![round_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
 
-    for (team_id, _, _, _) in &standings {
-        let opponents = team_opponents.get(team_id).cloned().unwrap_or_default();
-        let buchholz: f64 = opponents
-            .iter()
-            .map(|opp_id| team_wins.get(opp_id).copied().unwrap_or(0) as f64)
-            .sum();
-        buchholz_scores.insert(team_id.clone(), buchholz);
+    if scored_games > 0 {
+        return Err(format!("Cannot delete round {} after scores have been entered.", round_number));
     }
 
-    // Calculate Fine Buchholz scores (sum of opponents' Buchholz scores)
-    let mut fine_buchholz_scores: HashMap<String, f64> = HashMap::new();
+    // A round half deleted could neither be played nor drawn again
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
 
-    for (team_id, _, _, _) in &standings {
-        let opponents = team_opponents.get(team_id).cloned().unwrap_or_default();
-        let fine_buchholz: f64 = opponents
-            .iter()
-            .map(|opp_id| buchholz_scores.get(opp_id).copied().unwrap_or(0.0))
-            .sum();
-        fine_buchholz_scores.insert(team_id.clone(), fine_buchholz);
+    for table in ["court_history", "pairing_history", "pairing_floats", "bye_history"] {
+        tx.execute(&format!("DELETE FROM {} WHERE round_id = ?1", table), params![round_id])
+            .map_err(|e| e.to_string())?;
     }
 
-    // Update Buchholz and Fine Buchholz scores in database
-    for (team_id, buchholz) in &buchholz_scores {
-        let fine_buchholz = fine_buchholz_scores.get(team_id).copied().unwrap_or(0.0);
-        conn.execute(
-            "UPDATE team_standings SET buchholz_score = ?3, fine_buchholz_score = ?4 WHERE tournament_id = ?1 AND team_id = ?2",
-            params![tournament_id, team_id, buchholz, fine_buchholz],
+    // The poules are drawn with the first round
+    if round_number == 1 {
+        tx.execute(
+            "DELETE FROM pool_assignments WHERE tournament_id = ?1",
+            params![tournament_id],
         )
         .map_err(|e| e.to_string())?;
     }
 
-    // Calculate ranks with tiebreaker order: wins → buchholz → fine_buchholz → differential → random
-    // Generate random tiebreaker values for each team
-    use rand::Rng;
-    let mut rng = rand::thread_rng();
-    let random_tiebreakers: HashMap<String, u64> = standings
-        .iter()
-        .map(|(id, _, _, _)| (id.clone(), rng.gen()))
-        .collect();
+    tx.execute(
+        "DELETE FROM qualifying_games WHERE round_id = ?1",
+        params![round_id],
+    )
+    .map_err(|e| e.to_string())?;
 
-    let mut ranked: Vec<(String, i32, f64, f64, i32, u64)> = standings
-        .iter()
-        .map(|(id, wins, diff, _)| {
-            let buchholz = buchholz_scores.get(id).copied().unwrap_or(0.0);
-            let fine_buchholz = fine_buchholz_scores.get(id).copied().unwrap_or(0.0);
-            let random_tb = random_tiebreakers.get(id).copied().unwrap_or(0);
-            (id.clone(), *wins, buchholz, fine_buchholz, *diff, random_tb)
-        })
-        .collect();
+    // Mêlée teams and games go with the round
+    tx.execute(
+        "DELETE FROM qualifying_rounds WHERE id = ?1",
+        params![round_id],
+    )
+    .map_err(|e| e.to_string())?;
 
-    // Sort by: wins DESC → buchholz DESC → fine_buchholz DESC → differential DESC → random
-    ranked.sort_by(|a, b| {
-        b.1.cmp(&a.1) // wins (descending)
-            .then(b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal)) // buchholz (descending)
-            .then(b.3.partial_cmp(&a.3).unwrap_or(std::cmp::Ordering::Equal)) // fine_buchholz (descending)
-            .then(b.4.cmp(&a.4)) // differential (descending)
-            .then(b.5.cmp(&a.5)) // random tiebreaker (descending)
-    });
+    // The round's draw, which for the first round of poules is also the pool draw
+    tx.execute(
+        "DELETE FROM draws WHERE reference_id = ?1",
+        params![round_id],
+    )
+    .map_err(|e| e.to_string())?;
 
-    for (rank, (team_id, _, _, _, _, _)) in ranked.iter().enumerate() {
-        conn.execute(
-            "UPDATE team_standings SET rank = ?3 WHERE tournament_id = ?1 AND team_id = ?2",
-            params![tournament_id, team_id, (rank + 1) as i32],
-        )
-        .map_err(|e| e.to_string())?;
-    }
+    tx.commit().map_err(|e| e.to_string())?;
 
     Ok(())
 }
 
-/// Calculate ranks using point quotient tiebreaker (for Swiss Hotel, Round Robin, Pool Play)
-/// Tiebreaker order: wins → differential → point_quotient → random
-fn calculate_point_quotient_ranks(conn: &rusqlite::Connection, tournament_id: &str) -> Result<(), String> {
-    // Get all standings
-    let mut stmt = conn
-        .prepare(
-            r#"
-            SELECT team_id, wins, differential, points_for, points_against
-            FROM team_standings
-            WHERE tournament_id = ?1
-            "#,
-        )
-        .map_err(|e| e.to_string())?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let standings: Vec<(String, i32, i32, i32, i32)> = stmt
-        .query_map(params![tournament_id], |row| {
-            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?))
+    fn team(id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id, "tournamentId": "t", "captain": id, "player2": "p", "player3": null,
+            "region": null, "club": null, "createdAt": "", "withdrawnAt": null
         })
-        .map_err(|e| e.to_string())?
-        .filter_map(|r| r.ok())
-        .collect();
-
-    // Calculate and update point quotient for each team
-    for (team_id, _, _, points_for, points_against) in &standings {
-        let point_quotient = if *points_against > 0 {
-            *points_for as f64 / *points_against as f64
-        } else if *points_for > 0 {
-            f64::MAX // Infinite quotient if no points against but some points for
-        } else {
-            1.0 // Default to 1.0 if no games played
-        };
-
-        conn.execute(
-            "UPDATE team_standings SET point_quotient = ?3 WHERE tournament_id = ?1 AND team_id = ?2",
-            params![tournament_id, team_id, point_quotient],
-        )
-        .map_err(|e| e.to_string())?;
     }
 
-    // Generate random tiebreaker values for each team
-    use rand::Rng;
-    let mut rng = rand::thread_rng();
-    let random_tiebreakers: HashMap<String, u64> = standings
-        .iter()
-        .map(|(id, _, _, _, _)| (id.clone(), rng.gen()))
-        .collect();
-
-    // Build ranked list with point quotient
-    let mut ranked: Vec<(String, i32, i32, f64, u64)> = standings
-        .iter()
-        .map(|(id, wins, diff, points_for, points_against)| {
-            let point_quotient = if *points_against > 0 {
-                *points_for as f64 / *points_against as f64
-            } else if *points_for > 0 {
-                f64::MAX
-            } else {
-                1.0
-            };
-            let random_tb = random_tiebreakers.get(id).copied().unwrap_or(0);
-            (id.clone(), *wins, *diff, point_quotient, random_tb)
+    /// A standing as stored before the later tiebreak scores were added
+    fn standing(id: &str, wins: i32, rank: i32) -> serde_json::Value {
+        serde_json::json!({
+            "id": id, "tournamentId": "t", "teamId": id, "wins": wins, "losses": 1 - wins,
+            "pointsFor": 13 * wins, "pointsAgainst": 13 - 13 * wins, "differential": 26 * wins - 13,
+            "buchholzScore": 1.0, "fineBuchholzScore": 1.0, "pointQuotient": 1.0,
+            "isEliminated": false, "rank": rank, "poolNumber": null, "poolRank": null
         })
-        .collect();
-
-    // Sort by: wins DESC → differential DESC → point_quotient DESC → random
-    ranked.sort_by(|a, b| {
-        b.1.cmp(&a.1) // wins (descending)
-            .then(b.2.cmp(&a.2)) // differential (descending)
-            .then(b.3.partial_cmp(&a.3).unwrap_or(std::cmp::Ordering::Equal)) // point_quotient (descending)
-            .then(b.4.cmp(&a.4)) // random tiebreaker (descending)
-    });
+    }
 
-    for (rank, (team_id, _, _, _, _)) in ranked.iter().enumerate() {
-        conn.execute(
-            "UPDATE team_standings SET rank = ?3 WHERE tournament_id = ?1 AND team_id = ?2",
-            params![tournament_id, team_id, (rank + 1) as i32],
-        )
-        .map_err(|e| e.to_string())?;
+    #[test]
+    fn score_groups_follow_the_stored_rank() {
+        // b is ahead of a on a tiebreaker after wins; c entered late and is not ranked yet
+        let standings: HashMap<String, TeamStanding> = [("a", 1, 2), ("b", 1, 1), ("c", 1, 0), ("d", 0, 3)]
+            .iter()
+            .map(|&(id, wins, rank)| (id.to_string(), serde_json::from_value(standing(id, wins, rank)).unwrap()))
+            .collect();
+        let mut ids = vec!["d", "c", "a", "b"];
+        ids.sort_by(|a, b| compare_standings(a, b, &standings));
+        assert_eq!(ids, vec!["b", "a", "d", "c"]);
     }
 
-    Ok(())
+    #[test]
+    fn replays_a_draw_stored_without_the_newer_standing_fields() {
+        let ids = ["a", "b", "c", "d", "e", "f"];
+        let inputs = serde_json::json!({
+            "pairingMethod": "swiss",
+            "roundNumber": 2,
+            "avoidancePolicy": "none",
+            "avoidanceRounds": null,
+            "teams": ids.iter().map(|id| team(id)).collect::<Vec<_>>(),
+            "standings": ids.iter().enumerate().map(|(i, id)| standing(id, (i < 3) as i32, i as i32 + 1)).collect::<Vec<_>>(),
+            "pairingHistory": [["a", "d"], ["b", "e"], ["c", "f"]],
+            "previousFloaters": [],
+            "byeHistory": [],
+            "pools": [],
+            "courts": { "regular": [1, 2, 3], "reserved": [] },
+            "courtHistory": []
+        });
+
+        let first = replay_round_draw(&inputs, &mut seeded_rng(7)).unwrap();
+        let again = replay_round_draw(&inputs, &mut seeded_rng(7)).unwrap();
+        assert_eq!(first, again);
+        assert_eq!(first["games"].as_array().unwrap().len(), 3);
+    }
 }
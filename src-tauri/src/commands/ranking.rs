@@ -0,0 +1,641 @@
+use crate::commands::draws::{new_seed, record_draw, seeded_rng, DrawRng};
+use crate::models::Team;
+use crate::commands::tournaments::get_bye_scoring;
+use rand::seq::SliceRandom;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A criterion used to order teams in the standings, applied in the order the
+/// tournament lists them until teams are separated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tiebreaker {
+    Wins,
+    Buchholz,
+    MedianBuchholz,
+    CutBuchholz,
+    FineBuchholz,
+    SonnebornBerger,
+    Differential,
+    Quotient,
+    PointsFor,
+    HeadToHead,
+    /// Ends the chain: teams still tied share their rank, and are only drawn
+    /// apart where they straddle a cut line (see `draw_ties_at_cuts`)
+    Random,
+}
+
+impl Tiebreaker {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "wins" => Ok(Tiebreaker::Wins),
+            "buchholz" => Ok(Tiebreaker::Buchholz),
+            "medianBuchholz" => Ok(Tiebreaker::MedianBuchholz),
+            "cutBuchholz" => Ok(Tiebreaker::CutBuchholz),
+            "fineBuchholz" => Ok(Tiebreaker::FineBuchholz),
+            "sonnebornBerger" => Ok(Tiebreaker::SonnebornBerger),
+            "differential" => Ok(Tiebreaker::Differential),
+            "quotient" => Ok(Tiebreaker::Quotient),
+            "pointsFor" => Ok(Tiebreaker::PointsFor),
+            "headToHead" => Ok(Tiebreaker::HeadToHead),
+            "random" => Ok(Tiebreaker::Random),
+            _ => Err(format!("Unknown tiebreaker: {}", value)),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Tiebreaker::Wins => "wins",
+            Tiebreaker::Buchholz => "buchholz",
+            Tiebreaker::MedianBuchholz => "medianBuchholz",
+            Tiebreaker::CutBuchholz => "cutBuchholz",
+            Tiebreaker::FineBuchholz => "fineBuchholz",
+            Tiebreaker::SonnebornBerger => "sonnebornBerger",
+            Tiebreaker::Differential => "differential",
+            Tiebreaker::Quotient => "quotient",
+            Tiebreaker::PointsFor => "pointsFor",
+            Tiebreaker::HeadToHead => "headToHead",
+            Tiebreaker::Random => "random",
+        }
+    }
+}
+
+/// Tiebreakers used when a tournament does not set its own: Buchholz for the
+/// Swiss system, point quotient for the other formats.
+pub fn default_tiebreakers(pairing_method: &str) -> Vec<Tiebreaker> {
+    use Tiebreaker::*;
+    match pairing_method {
+        "swiss" => vec![Wins, Buchholz, FineBuchholz, Differential, Random],
+        _ => vec![Wins, Differential, Quotient, Random],
+    }
+}
+
+/// Parses a stored chain ("wins,buchholz,..."). Every criterion may appear once,
+/// and `random` can only come last since nothing is applied after it.
+pub fn parse_tiebreakers(value: &str) -> Result<Vec<Tiebreaker>, String> {
+    let mut chain = Vec::new();
+    for name in value.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        let tiebreaker = Tiebreaker::parse(name)?;
+        if chain.contains(&tiebreaker) {
+            return Err(format!("Tiebreaker listed twice: {}", name));
+        }
+        if chain.contains(&Tiebreaker::Random) {
+            return Err("The random tiebreaker must come last.".to_string());
+        }
+        chain.push(tiebreaker);
+    }
+
+    if chain.is_empty() {
+        return Err("At least one tiebreaker is required.".to_string());
+    }
+    Ok(chain)
+}
+
+/// The chain a tournament ranks with; an empty (never set) chain means the default.
+pub fn tiebreakers_or_default(stored: &str, pairing_method: &str) -> Vec<Tiebreaker> {
+    parse_tiebreakers(stored).unwrap_or_else(|_| default_tiebreakers(pairing_method))
+}
+
+/// Validates a chain sent by the frontend and returns it in its stored form.
+pub fn join_tiebreakers(names: &[String]) -> Result<String, String> {
+    let chain = parse_tiebreakers(&names.join(","))?;
+    Ok(chain
+        .iter()
+        .map(|t| t.as_str())
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+struct TeamRecord {
+    team_id: String,
+    wins: i32,
+    points_for: i32,
+    points_against: i32,
+    differential: i32,
+    buchholz: f64,
+    median_buchholz: f64,
+    cut_buchholz: f64,
+    fine_buchholz: f64,
+    sonneborn_berger: f64,
+    quotient: f64,
+}
+
+/// Ranks every team of a tournament with its tiebreaker chain.
+///
+/// Teams start in one group; each criterion in turn orders the teams inside a
+/// group and splits it where they differ, so a criterion only ever separates
+/// teams still tied on all the previous ones. Head-to-head counts the wins
+/// among the teams of the group only. Teams still tied at the end of the
+/// chain share a rank ("5=") instead of being drawn apart. Buchholz scores,
+/// Sonneborn-Berger, the point quotient and the head-to-head wins are stored
+/// alongside the rank.
+pub fn rank_teams(conn: &rusqlite::Connection, tournament_id: &str) -> Result<(), String> {
+    let (pairing_method, stored_tiebreakers): (String, String) = conn
+        .query_row(
+            "SELECT pairing_method, tiebreakers FROM tournaments WHERE id = ?1",
+            params![tournament_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| e.to_string())?;
+    let chain = tiebreakers_or_default(&stored_tiebreakers, &pairing_method);
+
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT team_id, wins, points_for, points_against, differential
+            FROM team_standings
+            WHERE tournament_id = ?1
+            ORDER BY team_id
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let mut records: Vec<TeamRecord> = stmt
+        .query_map(params![tournament_id], |row| {
+            Ok(TeamRecord {
+                team_id: row.get(0)?,
+                wins: row.get(1)?,
+                points_for: row.get(2)?,
+                points_against: row.get(3)?,
+                differential: row.get(4)?,
+                buchholz: 0.0,
+                median_buchholz: 0.0,
+                cut_buchholz: 0.0,
+                fine_buchholz: 0.0,
+                sonneborn_berger: 0.0,
+                quotient: 0.0,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    // Opponents of each team
+    let mut stmt = conn
+        .prepare("SELECT team1_id, team2_id FROM pairing_history WHERE tournament_id = ?1")
+        .map_err(|e| e.to_string())?;
+
+    let mut opponents: HashMap<String, Vec<String>> = HashMap::new();
+    let pairs = stmt
+        .query_map(params![tournament_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    for (t1, t2) in pairs {
+        opponents.entry(t1.clone()).or_default().push(t2.clone());
+        opponents.entry(t2).or_default().push(t1);
+    }
+
+    // BYE wins only count toward opponents' Buchholz if the BYE policy says so
+    let bye_scoring = get_bye_scoring(conn, tournament_id)?;
+    let mut bye_wins: HashMap<String, i32> = HashMap::new();
+    if !bye_scoring.counts_in_tiebreaks {
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT g.team1_id, COUNT(*)
+                FROM qualifying_games g
+                JOIN qualifying_rounds r ON g.round_id = r.id
+                WHERE r.tournament_id = ?1 AND r.is_complete = 1 AND g.is_bye = 1 AND g.team1_id IS NOT NULL
+                GROUP BY g.team1_id
+                "#,
+            )
+            .map_err(|e| e.to_string())?;
+
+        bye_wins = stmt
+            .query_map(params![tournament_id], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.to_string())?;
+    }
+
+    let opponent_wins: HashMap<String, f64> = records
+        .iter()
+        .map(|r| {
+            let wins = r.wins - bye_wins.get(&r.team_id).copied().unwrap_or(0);
+            (r.team_id.clone(), wins as f64)
+        })
+        .collect();
+    let opponent_scores = |team_id: &str, scores: &HashMap<String, f64>| -> Vec<f64> {
+        opponents
+            .get(team_id)
+            .map(|opps| {
+                opps.iter()
+                    .map(|o| scores.get(o).copied().unwrap_or(0.0))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let game_winners = load_game_winners(conn, tournament_id)?;
+
+    for record in records.iter_mut() {
+        let mut scores = opponent_scores(&record.team_id, &opponent_wins);
+        record.buchholz = scores.iter().sum();
+
+        // Median Buchholz drops the best and the worst opponent, cut-1 only the worst
+        scores.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        record.median_buchholz = if scores.len() > 2 {
+            scores[1..scores.len() - 1].iter().sum()
+        } else {
+            record.buchholz
+        };
+        record.cut_buchholz = if scores.len() > 1 {
+            scores[1..].iter().sum()
+        } else {
+            record.buchholz
+        };
+
+        // Sonneborn-Berger: wins of the opponents the team beat
+        record.sonneborn_berger = game_winners
+            .iter()
+            .filter(|(winner, _)| *winner == record.team_id)
+            .map(|(_, loser)| opponent_wins.get(loser).copied().unwrap_or(0.0))
+            .sum();
+
+        record.quotient = if record.points_against > 0 {
+            record.points_for as f64 / record.points_against as f64
+        } else if record.points_for > 0 {
+            f64::MAX // Infinite quotient if no points against but some points for
+        } else {
+            1.0 // Default to 1.0 if no games played
+        };
+    }
+
+    // Fine Buchholz: sum of the opponents' Buchholz scores
+    let buchholz: HashMap<String, f64> = records
+        .iter()
+        .map(|r| (r.team_id.clone(), r.buchholz))
+        .collect();
+    for record in records.iter_mut() {
+        record.fine_buchholz = opponent_scores(&record.team_id, &buchholz).iter().sum();
+    }
+
+    let mut head_to_head: HashMap<usize, i32> = HashMap::new();
+    let mut groups: Vec<Vec<usize>> = vec![(0..records.len()).collect()];
+
+    for tiebreaker in chain.into_iter().take_while(|t| *t != Tiebreaker::Random) {
+        let mut next_groups = Vec::with_capacity(groups.len());
+        for mut group in groups {
+            if group.len() < 2 {
+                next_groups.push(group);
+                continue;
+            }
+
+            let keys: HashMap<usize, f64> = group
+                .iter()
+                .map(|&idx| {
+                    let r = &records[idx];
+                    let key = match tiebreaker {
+                        Tiebreaker::Wins => r.wins as f64,
+                        Tiebreaker::Buchholz => r.buchholz,
+                        Tiebreaker::MedianBuchholz => r.median_buchholz,
+                        Tiebreaker::CutBuchholz => r.cut_buchholz,
+                        Tiebreaker::FineBuchholz => r.fine_buchholz,
+                        Tiebreaker::SonnebornBerger => r.sonneborn_berger,
+                        Tiebreaker::Differential => r.differential as f64,
+                        Tiebreaker::Quotient => r.quotient,
+                        Tiebreaker::PointsFor => r.points_for as f64,
+                        Tiebreaker::HeadToHead => game_winners
+                            .iter()
+                            .filter(|(winner, loser)| {
+                                *winner == r.team_id
+                                    && group.iter().any(|&other| records[other].team_id == *loser)
+                            })
+                            .count() as f64,
+                        // Never reached: the chain stops at the draw
+                        Tiebreaker::Random => 0.0,
+                    };
+                    (idx, key)
+                })
+                .collect();
+
+            // Head-to-head wins are kept from the group they were counted in
+            if tiebreaker == Tiebreaker::HeadToHead {
+                head_to_head.extend(group.iter().map(|&idx| (idx, keys[&idx] as i32)));
+            }
+
+            // Highest first, then split where the key changes
+            group.sort_by(|a, b| {
+                keys[b]
+                    .partial_cmp(&keys[a])
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            let mut current: Vec<usize> = Vec::new();
+            for idx in group {
+                if let Some(&last) = current.last() {
+                    if keys[&last] != keys[&idx] {
+                        next_groups.push(std::mem::take(&mut current));
+                    }
+                }
+                current.push(idx);
+            }
+            next_groups.push(current);
+        }
+        groups = next_groups;
+    }
+
+    // Tied teams all take the rank of the first of them
+    let mut position = 0;
+    for group in groups {
+        let rank = position as i32 + 1;
+        let is_tied = group.len() > 1;
+        position += group.len();
+
+        for idx in group {
+            let record = &records[idx];
+            conn.execute(
+                r#"
+                UPDATE team_standings SET
+                    buchholz_score = ?3,
+                    median_buchholz_score = ?4,
+                    cut_buchholz_score = ?5,
+                    fine_buchholz_score = ?6,
+                    sonneborn_berger_score = ?7,
+                    point_quotient = ?8,
+                    head_to_head_wins = ?9,
+                    rank = ?10,
+                    is_tied = ?11
+                WHERE tournament_id = ?1 AND team_id = ?2
+                "#,
+                params![
+                    tournament_id,
+                    record.team_id,
+                    record.buchholz,
+                    record.median_buchholz,
+                    record.cut_buchholz,
+                    record.fine_buchholz,
+                    record.sonneborn_berger,
+                    record.quotient,
+                    head_to_head.get(&idx).copied().unwrap_or(0),
+                    rank,
+                    if is_tied { 1 } else { 0 }
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Groups of tied teams drawn apart at a cut line, each in the order it was
+/// drawn from
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TiebreakDrawInputs {
+    groups: Vec<Vec<String>>,
+}
+
+/// Draws the tiebreaks at a cut line again from the groups stored with their draw.
+pub fn replay_tiebreak_draw(inputs: &serde_json::Value, rng: &mut DrawRng) -> Result<serde_json::Value, String> {
+    let inputs: TiebreakDrawInputs = serde_json::from_value(inputs.clone()).map_err(|e| e.to_string())?;
+    let drawn: Vec<Vec<String>> = inputs
+        .groups
+        .into_iter()
+        .map(|mut group| {
+            group.shuffle(rng);
+            group
+        })
+        .collect();
+    serde_json::to_value(drawn).map_err(|e| e.to_string())
+}
+
+/// Draws apart the teams that share a rank across a cut line, such as the
+/// qualification boundary, the split between two brackets or the BYEs of a
+/// bracket. `ranks` is the shared rank of each team, in the order of `teams`;
+/// `cuts` are positions in that order, a cut at 8 separating the first eight
+/// teams from the rest. Ties that don't straddle a cut keep their order. The
+/// draw is recorded as a tiebreak.
+pub fn draw_ties_at_cuts(
+    conn: &rusqlite::Connection,
+    tournament_id: &str,
+    teams: &mut [Team],
+    ranks: &[(bool, i32)],
+    cuts: &[usize],
+) -> Result<(), String> {
+    let mut tied_ranges: Vec<(usize, usize)> = Vec::new();
+    let mut start = 0;
+    while start < teams.len() {
+        let mut end = start + 1;
+        while end < teams.len() && ranks[end] == ranks[start] {
+            end += 1;
+        }
+        if cuts.iter().any(|&cut| start < cut && cut < end) {
+            tied_ranges.push((start, end));
+        }
+        start = end;
+    }
+
+    if tied_ranges.is_empty() {
+        return Ok(());
+    }
+
+    let team_ids = |teams: &[Team]| -> Vec<Vec<String>> {
+        tied_ranges
+            .iter()
+            .map(|&(start, end)| teams[start..end].iter().map(|t| t.id.clone()).collect())
+            .collect()
+    };
+
+    let seed = new_seed();
+    let mut rng = seeded_rng(seed);
+    let tied = team_ids(teams);
+    for &(start, end) in &tied_ranges {
+        teams[start..end].shuffle(&mut rng);
+    }
+
+    record_draw(
+        conn,
+        tournament_id,
+        "tiebreak",
+        None,
+        seed,
+        &TiebreakDrawInputs { groups: tied },
+        &team_ids(teams),
+    )
+}
+
+/// (winner, loser) of every scored qualifying game in completed rounds. A game
+/// between two withdrawn teams is lost by both and has no winner.
+fn load_game_winners(
+    conn: &rusqlite::Connection,
+    tournament_id: &str,
+) -> Result<Vec<(String, String)>, String> {
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT g.team1_id, g.team2_id, g.team1_score, g.team2_score
+            FROM qualifying_games g
+            JOIN qualifying_rounds r ON g.round_id = r.id
+            WHERE r.tournament_id = ?1 AND r.is_complete = 1 AND g.is_bye = 0
+              AND g.team1_id IS NOT NULL AND g.team2_id IS NOT NULL
+              AND g.team1_score IS NOT NULL AND g.team2_score IS NOT NULL
+              AND g.team1_score != g.team2_score
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let games = stmt
+        .query_map(params![tournament_id], |row| {
+            let (t1, t2): (String, String) = (row.get(0)?, row.get(1)?);
+            let (s1, s2): (i32, i32) = (row.get(2)?, row.get(3)?);
+            Ok(if s1 > s2 { (t1, t2) } else { (t2, t1) })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(games)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::schema::create_tables;
+
+    /// A Swiss tournament ranked with `chain`, with one standing per
+    /// (team, wins) and the given pairings and (winner, loser) games
+    fn ranked(
+        chain: &str,
+        standings: &[(&str, i32)],
+        pairings: &[(&str, &str)],
+        games: &[(&str, &str)],
+    ) -> Vec<(String, i32, bool)> {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        // The teams themselves are never read, only their standings and games
+        conn.execute_batch("PRAGMA foreign_keys = OFF;").unwrap();
+        conn.execute(
+            r#"
+            INSERT INTO tournaments (
+                id, name, team_composition, tournament_type, start_date, end_date,
+                director, head_umpire, format, day_type, number_of_courts,
+                pairing_method, created_at, updated_at, tiebreakers
+            ) VALUES ('t', 'Test', 'mixed', 'club', '', '', '', '', 'double', 'single', 8,
+                      'swiss', '', '', ?1)
+            "#,
+            params![chain],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO qualifying_rounds (id, tournament_id, round_number, is_complete, created_at) VALUES ('r', 't', 1, 1, '')",
+            [],
+        )
+        .unwrap();
+        for (id, wins) in standings {
+            conn.execute(
+                "INSERT INTO team_standings (id, tournament_id, team_id, wins) VALUES (?1, 't', ?1, ?2)",
+                params![id, wins],
+            )
+            .unwrap();
+        }
+        for (i, (a, b)) in pairings.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO pairing_history (id, tournament_id, team1_id, team2_id, round_id) VALUES (?1, 't', ?2, ?3, 'r')",
+                params![format!("p{}", i), a, b],
+            )
+            .unwrap();
+        }
+        for (i, (winner, loser)) in games.iter().enumerate() {
+            conn.execute(
+                r#"
+                INSERT INTO qualifying_games (id, round_id, court_number, team1_id, team2_id, team1_score, team2_score)
+                VALUES (?1, 'r', 1, ?2, ?3, 13, 5)
+                "#,
+                params![format!("g{}", i), winner, loser],
+            )
+            .unwrap();
+        }
+
+        rank_teams(&conn, "t").unwrap();
+
+        let mut stmt = conn
+            .prepare("SELECT team_id, rank, is_tied FROM team_standings ORDER BY rank, team_id")
+            .unwrap();
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get::<_, i32>(2)? != 0)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        rows
+    }
+
+    fn rank(id: &str, rank: i32, is_tied: bool) -> (String, i32, bool) {
+        (id.to_string(), rank, is_tied)
+    }
+
+    // x and y both have 3 wins and a Buchholz of 6; x's middle opponent has
+    // 2 wins, y's only 1. r and v lost to them and stay level on everything.
+    const STANDINGS: &[(&str, i32)] = &[
+        ("s", 5), ("p", 4), ("x", 3), ("y", 3), ("q", 2), ("u", 1), ("r", 0), ("v", 0),
+    ];
+    const PAIRINGS: &[(&str, &str)] = &[
+        ("x", "p"), ("x", "q"), ("x", "r"), ("y", "s"), ("y", "u"), ("y", "v"),
+    ];
+
+    #[test]
+    fn random_must_come_last() {
+        assert!(parse_tiebreakers("wins,buchholz,random").is_ok());
+        assert!(parse_tiebreakers("wins,random,buchholz").is_err());
+        assert!(parse_tiebreakers("random,wins").is_err());
+        assert!(parse_tiebreakers("wins,wins").is_err());
+        assert!(parse_tiebreakers("").is_err());
+        assert_eq!(
+            join_tiebreakers(&["wins".to_string(), " headToHead ".to_string()]).unwrap(),
+            "wins,headToHead"
+        );
+    }
+
+    #[test]
+    fn teams_tied_on_the_whole_chain_share_a_rank() {
+        assert_eq!(
+            ranked("wins,buchholz,random", STANDINGS, PAIRINGS, &[]),
+            vec![
+                rank("s", 1, false),
+                rank("p", 2, false),
+                rank("x", 3, true),
+                rank("y", 3, true),
+                rank("q", 5, false),
+                rank("u", 6, false),
+                rank("r", 7, true),
+                rank("v", 7, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn median_buchholz_drops_the_best_and_worst_opponent() {
+        assert_eq!(
+            ranked("wins,medianBuchholz", STANDINGS, PAIRINGS, &[]),
+            vec![
+                rank("s", 1, false),
+                rank("p", 2, false),
+                rank("x", 3, false),
+                rank("y", 4, false),
+                rank("q", 5, false),
+                rank("u", 6, false),
+                rank("r", 7, true),
+                rank("v", 7, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn head_to_head_only_counts_games_inside_the_tied_group() {
+        // b beat a, but a's win over c, who is not tied with them, does not count
+        let standings = [("a", 1), ("b", 1), ("c", 0), ("d", 1)];
+        let games = [("b", "a"), ("a", "c"), ("d", "c")];
+        assert_eq!(
+            ranked("wins,headToHead", &standings, &[], &games),
+            vec![
+                rank("b", 1, false),
+                rank("a", 2, true),
+                rank("d", 2, true),
+                rank("c", 4, false),
+            ]
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use crate::models::GameRules;
+use serde::Serialize;
+
+/// What is wrong with an entered score, so the frontend can explain it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ScoreErrorKind {
+    NegativeScore,
+    AboveTarget,
+    Draw,
+    TargetNotReached,
+    /// Not a score rule: the game could not be found or saved
+    Other,
+}
+
+/// Error returned by the score entry commands
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreError {
+    pub kind: ScoreErrorKind,
+    pub message: String,
+    pub target_score: Option<i32>,
+}
+
+impl ScoreError {
+    fn rule(kind: ScoreErrorKind, message: String, target_score: i32) -> Self {
+        ScoreError {
+            kind,
+            message,
+            target_score: Some(target_score),
+        }
+    }
+}
+
+impl From<String> for ScoreError {
+    fn from(message: String) -> Self {
+        ScoreError {
+            kind: ScoreErrorKind::Other,
+            message,
+            target_score: None,
+        }
+    }
+}
+
+/// Checks a played game's score against the tournament's rules: no negative
+/// scores, nobody above the target, no draw, and the winner on the target
+/// unless time was called.
+pub fn validate_score(rules: &GameRules, team1_score: i32, team2_score: i32) -> Result<(), ScoreError> {
+    let target = rules.target_score;
+
+    if team1_score < 0 || team2_score < 0 {
+        return Err(ScoreError::rule(
+            ScoreErrorKind::NegativeScore,
+            "Scores cannot be negative.".to_string(),
+            target,
+        ));
+    }
+    if team1_score > target || team2_score > target {
+        return Err(ScoreError::rule(
+            ScoreErrorKind::AboveTarget,
+            format!("Games are played to {}: no score can be higher.", target),
+            target,
+        ));
+    }
+    if team1_score == team2_score {
+        return Err(ScoreError::rule(
+            ScoreErrorKind::Draw,
+            "A game cannot end in a draw.".to_string(),
+            target,
+        ));
+    }
+    if !rules.time_limited && team1_score.max(team2_score) != target {
+        return Err(ScoreError::rule(
+            ScoreErrorKind::TargetNotReached,
+            format!("The winner must reach {} points.", target),
+            target,
+        ));
+    }
+
+    Ok(())
+}
@@ -1,7 +1,10 @@
+use crate::commands::brackets::apply_walkovers;
+use crate::commands::qualifying::{forfeit_withdrawn_games, rebuild_standings};
 use crate::db::Database;
 use crate::models::{CreateTeamData, Team, TeamStanding};
 use chrono::Utc;
 use rusqlite::params;
+use std::collections::HashSet;
 use tauri::State;
 use uuid::Uuid;
 
@@ -12,7 +15,7 @@ pub fn get_teams(db: State<Database>, tournament_id: String) -> Result<Vec<Team>
     let mut stmt = conn
         .prepare(
             r#"
-            SELECT id, tournament_id, captain, player2, player3, region, club, created_at
+            SELECT id, tournament_id, captain, player2, player3, region, club, created_at, is_active, withdrawn_at, seed
             FROM teams
             WHERE tournament_id = ?1
             ORDER BY captain
@@ -31,6 +34,9 @@ pub fn get_teams(db: State<Database>, tournament_id: String) -> Result<Vec<Team>
                 region: row.get(5)?,
                 club: row.get(6)?,
                 created_at: row.get(7)?,
+                is_active: row.get::<_, i32>(8)? != 0,
+                withdrawn_at: row.get(9)?,
+                seed: row.get(10)?,
             })
         })
         .map_err(|e| e.to_string())?
@@ -47,7 +53,7 @@ pub fn get_team(db: State<Database>, id: String) -> Result<Team, String> {
     let team = conn
         .query_row(
             r#"
-            SELECT id, tournament_id, captain, player2, player3, region, club, created_at
+            SELECT id, tournament_id, captain, player2, player3, region, club, created_at, is_active, withdrawn_at, seed
             FROM teams
             WHERE id = ?1
             "#,
@@ -62,6 +68,9 @@ pub fn get_team(db: State<Database>, id: String) -> Result<Team, String> {
                     region: row.get(5)?,
                     club: row.get(6)?,
                     created_at: row.get(7)?,
+                    is_active: row.get::<_, i32>(8)? != 0,
+                    withdrawn_at: row.get(9)?,
+                    seed: row.get(10)?,
                 })
             },
         )
@@ -74,40 +83,186 @@ pub fn get_team(db: State<Database>, id: String) -> Result<Team, String> {
 pub fn create_team(db: State<Database>, data: CreateTeamData) -> Result<Team, String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
-    // Get tournament's number of courts
-    let number_of_courts: i32 = conn
+    // Get the tournament's team limit, if the director set one
+    let (max_teams, pairing_method): (Option<i32>, String) = conn
         .query_row(
-            "SELECT number_of_courts FROM tournaments WHERE id = ?1",
+            "SELECT max_teams, pairing_method FROM tournaments WHERE id = ?1",
             params![data.tournament_id],
-            |row| row.get(0),
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| e.to_string())?;
+
+    if pairing_method == "melee" {
+        return Err("A mêlée has no fixed teams: enter the players individually.".to_string());
+    }
+
+    // Once qualifying has started a team can only join as a late entry
+    if rounds_exist(&conn, &data.tournament_id)? {
+        return Err("Qualifying has already started: register the team as a late entry.".to_string());
+    }
+
+    check_team_limit(&conn, &data.tournament_id, max_teams)?;
+
+    insert_team(&conn, data)
+}
+
+/// Registers a team that arrives after qualifying started. It is drawn from
+/// the next round on. Under the "creditedByes" late entry policy every round
+/// it missed counts as a BYE, otherwise it starts with no wins.
+#[tauri::command]
+pub fn register_late_team(db: State<Database>, data: CreateTeamData) -> Result<Team, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let (max_teams, pairing_method, number_of_qualifying_rounds, late_entry_policy): (Option<i32>, String, i32, String) = conn
+        .query_row(
+            "SELECT max_teams, pairing_method, number_of_qualifying_rounds, late_entry_policy FROM tournaments WHERE id = ?1",
+            params![data.tournament_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
         )
         .map_err(|e| e.to_string())?;
 
+    // Only Swiss pairings are drawn from the standings of the moment; the
+    // other formats fix their schedule or their pools with the first round
+    if pairing_method != "swiss" && pairing_method != "swissHotel" {
+        return Err("Late entries can only join a Swiss system or Swiss Hotel qualifier.".to_string());
+    }
+
+    let mut stmt = conn
+        .prepare("SELECT id, is_complete FROM qualifying_rounds WHERE tournament_id = ?1 ORDER BY round_number")
+        .map_err(|e| e.to_string())?;
+
+    let rounds: Vec<(String, bool)> = stmt
+        .query_map(params![data.tournament_id], |row| Ok((row.get(0)?, row.get::<_, i32>(1)? != 0)))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    if rounds.is_empty() {
+        return Err("Qualifying has not started yet: add the team as a regular entry.".to_string());
+    }
+    if rounds.len() as i32 >= number_of_qualifying_rounds {
+        return Err("Every qualifying round has already been drawn: a late entry would not play.".to_string());
+    }
+
+    // The team, its BYEs and its standing are saved together or not at all
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+
+    check_team_limit(&tx, &data.tournament_id, max_teams)?;
+
+    let team = insert_team(&tx, data)?;
+
+    if late_entry_policy == "creditedByes" {
+        for (round_id, _) in &rounds {
+            // The BYE takes the court number after the round's last game
+            let court_number: i32 = tx
+                .query_row(
+                    "SELECT COALESCE(MAX(court_number), 0) + 1 FROM qualifying_games WHERE round_id = ?1",
+                    params![round_id],
+                    |row| row.get(0),
+                )
+                .map_err(|e| e.to_string())?;
+
+            tx.execute(
+                r#"
+                INSERT INTO qualifying_games (id, round_id, court_number, team1_id, team2_id, is_bye, slot_number)
+                VALUES (?1, ?2, ?3, ?4, NULL, 1, 1)
+                "#,
+                params![Uuid::new_v4().to_string(), round_id, court_number, team.id],
+            )
+            .map_err(|e| e.to_string())?;
+
+            // A credited BYE counts as the team's BYE when the next one is handed out
+            tx.execute(
+                "INSERT INTO bye_history (id, tournament_id, team_id, round_id) VALUES (?1, ?2, ?3, ?4)",
+                params![Uuid::new_v4().to_string(), team.tournament_id, team.id, round_id],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+    }
+
+    // Give the team its place in the current standings; BYEs of rounds still
+    // in play count once those rounds complete
+    if rounds.iter().any(|(_, is_complete)| *is_complete) {
+        rebuild_standings(&tx, &team.tournament_id)?;
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(team)
+}
+
+/// Whether qualifying rounds have been generated for a tournament
+fn rounds_exist(conn: &rusqlite::Connection, tournament_id: &str) -> Result<bool, String> {
+    conn.query_row(
+        "SELECT COUNT(*) > 0 FROM qualifying_rounds WHERE tournament_id = ?1",
+        params![tournament_id],
+        |row| row.get(0),
+    )
+    .map_err(|e| e.to_string())
+}
+
+fn check_team_limit(conn: &rusqlite::Connection, tournament_id: &str, max_teams: Option<i32>) -> Result<(), String> {
     // Get current team count
     let current_team_count: i32 = conn
         .query_row(
             "SELECT COUNT(*) FROM teams WHERE tournament_id = ?1",
-            params![data.tournament_id],
+            params![tournament_id],
             |row| row.get(0),
         )
         .map_err(|e| e.to_string())?;
 
-    // Check if adding one more team would exceed the limit (2x courts)
-    let max_teams = number_of_courts * 2;
-    if current_team_count >= max_teams {
-        return Err(format!(
-            "Cannot add more teams. Maximum is {} teams ({} courts × 2).",
-            max_teams, number_of_courts
-        ));
+    // Check if adding one more team would exceed the limit
+    if let Some(max_teams) = max_teams {
+        if current_team_count >= max_teams {
+            return Err(format!(
+                "Cannot add more teams. Maximum is {} teams.",
+                max_teams
+            ));
+        }
     }
 
+    Ok(())
+}
+
+/// A protected seed is a positive number held by a single team of the tournament
+fn check_seed(
+    conn: &rusqlite::Connection,
+    tournament_id: &str,
+    team_id: Option<&str>,
+    seed: Option<i32>,
+) -> Result<(), String> {
+    let Some(seed) = seed else {
+        return Ok(());
+    };
+    if seed < 1 {
+        return Err("A seed is a number from 1 up.".to_string());
+    }
+
+    let holder: Option<String> = conn
+        .query_row(
+            "SELECT captain FROM teams WHERE tournament_id = ?1 AND seed = ?2 AND id != COALESCE(?3, '')",
+            params![tournament_id, seed, team_id],
+            |row| row.get(0),
+        )
+        .ok();
+
+    match holder {
+        Some(captain) => Err(format!("Seed {} is already given to {}.", seed, captain)),
+        None => Ok(()),
+    }
+}
+
+/// Inserts a team with an empty standing
+fn insert_team(conn: &rusqlite::Connection, data: CreateTeamData) -> Result<Team, String> {
+    check_seed(conn, &data.tournament_id, None, data.seed)?;
+
     let id = Uuid::new_v4().to_string();
     let now = Utc::now().to_rfc3339();
 
     conn.execute(
         r#"
-        INSERT INTO teams (id, tournament_id, captain, player2, player3, region, club, created_at)
-        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+        INSERT INTO teams (id, tournament_id, captain, player2, player3, region, club, seed, created_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
         "#,
         params![
             id,
@@ -117,6 +272,7 @@ pub fn create_team(db: State<Database>, data: CreateTeamData) -> Result<Team, St
             data.player3,
             data.region,
             data.club,
+            data.seed,
             now,
         ],
     )
@@ -141,7 +297,10 @@ pub fn create_team(db: State<Database>, data: CreateTeamData) -> Result<Team, St
         player3: data.player3,
         region: data.region,
         club: data.club,
+        seed: data.seed,
         created_at: now,
+        is_active: true,
+        withdrawn_at: None,
     };
 
     Ok(team)
@@ -151,6 +310,8 @@ pub fn create_team(db: State<Database>, data: CreateTeamData) -> Result<Team, St
 pub fn update_team(db: State<Database>, id: String, data: CreateTeamData) -> Result<(), String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
+    check_seed(&conn, &data.tournament_id, Some(&id), data.seed)?;
+
     conn.execute(
         r#"
         UPDATE teams SET
@@ -158,10 +319,11 @@ pub fn update_team(db: State<Database>, id: String, data: CreateTeamData) -> Res
             player2 = ?3,
             player3 = ?4,
             region = ?5,
-            club = ?6
+            club = ?6,
+            seed = ?7
         WHERE id = ?1
         "#,
-        params![id, data.captain, data.player2, data.player3, data.region, data.club],
+        params![id, data.captain, data.player2, data.player3, data.region, data.club, data.seed],
     )
     .map_err(|e| e.to_string())?;
 
@@ -191,7 +353,7 @@ pub fn delete_team(db: State<Database>, id: String) -> Result<(), String> {
         .map_err(|e| e.to_string())?;
 
     if rounds_exist > 0 {
-        return Err("Cannot delete teams after qualifying rounds have been generated. Delete all rounds first.".to_string());
+        return Err("Cannot delete teams after qualifying rounds have been generated. Withdraw the team instead, or delete all rounds first.".to_string());
     }
 
     conn.execute("DELETE FROM teams WHERE id = ?1", params![id])
@@ -200,6 +362,56 @@ pub fn delete_team(db: State<Database>, id: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Withdraws a team that leaves mid-event. It is left out of every later
+/// draw, its unplayed qualifying games are forfeited and its bracket matches
+/// become walkovers for its opponents. Its results so far stand.
+#[tauri::command]
+pub fn withdraw_team(db: State<Database>, id: String) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let (tournament_id, is_active): (String, bool) = conn
+        .query_row(
+            "SELECT tournament_id, is_active FROM teams WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get(0)?, row.get::<_, i32>(1)? != 0)),
+        )
+        .map_err(|e| e.to_string())?;
+
+    if !is_active {
+        return Err("This team has already withdrawn.".to_string());
+    }
+
+    // The withdrawal, its forfeits and its walkovers are saved together or not at all
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+
+    tx.execute(
+        "UPDATE teams SET is_active = 0, withdrawn_at = ?2 WHERE id = ?1",
+        params![id, Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    forfeit_withdrawn_games(&tx, &tournament_id)?;
+
+    let mut stmt = tx
+        .prepare("SELECT id FROM brackets WHERE tournament_id = ?1")
+        .map_err(|e| e.to_string())?;
+
+    let bracket_ids: Vec<String> = stmt
+        .query_map(params![tournament_id], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    drop(stmt);
+
+    for bracket_id in bracket_ids {
+        apply_walkovers(&tx, &bracket_id)?;
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
 #[tauri::command]
 pub fn import_teams(
     db: State<Database>,
@@ -208,15 +420,23 @@ pub fn import_teams(
 ) -> Result<i32, String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
-    // Get tournament's number of courts
-    let number_of_courts: i32 = conn
+    // Get the tournament's team limit, if the director set one
+    let (max_teams, pairing_method): (Option<i32>, String) = conn
         .query_row(
-            "SELECT number_of_courts FROM tournaments WHERE id = ?1",
+            "SELECT max_teams, pairing_method FROM tournaments WHERE id = ?1",
             params![tournament_id],
-            |row| row.get(0),
+            |row| Ok((row.get(0)?, row.get(1)?)),
         )
         .map_err(|e| e.to_string())?;
 
+    if pairing_method == "melee" {
+        return Err("A mêlée has no fixed teams: enter the players individually.".to_string());
+    }
+
+    if rounds_exist(&conn, &tournament_id)? {
+        return Err("Qualifying has already started: register late teams one by one as late entries.".to_string());
+    }
+
     // Get current team count
     let current_team_count: i32 = conn
         .query_row(
@@ -226,15 +446,27 @@ pub fn import_teams(
         )
         .map_err(|e| e.to_string())?;
 
-    // Check if importing all teams would exceed the limit (2x courts)
-    let max_teams = number_of_courts * 2;
+    // Check if importing all teams would exceed the limit
     let teams_to_import = teams.len() as i32;
-    if current_team_count + teams_to_import > max_teams {
-        let available_slots = max_teams - current_team_count;
-        return Err(format!(
-            "Cannot import {} teams. Maximum is {} teams ({} courts × 2). Currently have {} teams, only {} slots available.",
-            teams_to_import, max_teams, number_of_courts, current_team_count, available_slots
-        ));
+    if let Some(max_teams) = max_teams {
+        if current_team_count + teams_to_import > max_teams {
+            let available_slots = (max_teams - current_team_count).max(0);
+            return Err(format!(
+                "Cannot import {} teams. Maximum is {} teams. Currently have {} teams, only {} slots available.",
+                teams_to_import, max_teams, current_team_count, available_slots
+            ));
+        }
+    }
+
+    // Seeds are checked first so that a clash doesn't leave half the teams imported
+    let mut seeds = HashSet::new();
+    for team_data in &teams {
+        check_seed(&conn, &tournament_id, None, team_data.seed)?;
+        if let Some(seed) = team_data.seed {
+            if !seeds.insert(seed) {
+                return Err(format!("Seed {} is given to more than one team.", seed));
+            }
+        }
     }
 
     let now = Utc::now().to_rfc3339();
@@ -245,8 +477,8 @@ pub fn import_teams(
 
         conn.execute(
             r#"
-            INSERT INTO teams (id, tournament_id, captain, player2, player3, region, club, created_at)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            INSERT INTO teams (id, tournament_id, captain, player2, player3, region, club, seed, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
             "#,
             params![
                 id,
@@ -256,6 +488,7 @@ pub fn import_teams(
                 team_data.player3,
                 team_data.region,
                 team_data.club,
+                team_data.seed,
                 now,
             ],
         )
@@ -285,10 +518,14 @@ pub fn get_standings(db: State<Database>, tournament_id: String) -> Result<Vec<T
     let mut stmt = conn
         .prepare(
             r#"
-            SELECT id, tournament_id, team_id, wins, losses, points_for, points_against, differential, buchholz_score, fine_buchholz_score, point_quotient, is_eliminated, rank
-            FROM team_standings
-            WHERE tournament_id = ?1
-            ORDER BY rank ASC, wins DESC, buchholz_score DESC, fine_buchholz_score DESC, differential DESC
+            SELECT ts.id, ts.tournament_id, ts.team_id, ts.wins, ts.losses, ts.points_for, ts.points_against, ts.differential,
+                   ts.buchholz_score, ts.fine_buchholz_score, ts.point_quotient, ts.is_eliminated, ts.rank,
+                   pa.pool_number, pa.pool_rank, ts.median_buchholz_score, ts.cut_buchholz_score,
+                   ts.sonneborn_berger_score, ts.head_to_head_wins, ts.is_tied
+            FROM team_standings ts
+            LEFT JOIN pool_assignments pa ON pa.team_id = ts.team_id AND pa.tournament_id = ts.tournament_id
+            WHERE ts.tournament_id = ?1
+            ORDER BY ts.rank ASC, ts.team_id ASC
             "#,
         )
         .map_err(|e| e.to_string())?;
@@ -305,10 +542,17 @@ pub fn get_standings(db: State<Database>, tournament_id: String) -> Result<Vec<T
                 points_against: row.get(6)?,
                 differential: row.get(7)?,
                 buchholz_score: row.get(8)?,
+                median_buchholz_score: row.get(15)?,
+                cut_buchholz_score: row.get(16)?,
                 fine_buchholz_score: row.get(9)?,
+                sonneborn_berger_score: row.get(17)?,
                 point_quotient: row.get(10)?,
+                head_to_head_wins: row.get(18)?,
                 is_eliminated: row.get::<_, i32>(11)? != 0,
                 rank: row.get(12)?,
+                is_tied: row.get::<_, i32>(19)? != 0,
+                pool_number: row.get(13)?,
+                pool_rank: row.get(14)?,
             })
         })
         .map_err(|e| e.to_string())?
@@ -355,7 +599,7 @@ pub fn delete_all_teams(db: State<Database>, tournament_id: String) -> Result<()
 pub fn get_team_by_id(conn: &rusqlite::Connection, id: &str) -> Result<Option<Team>, String> {
     match conn.query_row(
         r#"
-        SELECT id, tournament_id, captain, player2, player3, region, club, created_at
+        SELECT id, tournament_id, captain, player2, player3, region, club, created_at, is_active, withdrawn_at, seed
         FROM teams
         WHERE id = ?1
         "#,
@@ -370,6 +614,9 @@ pub fn get_team_by_id(conn: &rusqlite::Connection, id: &str) -> Result<Option<Te
                 region: row.get(5)?,
                 club: row.get(6)?,
                 created_at: row.get(7)?,
+                is_active: row.get::<_, i32>(8)? != 0,
+                withdrawn_at: row.get(9)?,
+                seed: row.get(10)?,
             })
         },
     ) {
@@ -1,5 +1,8 @@
+use crate::commands::courts::sync_courts;
 use crate::db::Database;
-use crate::models::{CreateTournamentData, Tournament, Umpire};
+use crate::models::{ByeScoring, CreateTournamentData, ForfeitScoring, GameRules, Tournament, Umpire};
+use crate::commands::ranking::{join_tiebreakers, tiebreakers_or_default};
+use crate::pairing::avoidance::AvoidancePolicy;
 use chrono::Utc;
 use rusqlite::params;
 use tauri::State;
@@ -15,7 +18,11 @@ pub fn get_tournaments(db: State<Database>) -> Result<Vec<Tournament>, String> {
             SELECT id, name, team_composition, tournament_type, start_date, end_date,
                    director, head_umpire, format, number_of_courts,
                    number_of_qualifying_rounds, has_consolante, advance_all, advance_count, bracket_size,
-                   pairing_method, region_avoidance, created_at, updated_at
+                   pairing_method, avoidance_policy, created_at, updated_at,
+                   bye_winner_score, bye_loser_score, bye_counts_in_tiebreaks,
+                   forfeit_winner_score, forfeit_loser_score, max_teams, avoidance_rounds,
+                   tiebreakers, late_entry_policy, target_score, time_limit_minutes, extra_ends, first_round_draw,
+                   bracket_seeding
             FROM tournaments
             ORDER BY created_at DESC
             "#,
@@ -41,7 +48,21 @@ pub fn get_tournaments(db: State<Database>) -> Result<Vec<Tournament>, String> {
                 advance_count: row.get(13)?,
                 bracket_size: row.get(14)?,
                 pairing_method: row.get(15)?,
-                region_avoidance: row.get::<_, i32>(16)? != 0,
+                avoidance_policy: row.get(16)?,
+                avoidance_rounds: row.get(25)?,
+                tiebreakers: chain_names(&row.get::<_, String>(26)?, &row.get::<_, String>(15)?),
+                max_teams: row.get(24)?,
+                bye_winner_score: row.get(19)?,
+                bye_loser_score: row.get(20)?,
+                bye_counts_in_tiebreaks: row.get::<_, i32>(21)? != 0,
+                forfeit_winner_score: row.get(22)?,
+                forfeit_loser_score: row.get(23)?,
+                late_entry_policy: row.get(27)?,
+                target_score: row.get(28)?,
+                time_limit_minutes: row.get(29)?,
+                extra_ends: row.get(30)?,
+                first_round_draw: row.get(31)?,
+                bracket_seeding: row.get(32)?,
                 created_at: row.get(17)?,
                 updated_at: row.get(18)?,
             })
@@ -63,7 +84,11 @@ pub fn get_tournament(db: State<Database>, id: String) -> Result<Tournament, Str
             SELECT id, name, team_composition, tournament_type, start_date, end_date,
                    director, head_umpire, format, number_of_courts,
                    number_of_qualifying_rounds, has_consolante, advance_all, advance_count, bracket_size,
-                   pairing_method, region_avoidance, created_at, updated_at
+                   pairing_method, avoidance_policy, created_at, updated_at,
+                   bye_winner_score, bye_loser_score, bye_counts_in_tiebreaks,
+                   forfeit_winner_score, forfeit_loser_score, max_teams, avoidance_rounds,
+                   tiebreakers, late_entry_policy, target_score, time_limit_minutes, extra_ends, first_round_draw,
+                   bracket_seeding
             FROM tournaments
             WHERE id = ?1
             "#,
@@ -86,7 +111,21 @@ pub fn get_tournament(db: State<Database>, id: String) -> Result<Tournament, Str
                     advance_count: row.get(13)?,
                     bracket_size: row.get(14)?,
                     pairing_method: row.get(15)?,
-                    region_avoidance: row.get::<_, i32>(16)? != 0,
+                    avoidance_policy: row.get(16)?,
+                    avoidance_rounds: row.get(25)?,
+                    tiebreakers: chain_names(&row.get::<_, String>(26)?, &row.get::<_, String>(15)?),
+                    max_teams: row.get(24)?,
+                    bye_winner_score: row.get(19)?,
+                    bye_loser_score: row.get(20)?,
+                    bye_counts_in_tiebreaks: row.get::<_, i32>(21)? != 0,
+                    forfeit_winner_score: row.get(22)?,
+                    forfeit_loser_score: row.get(23)?,
+                    late_entry_policy: row.get(27)?,
+                    target_score: row.get(28)?,
+                    time_limit_minutes: row.get(29)?,
+                    extra_ends: row.get(30)?,
+                    first_round_draw: row.get(31)?,
+                    bracket_seeding: row.get(32)?,
                     created_at: row.get(17)?,
                     updated_at: row.get(18)?,
                 })
@@ -104,17 +143,39 @@ pub fn create_tournament(
 ) -> Result<Tournament, String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
+    AvoidancePolicy::parse(&data.avoidance_policy)?;
+    let tiebreakers = match &data.tiebreakers {
+        Some(names) => join_tiebreakers(names)?,
+        None => chain_names("", &data.pairing_method).join(","),
+    };
+
     let id = Uuid::new_v4().to_string();
     let now = Utc::now().to_rfc3339();
 
+    // Default BYE is a 13-7 win (FPUSA rules), default forfeit a 13-0 win
+    let bye_winner_score = data.bye_winner_score.unwrap_or(13);
+    let bye_loser_score = data.bye_loser_score.unwrap_or(7);
+    let bye_counts_in_tiebreaks = data.bye_counts_in_tiebreaks.unwrap_or(true);
+    let forfeit_winner_score = data.forfeit_winner_score.unwrap_or(13);
+    let forfeit_loser_score = data.forfeit_loser_score.unwrap_or(0);
+    let late_entry_policy = data.late_entry_policy.unwrap_or_else(|| "zeroWins".to_string());
+    let target_score = data.target_score.unwrap_or(13);
+    let extra_ends = data.extra_ends.unwrap_or(1);
+    let first_round_draw = data.first_round_draw.unwrap_or_else(|| "random".to_string());
+    let bracket_seeding = data.bracket_seeding.unwrap_or_else(|| "classic".to_string());
+
     conn.execute(
         r#"
         INSERT INTO tournaments (
             id, name, team_composition, tournament_type, start_date, end_date,
             director, head_umpire, format, day_type, number_of_courts,
             number_of_qualifying_rounds, has_consolante, advance_all, advance_count, bracket_size,
-            pairing_method, region_avoidance, created_at, updated_at
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 'single', ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)
+            pairing_method, avoidance_policy, created_at, updated_at,
+            bye_winner_score, bye_loser_score, bye_counts_in_tiebreaks,
+            forfeit_winner_score, forfeit_loser_score, max_teams, avoidance_rounds, tiebreakers,
+            late_entry_policy, target_score, time_limit_minutes, extra_ends, first_round_draw, bracket_seeding
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 'single', ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19,
+                  ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33)
         "#,
         params![
             id,
@@ -133,13 +194,29 @@ pub fn create_tournament(
             data.advance_count,
             data.bracket_size,
             data.pairing_method,
-            if data.region_avoidance { 1 } else { 0 },
+            data.avoidance_policy,
             now,
             now,
+            bye_winner_score,
+            bye_loser_score,
+            if bye_counts_in_tiebreaks { 1 } else { 0 },
+            forfeit_winner_score,
+            forfeit_loser_score,
+            data.max_teams,
+            data.avoidance_rounds,
+            tiebreakers,
+            late_entry_policy,
+            target_score,
+            data.time_limit_minutes,
+            extra_ends,
+            first_round_draw,
+            bracket_seeding,
         ],
     )
     .map_err(|e| e.to_string())?;
 
+    sync_courts(&conn, &id)?;
+
     // Insert additional umpires if provided
     if let Some(umpires) = data.additional_umpires {
         for umpire_name in umpires {
@@ -171,7 +248,21 @@ pub fn create_tournament(
         advance_count: data.advance_count,
         bracket_size: data.bracket_size,
         pairing_method: data.pairing_method,
-        region_avoidance: data.region_avoidance,
+        avoidance_policy: data.avoidance_policy,
+        avoidance_rounds: data.avoidance_rounds,
+        tiebreakers: tiebreakers.split(',').map(String::from).collect(),
+        max_teams: data.max_teams,
+        bye_winner_score,
+        bye_loser_score,
+        bye_counts_in_tiebreaks,
+        forfeit_winner_score,
+        forfeit_loser_score,
+        late_entry_policy,
+        target_score,
+        time_limit_minutes: data.time_limit_minutes,
+        extra_ends,
+        first_round_draw,
+        bracket_seeding,
         created_at: now.clone(),
         updated_at: now,
     };
@@ -187,6 +278,13 @@ pub fn update_tournament(
 ) -> Result<(), String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
+    AvoidancePolicy::parse(&data.avoidance_policy)?;
+    let tiebreakers = data
+        .tiebreakers
+        .as_deref()
+        .map(join_tiebreakers)
+        .transpose()?;
+
     // Check if qualifying rounds have been generated
     let rounds_exist: i32 = conn
         .query_row(
@@ -237,8 +335,22 @@ pub fn update_tournament(
             advance_count = ?14,
             bracket_size = ?15,
             pairing_method = ?16,
-            region_avoidance = ?17,
-            updated_at = ?18
+            avoidance_policy = ?17,
+            updated_at = ?18,
+            bye_winner_score = COALESCE(?19, bye_winner_score),
+            bye_loser_score = COALESCE(?20, bye_loser_score),
+            bye_counts_in_tiebreaks = COALESCE(?21, bye_counts_in_tiebreaks),
+            forfeit_winner_score = COALESCE(?22, forfeit_winner_score),
+            forfeit_loser_score = COALESCE(?23, forfeit_loser_score),
+            max_teams = NULLIF(COALESCE(?24, max_teams), 0),
+            avoidance_rounds = NULLIF(COALESCE(?25, avoidance_rounds), 0),
+            tiebreakers = COALESCE(?26, tiebreakers),
+            late_entry_policy = COALESCE(?27, late_entry_policy),
+            target_score = COALESCE(?28, target_score),
+            time_limit_minutes = NULLIF(COALESCE(?29, time_limit_minutes), 0),
+            extra_ends = COALESCE(?30, extra_ends),
+            first_round_draw = COALESCE(?31, first_round_draw),
+            bracket_seeding = COALESCE(?32, bracket_seeding)
         WHERE id = ?1
         "#,
         params![
@@ -258,12 +370,28 @@ pub fn update_tournament(
             data.advance_count,
             data.bracket_size,
             data.pairing_method,
-            if data.region_avoidance { 1 } else { 0 },
+            data.avoidance_policy,
             now,
+            data.bye_winner_score,
+            data.bye_loser_score,
+            data.bye_counts_in_tiebreaks.map(|b| if b { 1 } else { 0 }),
+            data.forfeit_winner_score,
+            data.forfeit_loser_score,
+            data.max_teams,
+            data.avoidance_rounds,
+            tiebreakers,
+            data.late_entry_policy,
+            data.target_score,
+            data.time_limit_minutes,
+            data.extra_ends,
+            data.first_round_draw,
+            data.bracket_seeding,
         ],
     )
     .map_err(|e| e.to_string())?;
 
+    sync_courts(&conn, &id)?;
+
     // Update additional umpires
     conn.execute("DELETE FROM umpires WHERE tournament_id = ?1", params![id])
         .map_err(|e| e.to_string())?;
@@ -316,3 +444,57 @@ pub fn get_umpires(db: State<Database>, tournament_id: String) -> Result<Vec<Ump
 
     Ok(umpires)
 }
+
+pub fn get_bye_scoring(conn: &rusqlite::Connection, tournament_id: &str) -> Result<ByeScoring, String> {
+    conn.query_row(
+        "SELECT bye_winner_score, bye_loser_score, bye_counts_in_tiebreaks FROM tournaments WHERE id = ?1",
+        params![tournament_id],
+        |row| {
+            Ok(ByeScoring {
+                winner_score: row.get(0)?,
+                loser_score: row.get(1)?,
+                counts_in_tiebreaks: row.get::<_, i32>(2)? != 0,
+            })
+        },
+    )
+    .map_err(|e| e.to_string())
+}
+
+pub fn get_forfeit_scoring(conn: &rusqlite::Connection, tournament_id: &str) -> Result<ForfeitScoring, String> {
+    conn.query_row(
+        "SELECT forfeit_winner_score, forfeit_loser_score FROM tournaments WHERE id = ?1",
+        params![tournament_id],
+        |row| {
+            Ok(ForfeitScoring {
+                winner_score: row.get(0)?,
+                loser_score: row.get(1)?,
+            })
+        },
+    )
+    .map_err(|e| e.to_string())
+}
+
+pub fn get_game_rules(conn: &rusqlite::Connection, tournament_id: &str) -> Result<GameRules, String> {
+    conn.query_row(
+        "SELECT target_score, time_limit_minutes, extra_ends FROM tournaments WHERE id = ?1",
+        params![tournament_id],
+        |row| {
+            let time_limit_minutes: Option<i32> = row.get(1)?;
+            Ok(GameRules {
+                target_score: row.get(0)?,
+                time_limited: time_limit_minutes.is_some(),
+                time_limit_minutes,
+                extra_ends: row.get(2)?,
+            })
+        },
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Names of the tiebreakers a tournament ranks with, as sent to the frontend
+fn chain_names(stored: &str, pairing_method: &str) -> Vec<String> {
+    tiebreakers_or_default(stored, pairing_method)
+        .iter()
+        .map(|t| t.as_str().to_string())
+        .collect()
+}
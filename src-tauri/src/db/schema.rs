@@ -21,10 +21,24 @@ pub fn create_tables(conn: &Connection) -> Result<()> {
             advance_all INTEGER NOT NULL DEFAULT 1,
             advance_count INTEGER,
             bracket_size INTEGER NOT NULL DEFAULT 16,
-            pairing_method TEXT NOT NULL CHECK (pairing_method IN ('swiss', 'swissHotel', 'roundRobin', 'poolPlay')),
-            region_avoidance INTEGER NOT NULL DEFAULT 0,
+            pairing_method TEXT NOT NULL CHECK (pairing_method IN ('swiss', 'swissHotel', 'roundRobin', 'poolPlay', 'poules', 'melee')),
+            avoidance_policy TEXT NOT NULL DEFAULT 'none' CHECK (avoidance_policy IN ('none', 'region', 'club', 'regionFirst', 'clubFirst')),
             created_at TEXT NOT NULL,
-            updated_at TEXT NOT NULL
+            updated_at TEXT NOT NULL,
+            bye_winner_score INTEGER NOT NULL DEFAULT 13,
+            bye_loser_score INTEGER NOT NULL DEFAULT 7,
+            bye_counts_in_tiebreaks INTEGER NOT NULL DEFAULT 1,
+            forfeit_winner_score INTEGER NOT NULL DEFAULT 13,
+            forfeit_loser_score INTEGER NOT NULL DEFAULT 0,
+            max_teams INTEGER,
+            avoidance_rounds INTEGER,
+            tiebreakers TEXT NOT NULL DEFAULT '',
+            late_entry_policy TEXT NOT NULL DEFAULT 'zeroWins' CHECK (late_entry_policy IN ('zeroWins', 'creditedByes')),
+            target_score INTEGER NOT NULL DEFAULT 13 CHECK (target_score BETWEEN 1 AND 13),
+            time_limit_minutes INTEGER,
+            extra_ends INTEGER NOT NULL DEFAULT 1 CHECK (extra_ends >= 0),
+            first_round_draw TEXT NOT NULL DEFAULT 'random' CHECK (first_round_draw IN ('random', 'seeded')),
+            bracket_seeding TEXT NOT NULL DEFAULT 'classic' CHECK (bracket_seeding IN ('classic', 'standard', 'random'))
         );
 
         -- Additional umpires (one-to-many with tournaments)
@@ -35,6 +49,18 @@ pub fn create_tables(conn: &Connection) -> Result<()> {
             FOREIGN KEY (tournament_id) REFERENCES tournaments(id) ON DELETE CASCADE
         );
 
+        -- Courts (terrains) of a tournament, numbered 1..number_of_courts
+        CREATE TABLE IF NOT EXISTS courts (
+            id TEXT PRIMARY KEY,
+            tournament_id TEXT NOT NULL,
+            court_number INTEGER NOT NULL,
+            label TEXT,
+            is_active INTEGER NOT NULL DEFAULT 1,
+            is_reserved INTEGER NOT NULL DEFAULT 0,
+            FOREIGN KEY (tournament_id) REFERENCES tournaments(id) ON DELETE CASCADE,
+            UNIQUE(tournament_id, court_number)
+        );
+
         -- Teams table
         CREATE TABLE IF NOT EXISTS teams (
             id TEXT PRIMARY KEY,
@@ -44,6 +70,9 @@ pub fn create_tables(conn: &Connection) -> Result<()> {
             player3 TEXT,
             region TEXT,
             club TEXT,
+            seed INTEGER CHECK (seed >= 1),
+            is_active INTEGER NOT NULL DEFAULT 1,
+            withdrawn_at TEXT,
             created_at TEXT NOT NULL,
             FOREIGN KEY (tournament_id) REFERENCES tournaments(id) ON DELETE CASCADE
         );
@@ -54,6 +83,9 @@ pub fn create_tables(conn: &Connection) -> Result<()> {
             tournament_id TEXT NOT NULL,
             round_number INTEGER NOT NULL,
             is_complete INTEGER NOT NULL DEFAULT 0,
+            draw_method TEXT,
+            started_at TEXT,
+            ended_at TEXT,
             created_at TEXT NOT NULL,
             FOREIGN KEY (tournament_id) REFERENCES tournaments(id) ON DELETE CASCADE
         );
@@ -68,6 +100,9 @@ pub fn create_tables(conn: &Connection) -> Result<()> {
             team1_score INTEGER,
             team2_score INTEGER,
             is_bye INTEGER NOT NULL DEFAULT 0,
+            is_forfeit INTEGER NOT NULL DEFAULT 0,
+            slot_number INTEGER NOT NULL DEFAULT 1,
+            ended_at TEXT,
             FOREIGN KEY (round_id) REFERENCES qualifying_rounds(id) ON DELETE CASCADE,
             FOREIGN KEY (team1_id) REFERENCES teams(id) ON DELETE SET NULL,
             FOREIGN KEY (team2_id) REFERENCES teams(id) ON DELETE SET NULL
@@ -84,10 +119,15 @@ pub fn create_tables(conn: &Connection) -> Result<()> {
             points_against INTEGER NOT NULL DEFAULT 0,
             differential INTEGER NOT NULL DEFAULT 0,
             buchholz_score REAL NOT NULL DEFAULT 0,
+            median_buchholz_score REAL NOT NULL DEFAULT 0,
+            cut_buchholz_score REAL NOT NULL DEFAULT 0,
             fine_buchholz_score REAL NOT NULL DEFAULT 0,
+            sonneborn_berger_score REAL NOT NULL DEFAULT 0,
             point_quotient REAL NOT NULL DEFAULT 0,
+            head_to_head_wins INTEGER NOT NULL DEFAULT 0,
             is_eliminated INTEGER NOT NULL DEFAULT 0,
             rank INTEGER NOT NULL DEFAULT 0,
+            is_tied INTEGER NOT NULL DEFAULT 0,
             FOREIGN KEY (tournament_id) REFERENCES tournaments(id) ON DELETE CASCADE,
             FOREIGN KEY (team_id) REFERENCES teams(id) ON DELETE CASCADE,
             UNIQUE(tournament_id, team_id)
@@ -119,6 +159,10 @@ pub fn create_tables(conn: &Connection) -> Result<()> {
             winner_id TEXT,
             next_match_id TEXT,
             is_bye INTEGER NOT NULL DEFAULT 0,
+            is_forfeit INTEGER NOT NULL DEFAULT 0,
+            slot_number INTEGER NOT NULL DEFAULT 1,
+            started_at TEXT,
+            ended_at TEXT,
             FOREIGN KEY (bracket_id) REFERENCES brackets(id) ON DELETE CASCADE,
             FOREIGN KEY (team1_id) REFERENCES teams(id) ON DELETE SET NULL,
             FOREIGN KEY (team2_id) REFERENCES teams(id) ON DELETE SET NULL,
@@ -151,7 +195,163 @@ pub fn create_tables(conn: &Connection) -> Result<()> {
             FOREIGN KEY (round_id) REFERENCES qualifying_rounds(id) ON DELETE CASCADE
         );
 
+        -- Float history (Swiss teams paired outside their score group)
+        CREATE TABLE IF NOT EXISTS pairing_floats (
+            id TEXT PRIMARY KEY,
+            tournament_id TEXT NOT NULL,
+            team_id TEXT NOT NULL,
+            round_id TEXT NOT NULL,
+            direction TEXT NOT NULL CHECK (direction IN ('up', 'down')),
+            FOREIGN KEY (tournament_id) REFERENCES tournaments(id) ON DELETE CASCADE,
+            FOREIGN KEY (team_id) REFERENCES teams(id) ON DELETE CASCADE,
+            FOREIGN KEY (round_id) REFERENCES qualifying_rounds(id) ON DELETE CASCADE
+        );
+
+        -- BYE history (one BYE per team until every team has had one)
+        CREATE TABLE IF NOT EXISTS bye_history (
+            id TEXT PRIMARY KEY,
+            tournament_id TEXT NOT NULL,
+            team_id TEXT NOT NULL,
+            round_id TEXT NOT NULL,
+            FOREIGN KEY (tournament_id) REFERENCES tournaments(id) ON DELETE CASCADE,
+            FOREIGN KEY (team_id) REFERENCES teams(id) ON DELETE CASCADE,
+            FOREIGN KEY (round_id) REFERENCES qualifying_rounds(id) ON DELETE CASCADE
+        );
+
+        -- Poule membership (pools of 4 or 3 drawn at the start of qualifying)
+        CREATE TABLE IF NOT EXISTS pool_assignments (
+            id TEXT PRIMARY KEY,
+            tournament_id TEXT NOT NULL,
+            team_id TEXT NOT NULL,
+            pool_number INTEGER NOT NULL,
+            position INTEGER NOT NULL,
+            pool_rank INTEGER,
+            FOREIGN KEY (tournament_id) REFERENCES tournaments(id) ON DELETE CASCADE,
+            FOREIGN KEY (team_id) REFERENCES teams(id) ON DELETE CASCADE,
+            UNIQUE(tournament_id, team_id)
+        );
+
+        -- Individual players of a mêlée, drawn into new teams every round
+        CREATE TABLE IF NOT EXISTS players (
+            id TEXT PRIMARY KEY,
+            tournament_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            club TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (tournament_id) REFERENCES tournaments(id) ON DELETE CASCADE
+        );
+
+        -- Player standings (a player earns the results of every team they played in)
+        CREATE TABLE IF NOT EXISTS player_standings (
+            id TEXT PRIMARY KEY,
+            tournament_id TEXT NOT NULL,
+            player_id TEXT NOT NULL,
+            wins INTEGER NOT NULL DEFAULT 0,
+            losses INTEGER NOT NULL DEFAULT 0,
+            points_for INTEGER NOT NULL DEFAULT 0,
+            points_against INTEGER NOT NULL DEFAULT 0,
+            differential INTEGER NOT NULL DEFAULT 0,
+            rank INTEGER NOT NULL DEFAULT 0,
+            FOREIGN KEY (tournament_id) REFERENCES tournaments(id) ON DELETE CASCADE,
+            FOREIGN KEY (player_id) REFERENCES players(id) ON DELETE CASCADE,
+            UNIQUE(tournament_id, player_id)
+        );
+
+        -- Teams formed for one mêlée round
+        CREATE TABLE IF NOT EXISTS melee_teams (
+            id TEXT PRIMARY KEY,
+            round_id TEXT NOT NULL,
+            FOREIGN KEY (round_id) REFERENCES qualifying_rounds(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS melee_team_players (
+            team_id TEXT NOT NULL,
+            player_id TEXT NOT NULL,
+            PRIMARY KEY (team_id, player_id),
+            FOREIGN KEY (team_id) REFERENCES melee_teams(id) ON DELETE CASCADE,
+            FOREIGN KEY (player_id) REFERENCES players(id) ON DELETE CASCADE
+        );
+
+        -- Games of a mêlée round; a player sitting the round out has a BYE game without a court
+        CREATE TABLE IF NOT EXISTS melee_games (
+            id TEXT PRIMARY KEY,
+            round_id TEXT NOT NULL,
+            court_number INTEGER,
+            slot_number INTEGER NOT NULL DEFAULT 1,
+            team1_id TEXT NOT NULL,
+            team2_id TEXT,
+            team1_score INTEGER,
+            team2_score INTEGER,
+            is_bye INTEGER NOT NULL DEFAULT 0,
+            FOREIGN KEY (round_id) REFERENCES qualifying_rounds(id) ON DELETE CASCADE,
+            FOREIGN KEY (team1_id) REFERENCES melee_teams(id) ON DELETE CASCADE,
+            FOREIGN KEY (team2_id) REFERENCES melee_teams(id) ON DELETE CASCADE
+        );
+
+        -- Scores corrected after their round was completed, with the reason given
+        CREATE TABLE IF NOT EXISTS score_corrections (
+            id TEXT PRIMARY KEY,
+            tournament_id TEXT NOT NULL,
+            game_id TEXT NOT NULL,
+            old_team1_score INTEGER,
+            old_team2_score INTEGER,
+            new_team1_score INTEGER NOT NULL,
+            new_team2_score INTEGER NOT NULL,
+            reason TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (tournament_id) REFERENCES tournaments(id) ON DELETE CASCADE,
+            FOREIGN KEY (game_id) REFERENCES qualifying_games(id) ON DELETE CASCADE
+        );
+
+        -- Changes made by hand to a round's pairings or courts after the draw
+        CREATE TABLE IF NOT EXISTS pairing_overrides (
+            id TEXT PRIMARY KEY,
+            tournament_id TEXT NOT NULL,
+            round_id TEXT NOT NULL,
+            kind TEXT NOT NULL CHECK (kind IN ('swap', 'court')),
+            game1_id TEXT NOT NULL,
+            game2_id TEXT,
+            team1_id TEXT,
+            team2_id TEXT,
+            old_court INTEGER,
+            new_court INTEGER,
+            reason TEXT,
+            warnings TEXT NOT NULL DEFAULT '[]',
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (tournament_id) REFERENCES tournaments(id) ON DELETE CASCADE,
+            FOREIGN KEY (round_id) REFERENCES qualifying_rounds(id) ON DELETE CASCADE
+        );
+
+        -- End-by-end scores of a qualifying game or a bracket match
+        CREATE TABLE IF NOT EXISTS game_ends (
+            id TEXT PRIMARY KEY,
+            qualifying_game_id TEXT,
+            bracket_match_id TEXT,
+            end_number INTEGER NOT NULL,
+            team1_points INTEGER NOT NULL DEFAULT 0 CHECK (team1_points BETWEEN 0 AND 6),
+            team2_points INTEGER NOT NULL DEFAULT 0 CHECK (team2_points BETWEEN 0 AND 6),
+            recorded_at TEXT NOT NULL,
+            FOREIGN KEY (qualifying_game_id) REFERENCES qualifying_games(id) ON DELETE CASCADE,
+            FOREIGN KEY (bracket_match_id) REFERENCES bracket_matches(id) ON DELETE CASCADE,
+            CHECK ((qualifying_game_id IS NULL) != (bracket_match_id IS NULL)),
+            CHECK (team1_points = 0 OR team2_points = 0)
+        );
+
+        -- Every random draw with its seed and inputs, so it can be replayed
+        CREATE TABLE IF NOT EXISTS draws (
+            id TEXT PRIMARY KEY,
+            tournament_id TEXT NOT NULL,
+            kind TEXT NOT NULL CHECK(kind IN ('round', 'bracket', 'tiebreak')),
+            reference_id TEXT,
+            seed TEXT NOT NULL,
+            inputs TEXT NOT NULL,
+            result TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (tournament_id) REFERENCES tournaments(id) ON DELETE CASCADE
+        );
+
         -- Create indexes for better query performance
+        CREATE INDEX IF NOT EXISTS idx_courts_tournament ON courts(tournament_id);
         CREATE INDEX IF NOT EXISTS idx_teams_tournament ON teams(tournament_id);
         CREATE INDEX IF NOT EXISTS idx_qualifying_rounds_tournament ON qualifying_rounds(tournament_id);
         CREATE INDEX IF NOT EXISTS idx_qualifying_games_round ON qualifying_games(round_id);
@@ -160,6 +360,18 @@ pub fn create_tables(conn: &Connection) -> Result<()> {
         CREATE INDEX IF NOT EXISTS idx_bracket_matches_bracket ON bracket_matches(bracket_id);
         CREATE INDEX IF NOT EXISTS idx_pairing_history_tournament ON pairing_history(tournament_id);
         CREATE INDEX IF NOT EXISTS idx_court_history_tournament ON court_history(tournament_id);
+        CREATE INDEX IF NOT EXISTS idx_pairing_floats_tournament ON pairing_floats(tournament_id);
+        CREATE INDEX IF NOT EXISTS idx_bye_history_tournament ON bye_history(tournament_id);
+        CREATE INDEX IF NOT EXISTS idx_pool_assignments_tournament ON pool_assignments(tournament_id);
+        CREATE INDEX IF NOT EXISTS idx_draws_tournament ON draws(tournament_id);
+        CREATE INDEX IF NOT EXISTS idx_players_tournament ON players(tournament_id);
+        CREATE INDEX IF NOT EXISTS idx_player_standings_tournament ON player_standings(tournament_id);
+        CREATE INDEX IF NOT EXISTS idx_melee_teams_round ON melee_teams(round_id);
+        CREATE INDEX IF NOT EXISTS idx_melee_games_round ON melee_games(round_id);
+        CREATE INDEX IF NOT EXISTS idx_score_corrections_tournament ON score_corrections(tournament_id);
+        CREATE INDEX IF NOT EXISTS idx_game_ends_game ON game_ends(qualifying_game_id);
+        CREATE INDEX IF NOT EXISTS idx_game_ends_match ON game_ends(bracket_match_id);
+        CREATE INDEX IF NOT EXISTS idx_pairing_overrides_tournament ON pairing_overrides(tournament_id);
         "#,
     )?;
 
@@ -294,5 +506,175 @@ pub fn create_tables(conn: &Connection) -> Result<()> {
         ).ok();
     }
 
+    // Migration: BYE and forfeit scoring policy
+    add_column_if_missing(conn, "tournaments", "bye_winner_score", "INTEGER NOT NULL DEFAULT 13")?;
+    add_column_if_missing(conn, "tournaments", "bye_loser_score", "INTEGER NOT NULL DEFAULT 7")?;
+    add_column_if_missing(conn, "tournaments", "bye_counts_in_tiebreaks", "INTEGER NOT NULL DEFAULT 1")?;
+    add_column_if_missing(conn, "tournaments", "forfeit_winner_score", "INTEGER NOT NULL DEFAULT 13")?;
+    add_column_if_missing(conn, "tournaments", "forfeit_loser_score", "INTEGER NOT NULL DEFAULT 0")?;
+
+    // Migration: games played in waves when there are more games than courts
+    add_column_if_missing(conn, "tournaments", "max_teams", "INTEGER")?;
+    add_column_if_missing(conn, "qualifying_games", "slot_number", "INTEGER NOT NULL DEFAULT 1")?;
+    add_column_if_missing(conn, "bracket_matches", "slot_number", "INTEGER NOT NULL DEFAULT 1")?;
+
+    // Migration: Allow the 'poules' pairing method. The CHECK constraint can only be
+    // changed by recreating the table.
+    if !stored_table_sql(conn, "tournaments").contains("'poules'") {
+        recreate_table(conn, "tournaments", "'poolPlay')", "'poolPlay', 'poules')")?;
+    }
+
+    // Migration: region_avoidance becomes an avoidance policy covering clubs as well
+    add_column_if_missing(
+        conn,
+        "tournaments",
+        "avoidance_policy",
+        "TEXT NOT NULL DEFAULT 'none' CHECK (avoidance_policy IN ('none', 'region', 'club', 'regionFirst', 'clubFirst'))",
+    )?;
+    add_column_if_missing(conn, "tournaments", "avoidance_rounds", "INTEGER")?;
+
+    if column_exists(conn, "tournaments", "region_avoidance") {
+        conn.execute_batch(
+            r#"
+            UPDATE tournaments SET avoidance_policy = 'region' WHERE region_avoidance = 1;
+            ALTER TABLE tournaments DROP COLUMN region_avoidance;
+            "#,
+        )?;
+    }
+
+    // Migration: tiebreaker chain (empty means the default for the pairing method)
+    add_column_if_missing(conn, "tournaments", "tiebreakers", "TEXT NOT NULL DEFAULT ''")?;
+
+    // Migration: Allow the 'melee' pairing method
+    if !stored_table_sql(conn, "tournaments").contains("'melee'") {
+        recreate_table(conn, "tournaments", "'poules')", "'poules', 'melee')")?;
+    }
+
+    // Migration: withdrawn teams and forfeited games
+    add_column_if_missing(conn, "teams", "is_active", "INTEGER NOT NULL DEFAULT 1")?;
+    add_column_if_missing(conn, "teams", "withdrawn_at", "TEXT")?;
+    add_column_if_missing(conn, "qualifying_games", "is_forfeit", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "bracket_matches", "is_forfeit", "INTEGER NOT NULL DEFAULT 0")?;
+
+    // Migration: how a team registered after qualifying started enters the standings
+    add_column_if_missing(
+        conn,
+        "tournaments",
+        "late_entry_policy",
+        "TEXT NOT NULL DEFAULT 'zeroWins' CHECK (late_entry_policy IN ('zeroWins', 'creditedByes'))",
+    )?;
+
+    // Migration: the points a game is played to, and an optional time limit
+    add_column_if_missing(
+        conn,
+        "tournaments",
+        "target_score",
+        "INTEGER NOT NULL DEFAULT 13 CHECK (target_score BETWEEN 1 AND 13)",
+    )?;
+    add_column_if_missing(conn, "tournaments", "time_limit_minutes", "INTEGER")?;
+
+    // Migration: ends played after time is called, and round and match timing
+    add_column_if_missing(
+        conn,
+        "tournaments",
+        "extra_ends",
+        "INTEGER NOT NULL DEFAULT 1 CHECK (extra_ends >= 0)",
+    )?;
+    add_column_if_missing(conn, "qualifying_rounds", "started_at", "TEXT")?;
+    add_column_if_missing(conn, "qualifying_rounds", "ended_at", "TEXT")?;
+    add_column_if_missing(conn, "qualifying_games", "ended_at", "TEXT")?;
+    add_column_if_missing(conn, "bracket_matches", "started_at", "TEXT")?;
+    add_column_if_missing(conn, "bracket_matches", "ended_at", "TEXT")?;
+
+    // Migration: how the first Swiss round is drawn, and protected seeds
+    add_column_if_missing(
+        conn,
+        "tournaments",
+        "first_round_draw",
+        "TEXT NOT NULL DEFAULT 'random' CHECK (first_round_draw IN ('random', 'seeded'))",
+    )?;
+    add_column_if_missing(conn, "teams", "seed", "INTEGER CHECK (seed >= 1)")?;
+    add_column_if_missing(conn, "qualifying_rounds", "draw_method", "TEXT")?;
+
+    // Migration: median and cut-1 Buchholz, Sonneborn-Berger and head-to-head in the standings
+    add_column_if_missing(conn, "team_standings", "median_buchholz_score", "REAL NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "team_standings", "cut_buchholz_score", "REAL NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "team_standings", "sonneborn_berger_score", "REAL NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "team_standings", "head_to_head_wins", "INTEGER NOT NULL DEFAULT 0")?;
+
+    // Migration: teams still tied after every tiebreaker share their rank
+    add_column_if_missing(conn, "team_standings", "is_tied", "INTEGER NOT NULL DEFAULT 0")?;
+
+    // Migration: how the qualified teams are placed in the brackets
+    add_column_if_missing(
+        conn,
+        "tournaments",
+        "bracket_seeding",
+        "TEXT NOT NULL DEFAULT 'classic' CHECK (bracket_seeding IN ('classic', 'standard', 'random'))",
+    )?;
+
+    Ok(())
+}
+
+/// The stored CREATE TABLE statement of a table, empty if there is no such table
+fn stored_table_sql(conn: &Connection, table: &str) -> String {
+    conn.query_row(
+        "SELECT sql FROM sqlite_master WHERE type='table' AND name=?1",
+        [table],
+        |row| row.get(0),
+    )
+    .unwrap_or_default()
+}
+
+/// Recreates a table from its stored definition with `from` replaced by `to`,
+/// which is the only way to change a CHECK constraint in SQLite. Every column
+/// and row is kept. Foreign keys are off so the DROP doesn't cascade.
+fn recreate_table(conn: &Connection, table: &str, from: &str, to: &str) -> Result<()> {
+    let sql = stored_table_sql(conn, table);
+    let Some(columns_start) = sql.find('(') else {
+        return Ok(());
+    };
+    let new_table_sql = format!(
+        "CREATE TABLE {}_new {}",
+        table,
+        sql[columns_start..].replace(from, to)
+    );
+    conn.execute_batch(&format!(
+        r#"
+        PRAGMA foreign_keys = OFF;
+
+        {new_table_sql};
+        INSERT INTO {table}_new SELECT * FROM {table};
+
+        DROP TABLE {table};
+        ALTER TABLE {table}_new RENAME TO {table};
+
+        PRAGMA foreign_keys = ON;
+        "#,
+    ))
+}
+
+/// Whether a table already has a column
+fn column_exists(conn: &Connection, table: &str, column: &str) -> bool {
+    conn.query_row(
+        &format!(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('{}') WHERE name='{}'",
+            table, column
+        ),
+        [],
+        |row| row.get(0),
+    )
+    .unwrap_or(false)
+}
+
+/// Add a column to an existing table unless it is already there
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<()> {
+    if !column_exists(conn, table, column) {
+        conn.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            [],
+        )?;
+    }
+
     Ok(())
 }
@@ -1,6 +1,7 @@
 mod commands;
 mod db;
 mod models;
+mod pairing;
 
 use db::Database;
 use tauri::Manager;
@@ -26,12 +27,17 @@ pub fn run() {
             commands::update_tournament,
             commands::delete_tournament,
             commands::get_umpires,
+            // Court commands
+            commands::get_courts,
+            commands::update_court,
             // Team commands
             commands::get_teams,
             commands::get_team,
             commands::create_team,
             commands::update_team,
             commands::delete_team,
+            commands::withdraw_team,
+            commands::register_late_team,
             commands::import_teams,
             commands::delete_all_teams,
             commands::get_standings,
@@ -39,16 +45,48 @@ pub fn run() {
             commands::get_qualifying_rounds,
             commands::get_games_for_round,
             commands::generate_pairings,
+            commands::preview_pairings,
+            commands::accept_pairing_preview,
             commands::generate_all_qualifying_rounds,
             commands::delete_all_qualifying_rounds,
+            commands::delete_last_qualifying_round,
             commands::update_game_score,
             commands::complete_round,
+            commands::recalculate_standings,
+            commands::correct_game_score,
+            commands::get_score_corrections,
+            // Poule commands
+            commands::get_pools,
+            // Mêlée commands
+            commands::get_players,
+            commands::create_player,
+            commands::delete_player,
+            commands::get_player_standings,
+            commands::get_melee_games,
+            commands::update_melee_game_score,
             // Bracket commands
             commands::get_brackets,
             commands::get_matches_for_bracket,
             commands::generate_brackets,
             commands::delete_brackets,
             commands::update_match_score,
+            // End-by-end scoring and timing commands
+            commands::start_round,
+            commands::start_match,
+            commands::get_game_ends,
+            commands::record_game_end,
+            commands::undo_last_game_end,
+            commands::get_match_ends,
+            commands::record_match_end,
+            commands::undo_last_match_end,
+            commands::get_court_durations,
+            // Pairing override commands
+            commands::swap_teams,
+            commands::move_game_to_court,
+            commands::get_pairing_overrides,
+            // Draw commands
+            commands::get_draws,
+            commands::replay_draw,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
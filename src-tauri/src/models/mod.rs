@@ -20,7 +20,21 @@ pub struct Tournament {
     pub advance_count: Option<i32>,
     pub bracket_size: i32,
     pub pairing_method: String,
-    pub region_avoidance: bool,
+    pub avoidance_policy: String,
+    pub avoidance_rounds: Option<i32>,
+    pub tiebreakers: Vec<String>,
+    pub max_teams: Option<i32>,
+    pub bye_winner_score: i32,
+    pub bye_loser_score: i32,
+    pub bye_counts_in_tiebreaks: bool,
+    pub forfeit_winner_score: i32,
+    pub forfeit_loser_score: i32,
+    pub late_entry_policy: String,
+    pub target_score: i32,
+    pub time_limit_minutes: Option<i32>,
+    pub extra_ends: i32,
+    pub first_round_draw: String,
+    pub bracket_seeding: String,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -45,7 +59,78 @@ pub struct CreateTournamentData {
     pub advance_count: Option<i32>,
     pub bracket_size: i32,
     pub pairing_method: String,
-    pub region_avoidance: bool,
+    pub avoidance_policy: String,
+    // Avoidance applies to every round when not set; left unchanged on update
+    // when omitted, 0 makes it apply to every round again
+    #[serde(default)]
+    pub avoidance_rounds: Option<i32>,
+    // Ranking criteria in order; the pairing method's default when omitted on create
+    #[serde(default)]
+    pub tiebreakers: Option<Vec<String>>,
+    // No cap when not set; left unchanged on update when omitted, 0 removes the cap
+    #[serde(default)]
+    pub max_teams: Option<i32>,
+    // Scoring policy; left unchanged on update when omitted
+    #[serde(default)]
+    pub bye_winner_score: Option<i32>,
+    #[serde(default)]
+    pub bye_loser_score: Option<i32>,
+    #[serde(default)]
+    pub bye_counts_in_tiebreaks: Option<bool>,
+    #[serde(default)]
+    pub forfeit_winner_score: Option<i32>,
+    #[serde(default)]
+    pub forfeit_loser_score: Option<i32>,
+    // "zeroWins" or "creditedByes"; left unchanged on update when omitted
+    #[serde(default)]
+    pub late_entry_policy: Option<String>,
+    // Points a game is played to: 13 unless set, left unchanged on update when omitted
+    #[serde(default)]
+    pub target_score: Option<i32>,
+    // Games may end below the target score when set; left unchanged on update
+    // when omitted, 0 removes the time limit
+    #[serde(default)]
+    pub time_limit_minutes: Option<i32>,
+    // Ends played after the one in progress when time is called: 1 unless set
+    #[serde(default)]
+    pub extra_ends: Option<i32>,
+    // "random" or "seeded" first Swiss round; left unchanged on update when omitted
+    #[serde(default)]
+    pub first_round_draw: Option<String>,
+    // "classic" (the best seeds get the BYEs, the rest is drawn), "standard"
+    // (seed positions, 1 v 16, 8 v 9...) or "random"; unchanged on update when omitted
+    #[serde(default)]
+    pub bracket_seeding: Option<String>,
+}
+
+/// How a forfeited game or a bracket walkover is scored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForfeitScoring {
+    pub winner_score: i32,
+    pub loser_score: i32,
+}
+
+/// What a valid game score looks like in a tournament
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameRules {
+    pub target_score: i32,
+    /// Time-limited games may end with the winner below the target score
+    pub time_limited: bool,
+    pub time_limit_minutes: Option<i32>,
+    pub extra_ends: i32,
+}
+
+/// How a BYE is scored in qualifying rounds and brackets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ByeScoring {
+    pub winner_score: i32,
+    pub loser_score: i32,
+    /// Whether BYE points count toward differential and point quotient,
+    /// and the BYE win toward opponents' Buchholz
+    pub counts_in_tiebreaks: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +141,25 @@ pub struct Umpire {
     pub name: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Court {
+    pub id: String,
+    pub tournament_id: String,
+    pub court_number: i32,
+    pub label: Option<String>,
+    pub is_active: bool,
+    pub is_reserved: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCourtData {
+    pub label: Option<String>,
+    pub is_active: bool,
+    pub is_reserved: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Team {
@@ -66,7 +170,19 @@ pub struct Team {
     pub player3: Option<String>,
     pub region: Option<String>,
     pub club: Option<String>,
+    /// Protected seed ("tête de série") for a seeded first round, 1 being the strongest
+    #[serde(default)]
+    pub seed: Option<i32>,
     pub created_at: String,
+    /// False once the team has withdrawn: it is no longer drawn and its
+    /// remaining games are forfeited
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+    pub withdrawn_at: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +194,8 @@ pub struct CreateTeamData {
     pub player3: Option<String>,
     pub region: Option<String>,
     pub club: Option<String>,
+    #[serde(default)]
+    pub seed: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -87,6 +205,10 @@ pub struct QualifyingRound {
     pub tournament_id: String,
     pub round_number: i32,
     pub is_complete: bool,
+    /// How a first Swiss round was drawn: "random" or "seeded"
+    pub draw_method: Option<String>,
+    pub started_at: Option<String>,
+    pub ended_at: Option<String>,
     pub created_at: String,
 }
 
@@ -101,6 +223,9 @@ pub struct QualifyingGame {
     pub team1_score: Option<i32>,
     pub team2_score: Option<i32>,
     pub is_bye: bool,
+    pub is_forfeit: bool,
+    pub slot_number: i32,
+    pub ended_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -114,6 +239,9 @@ pub struct GameWithTeams {
     pub team1_score: Option<i32>,
     pub team2_score: Option<i32>,
     pub is_bye: bool,
+    pub is_forfeit: bool,
+    pub slot_number: i32,
+    pub ended_at: Option<String>,
     pub team1: Option<Team>,
     pub team2: Option<Team>,
 }
@@ -130,10 +258,25 @@ pub struct TeamStanding {
     pub points_against: i32,
     pub differential: i32,
     pub buchholz_score: f64,
+    // Absent from the standings stored with draws recorded before they existed
+    #[serde(default)]
+    pub median_buchholz_score: f64,
+    #[serde(default)]
+    pub cut_buchholz_score: f64,
     pub fine_buchholz_score: f64,
+    #[serde(default)]
+    pub sonneborn_berger_score: f64,
     pub point_quotient: f64,
+    /// Wins against the teams it was still tied with when head-to-head was applied
+    #[serde(default)]
+    pub head_to_head_wins: i32,
     pub is_eliminated: bool,
     pub rank: i32,
+    /// Shares its rank with other teams no tiebreaker could separate
+    #[serde(default)]
+    pub is_tied: bool,
+    pub pool_number: Option<i32>,
+    pub pool_rank: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -179,6 +322,10 @@ pub struct BracketMatch {
     pub winner_id: Option<String>,
     pub next_match_id: Option<String>,
     pub is_bye: bool,
+    pub is_forfeit: bool,
+    pub slot_number: i32,
+    pub started_at: Option<String>,
+    pub ended_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -196,6 +343,10 @@ pub struct MatchWithTeams {
     pub winner_id: Option<String>,
     pub next_match_id: Option<String>,
     pub is_bye: bool,
+    pub is_forfeit: bool,
+    pub slot_number: i32,
+    pub started_at: Option<String>,
+    pub ended_at: Option<String>,
     pub team1: Option<Team>,
     pub team2: Option<Team>,
     pub winner: Option<Team>,
@@ -220,3 +371,255 @@ pub struct CourtHistory {
     pub court_number: i32,
     pub round_id: String,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolAssignment {
+    pub id: String,
+    pub tournament_id: String,
+    pub team_id: String,
+    pub pool_number: i32,
+    pub position: i32,
+    pub pool_rank: Option<i32>,
+}
+
+/// An individual entered in a mêlée
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Player {
+    pub id: String,
+    pub tournament_id: String,
+    pub name: String,
+    pub club: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePlayerData {
+    pub tournament_id: String,
+    pub name: String,
+    pub club: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerStanding {
+    pub id: String,
+    pub tournament_id: String,
+    pub player_id: String,
+    pub wins: i32,
+    pub losses: i32,
+    pub points_for: i32,
+    pub points_against: i32,
+    pub differential: i32,
+    pub rank: i32,
+}
+
+/// A game of a mêlée round with the players drawn into each team
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MeleeGame {
+    pub id: String,
+    pub round_id: String,
+    pub court_number: Option<i32>,
+    pub slot_number: i32,
+    pub team1_id: String,
+    pub team2_id: Option<String>,
+    pub team1_score: Option<i32>,
+    pub team2_score: Option<i32>,
+    pub is_bye: bool,
+    pub team1: Vec<Player>,
+    pub team2: Vec<Player>,
+}
+
+/// A random draw (round pairings, bracket, tiebreak) with the seed and inputs
+/// it was drawn from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Draw {
+    pub id: String,
+    pub tournament_id: String,
+    pub kind: String,
+    pub reference_id: Option<String>,
+    pub seed: String,
+    pub inputs: serde_json::Value,
+    pub result: serde_json::Value,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DrawReplay {
+    pub draw: Draw,
+    pub result: serde_json::Value,
+    pub matches: bool,
+}
+
+/// A score changed after its round was completed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreCorrection {
+    pub id: String,
+    pub tournament_id: String,
+    pub game_id: String,
+    pub round_number: i32,
+    pub old_team1_score: Option<i32>,
+    pub old_team2_score: Option<i32>,
+    pub new_team1_score: i32,
+    pub new_team2_score: i32,
+    pub reason: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankChange {
+    pub team_id: String,
+    pub old_rank: i32,
+    pub new_rank: i32,
+}
+
+/// What a score correction changed, and what was already decided on the
+/// results it replaced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorrectionImpact {
+    pub correction: ScoreCorrection,
+    pub winner_changed: bool,
+    pub rank_changes: Vec<RankChange>,
+    /// Later rounds whose pairings were drawn from standings that included the old score
+    pub affected_round_numbers: Vec<i32>,
+    /// Brackets were already seeded from the old ranking
+    pub brackets_affected: bool,
+}
+
+/// One end of a game: at most one team scores, a dead end scores nothing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameEnd {
+    pub id: String,
+    pub end_number: i32,
+    pub team1_points: i32,
+    pub team2_points: i32,
+    pub recorded_at: String,
+}
+
+/// The ends of a game so far, and where the game stands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndsSummary {
+    pub ends: Vec<GameEnd>,
+    pub team1_total: i32,
+    pub team2_total: i32,
+    pub started_at: Option<String>,
+    /// When time is called, for time-limited games that have started
+    pub time_called_at: Option<String>,
+    /// Ends recorded after time was called, the end in progress included
+    pub ends_after_time: i32,
+    /// The final score has been written from the ends
+    pub is_decided: bool,
+}
+
+/// How long games on a court took, from the start of their round or match
+/// to the entry of their score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CourtDuration {
+    pub court_number: i32,
+    pub games_played: i32,
+    pub average_minutes: f64,
+    pub longest_minutes: f64,
+}
+
+/// Why a manual change to the pairings may be a bad idea
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OverrideWarningKind {
+    /// The two teams have already played each other
+    Rematch,
+    /// The two teams share a region or club the tournament keeps apart
+    SameAffiliation,
+    /// The team moved onto the BYE has already had one
+    RepeatBye,
+    /// The team has already played on the court
+    CourtRepeat,
+    /// Another game is on the court: the two games exchange courts
+    CourtTaken,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverrideWarning {
+    pub kind: OverrideWarningKind,
+    pub team_ids: Vec<String>,
+    pub court_number: Option<i32>,
+    pub message: String,
+}
+
+/// Outcome of a pairing override: nothing is changed while there are
+/// warnings the director has not confirmed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverrideResult {
+    pub applied: bool,
+    pub warnings: Vec<OverrideWarning>,
+}
+
+/// A logged change to a round's pairings ("swap") or courts ("court")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PairingOverride {
+    pub id: String,
+    pub tournament_id: String,
+    pub round_id: String,
+    pub round_number: i32,
+    pub kind: String,
+    pub game1_id: String,
+    pub game2_id: Option<String>,
+    pub team1_id: Option<String>,
+    pub team2_id: Option<String>,
+    pub old_court: Option<i32>,
+    pub new_court: Option<i32>,
+    pub reason: Option<String>,
+    pub warnings: Vec<OverrideWarning>,
+    pub created_at: String,
+}
+
+/// A game of a previewed round
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewGame {
+    pub slot_number: i32,
+    pub court_number: i32,
+    pub team1_id: String,
+    /// None for a BYE
+    pub team2_id: Option<String>,
+    pub is_repeat: bool,
+    pub same_region: bool,
+    /// Difference in wins between the two teams before the round
+    pub wins_gap: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PairingQualityReport {
+    pub repeats: i32,
+    pub same_region_pairs: i32,
+    pub max_wins_gap: i32,
+    pub total_wins_gap: i32,
+    pub bye_team_id: Option<String>,
+}
+
+/// The next round as it would be drawn, nothing being saved. Accepting it
+/// draws the round again from the same seed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PairingPreview {
+    pub tournament_id: String,
+    pub round_number: i32,
+    /// Kept as text: a 64-bit seed does not fit in a JavaScript number
+    pub seed: String,
+    pub games: Vec<PreviewGame>,
+    pub report: PairingQualityReport,
+}
@@ -0,0 +1,172 @@
+//! Minimum cost assignment (Hungarian algorithm).
+
+/// Assigns every row to a distinct column so that the total cost is minimal.
+///
+/// `costs` is a rectangular matrix with at most as many rows as columns.
+/// Returns, for each row, the index of its column. Runs in O(rows² × columns).
+pub fn min_cost_assignment(costs: &[Vec<i64>]) -> Vec<usize> {
+    let n = costs.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let m = costs[0].len();
+    assert!(n <= m, "assignment needs at least as many columns as rows");
+
+    // Potentials and matching use 1-based indices, 0 is the virtual start column
+    let mut u = vec![0i64; n + 1];
+    let mut v = vec![0i64; m + 1];
+    let mut p = vec![0usize; m + 1];
+    let mut way = vec![0usize; m + 1];
+
+    for i in 1..=n {
+        p[0] = i;
+        let mut j0 = 0;
+        let mut minv = vec![i64::MAX; m + 1];
+        let mut used = vec![false; m + 1];
+
+        loop {
+            used[j0] = true;
+            let i0 = p[j0];
+            let mut delta = i64::MAX;
+            let mut j1 = 0;
+
+            for j in 1..=m {
+                if used[j] {
+                    continue;
+                }
+                let cur = costs[i0 - 1][j - 1] - u[i0] - v[j];
+                if cur < minv[j] {
+                    minv[j] = cur;
+                    way[j] = j0;
+                }
+                if minv[j] < delta {
+                    delta = minv[j];
+                    j1 = j;
+                }
+            }
+
+            for j in 0..=m {
+                if used[j] {
+                    u[p[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    minv[j] -= delta;
+                }
+            }
+
+            j0 = j1;
+            if p[j0] == 0 {
+                break;
+            }
+        }
+
+        // Walk the augmenting path back to the start column
+        loop {
+            let j1 = way[j0];
+            p[j0] = p[j1];
+            j0 = j1;
+            if j0 == 0 {
+                break;
+            }
+        }
+    }
+
+    let mut assignment = vec![0; n];
+    for j in 1..=m {
+        if p[j] != 0 {
+            assignment[p[j] - 1] = j - 1;
+        }
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    /// Cheapest assignment cost by trying every injection of rows into columns
+    fn brute_force(costs: &[Vec<i64>]) -> i64 {
+        fn go(costs: &[Vec<i64>], row: usize, used: &mut Vec<bool>) -> i64 {
+            if row == costs.len() {
+                return 0;
+            }
+            let mut best = i64::MAX;
+            for j in 0..used.len() {
+                if !used[j] {
+                    used[j] = true;
+                    best = best.min(costs[row][j] + go(costs, row + 1, used));
+                    used[j] = false;
+                }
+            }
+            best
+        }
+        go(costs, 0, &mut vec![false; costs[0].len()])
+    }
+
+    fn total(costs: &[Vec<i64>], assignment: &[usize]) -> i64 {
+        assignment.iter().enumerate().map(|(i, &j)| costs[i][j]).sum()
+    }
+
+    fn check_distinct(assignment: &[usize], columns: usize) {
+        let mut seen = vec![false; columns];
+        for &j in assignment {
+            assert!(j < columns && !seen[j]);
+            seen[j] = true;
+        }
+    }
+
+    #[test]
+    fn empty_matrix() {
+        assert!(min_cost_assignment(&[]).is_empty());
+    }
+
+    #[test]
+    fn square_matrix() {
+        let costs = vec![vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]];
+        let assignment = min_cost_assignment(&costs);
+        assert_eq!(assignment, vec![1, 0, 2]);
+        assert_eq!(total(&costs, &assignment), 5);
+    }
+
+    #[test]
+    fn fewer_games_than_courts() {
+        // Two games, four courts: each game takes its own cheapest free court
+        let costs = vec![vec![5, 0, 5, 5], vec![5, 0, 5, 1]];
+        assert_eq!(min_cost_assignment(&costs), vec![1, 3]);
+
+        let costs = vec![vec![7, 3, 9, 2, 8]];
+        assert_eq!(min_cost_assignment(&costs), vec![3]);
+    }
+
+    #[test]
+    fn ties_still_give_distinct_columns() {
+        let costs = vec![vec![1; 4]; 3];
+        let assignment = min_cost_assignment(&costs);
+        check_distinct(&assignment, 4);
+        assert_eq!(total(&costs, &assignment), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn more_rows_than_columns() {
+        min_cost_assignment(&[vec![0], vec![0]]);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        for _ in 0..500 {
+            let columns = rng.gen_range(1..8);
+            let rows = rng.gen_range(1..=columns);
+            // Small range so that ties are common
+            let max = if rng.gen_bool(0.5) { 3 } else { 1000 };
+            let costs: Vec<Vec<i64>> = (0..rows).map(|_| (0..columns).map(|_| rng.gen_range(0..max)).collect()).collect();
+            let assignment = min_cost_assignment(&costs);
+            assert_eq!(assignment.len(), rows);
+            check_distinct(&assignment, columns);
+            assert_eq!(total(&costs, &assignment), brute_force(&costs), "{:?}", costs);
+        }
+    }
+}
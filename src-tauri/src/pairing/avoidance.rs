@@ -0,0 +1,151 @@
+//! Keeping teams from the same club or region apart.
+
+use crate::models::Team;
+use crate::pairing::matching::min_cost_perfect_matching;
+use std::collections::{HashMap, HashSet};
+
+/// Which affiliations a tournament keeps apart when pairing, most important first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AvoidancePolicy {
+    None,
+    Region,
+    Club,
+    /// Region and club, a region clash being worse than a club clash
+    RegionFirst,
+    /// Club and region, a club clash being worse than a region clash
+    ClubFirst,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Affiliation {
+    Region,
+    Club,
+}
+
+impl Affiliation {
+    fn of(self, team: &Team) -> Option<&str> {
+        let value = match self {
+            Affiliation::Region => team.region.as_deref(),
+            Affiliation::Club => team.club.as_deref(),
+        };
+        value.map(str::trim).filter(|v| !v.is_empty())
+    }
+}
+
+/// Clash level of a primary affiliation; a secondary clash counts for 1
+const PRIMARY_CLASH: u8 = 2;
+
+impl AvoidancePolicy {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "none" => Ok(AvoidancePolicy::None),
+            "region" => Ok(AvoidancePolicy::Region),
+            "club" => Ok(AvoidancePolicy::Club),
+            "regionFirst" => Ok(AvoidancePolicy::RegionFirst),
+            "clubFirst" => Ok(AvoidancePolicy::ClubFirst),
+            _ => Err(format!("Unknown avoidance policy: {}", value)),
+        }
+    }
+
+    /// The policy in force for a round. Avoidance can be limited to the first
+    /// `avoidance_rounds` rounds, after which teams are paired on results alone.
+    pub fn for_round(self, avoidance_rounds: Option<i32>, round_number: i32) -> Self {
+        match avoidance_rounds {
+            Some(rounds) if round_number > rounds => AvoidancePolicy::None,
+            _ => self,
+        }
+    }
+
+    fn affiliations(self) -> &'static [Affiliation] {
+        match self {
+            AvoidancePolicy::None => &[],
+            AvoidancePolicy::Region => &[Affiliation::Region],
+            AvoidancePolicy::Club => &[Affiliation::Club],
+            AvoidancePolicy::RegionFirst => &[Affiliation::Region, Affiliation::Club],
+            AvoidancePolicy::ClubFirst => &[Affiliation::Club, Affiliation::Region],
+        }
+    }
+
+    /// How much two teams have in common: 0 when nothing, 2 for the primary
+    /// affiliation, 1 for the secondary one, 3 for both.
+    pub fn clash(self, t1: &Team, t2: &Team) -> u8 {
+        let mut level = 0;
+        for (priority, affiliation) in self.affiliations().iter().enumerate() {
+            if let (Some(a1), Some(a2)) = (affiliation.of(t1), affiliation.of(t2)) {
+                if a1 == a2 {
+                    level += if priority == 0 { PRIMARY_CLASH } else { 1 };
+                }
+            }
+        }
+        level
+    }
+
+    /// Orders teams so that dealing them out one by one over several groups
+    /// spreads each club or region as widely as possible: the biggest
+    /// affiliations come first, each kept together.
+    pub fn spread_order(self, teams: &mut [&Team]) {
+        let affiliations = self.affiliations();
+        if affiliations.is_empty() {
+            return;
+        }
+
+        let mut sizes: Vec<HashMap<&str, usize>> = vec![HashMap::new(); affiliations.len()];
+        for team in teams.iter() {
+            for (idx, affiliation) in affiliations.iter().enumerate() {
+                if let Some(value) = affiliation.of(team) {
+                    *sizes[idx].entry(value).or_default() += 1;
+                }
+            }
+        }
+
+        let keys: HashMap<String, Vec<(usize, String)>> = teams
+            .iter()
+            .map(|team| {
+                let key = affiliations
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, affiliation)| match affiliation.of(team) {
+                        Some(value) => (usize::MAX - sizes[idx][value], value.to_string()),
+                        None => (usize::MAX, String::new()),
+                    })
+                    .collect();
+                (team.id.clone(), key)
+            })
+            .collect();
+
+        // Stable, so teams keep their (shuffled) order inside an affiliation
+        teams.sort_by(|a, b| keys[&a.id].cmp(&keys[&b.id]));
+    }
+}
+
+/// Cost of a rematch. Large enough that a rematch only survives when every
+/// other pairing also has one.
+const REMATCH_COST: i64 = 1_000_000;
+/// Cost per clash level of two opponents sharing a club or region
+const CLASH_COST: i64 = 1_000;
+
+/// Pairs the teams with as few rematches as possible, then as few (and as
+/// mild) club or region clashes as possible. Pass the teams shuffled for a
+/// random draw.
+///
+/// With an odd number of teams the last team is left out; callers set the BYE
+/// aside beforehand.
+pub fn pair_avoiding(
+    teams: &[&Team],
+    pairing_history: &HashSet<(String, String)>,
+    policy: AvoidancePolicy,
+) -> Vec<(String, Option<String>)> {
+    let teams = &teams[..teams.len() - teams.len() % 2];
+
+    min_cost_perfect_matching(teams.len(), |i, j| {
+        let (t1, t2) = (teams[i], teams[j]);
+        let mut cost = policy.clash(t1, t2) as i64 * CLASH_COST;
+        if pairing_history.contains(&(t1.id.clone(), t2.id.clone())) {
+            cost += REMATCH_COST;
+        }
+        cost
+    })
+    .into_iter()
+    .map(|(i, j)| (teams[i].id.clone(), Some(teams[j].id.clone())))
+    .collect()
+}
@@ -0,0 +1,817 @@
+//! Maximum weight matching on general graphs (Edmonds' blossom algorithm).
+//!
+//! This is a port of Joris van Rantwijk's `mwmatching.py`, which runs in
+//! O(n^3). Integer weights keep every dual variable integral, so there is no
+//! floating point drift between runs.
+
+/// Computes a maximum weight matching for the undirected graph described by
+/// `edges` (pairs of vertex indices with a weight).
+///
+/// When `max_cardinality` is true, only maximum-cardinality matchings are
+/// considered, which on a complete graph with an even number of vertices
+/// means a perfect matching.
+///
+/// Returns `mate`, where `mate[v]` is the vertex matched to `v`, if any.
+pub fn max_weight_matching(edges: &[(usize, usize, i64)], max_cardinality: bool) -> Vec<Option<usize>> {
+    if edges.is_empty() {
+        return Vec::new();
+    }
+    let mut state = Matcher::new(edges);
+    state.solve(max_cardinality);
+    state
+        .mate
+        .iter()
+        .map(|&p| if p >= 0 { Some(state.endpoint[p as usize]) } else { None })
+        .collect()
+}
+
+/// Minimum cost perfect matching over `n` vertices.
+///
+/// `cost(i, j)` is queried once for every unordered pair `i < j`. `n` must be
+/// even; every vertex is matched. Returns the matched pairs with `i < j`.
+pub fn min_cost_perfect_matching<F>(n: usize, mut cost: F) -> Vec<(usize, usize)>
+where
+    F: FnMut(usize, usize) -> i64,
+{
+    if n < 2 {
+        return Vec::new();
+    }
+
+    let mut costs = Vec::with_capacity(n * (n - 1) / 2);
+    for i in 0..n {
+        for j in (i + 1)..n {
+            costs.push((i, j, cost(i, j)));
+        }
+    }
+
+    // Turn costs into positive weights so the cheapest matching is the heaviest one
+    let max_cost = costs.iter().map(|&(_, _, c)| c).max().unwrap_or(0);
+    let edges: Vec<(usize, usize, i64)> = costs
+        .into_iter()
+        .map(|(i, j, c)| (i, j, max_cost - c + 1))
+        .collect();
+
+    let mate = max_weight_matching(&edges, true);
+
+    let mut pairs = Vec::with_capacity(n / 2);
+    for (i, m) in mate.iter().enumerate() {
+        if let Some(j) = *m {
+            if i < j {
+                pairs.push((i, j));
+            }
+        }
+    }
+    pairs
+}
+
+struct Matcher<'a> {
+    edges: &'a [(usize, usize, i64)],
+    nvertex: usize,
+    endpoint: Vec<usize>,
+    neighbend: Vec<Vec<usize>>,
+    mate: Vec<isize>,
+    label: Vec<u8>,
+    labelend: Vec<isize>,
+    inblossom: Vec<usize>,
+    blossomparent: Vec<isize>,
+    blossomchilds: Vec<Vec<usize>>,
+    blossombase: Vec<isize>,
+    blossomendps: Vec<Vec<usize>>,
+    bestedge: Vec<isize>,
+    blossombestedges: Vec<Option<Vec<usize>>>,
+    unusedblossoms: Vec<usize>,
+    dualvar: Vec<i64>,
+    allowedge: Vec<bool>,
+    queue: Vec<usize>,
+}
+
+/// Index into a blossom's child list the way Python does, allowing negatives.
+fn wrap(j: isize, len: usize) -> usize {
+    j.rem_euclid(len as isize) as usize
+}
+
+impl<'a> Matcher<'a> {
+    fn new(edges: &'a [(usize, usize, i64)]) -> Self {
+        let nedge = edges.len();
+        let nvertex = edges.iter().map(|&(i, j, _)| i.max(j) + 1).max().unwrap_or(0);
+        let maxweight = edges.iter().map(|&(_, _, w)| w).max().unwrap_or(0).max(0);
+
+        let endpoint: Vec<usize> = (0..2 * nedge)
+            .map(|p| if p % 2 == 0 { edges[p / 2].0 } else { edges[p / 2].1 })
+            .collect();
+
+        let mut neighbend = vec![Vec::new(); nvertex];
+        for (k, &(i, j, _)) in edges.iter().enumerate() {
+            neighbend[i].push(2 * k + 1);
+            neighbend[j].push(2 * k);
+        }
+
+        let mut blossombase = vec![-1; 2 * nvertex];
+        for (v, base) in blossombase.iter_mut().enumerate().take(nvertex) {
+            *base = v as isize;
+        }
+
+        let mut dualvar = vec![0; 2 * nvertex];
+        for d in dualvar.iter_mut().take(nvertex) {
+            *d = maxweight;
+        }
+
+        Matcher {
+            edges,
+            nvertex,
+            endpoint,
+            neighbend,
+            mate: vec![-1; nvertex],
+            label: vec![0; 2 * nvertex],
+            labelend: vec![-1; 2 * nvertex],
+            inblossom: (0..nvertex).collect(),
+            blossomparent: vec![-1; 2 * nvertex],
+            blossomchilds: vec![Vec::new(); 2 * nvertex],
+            blossombase,
+            blossomendps: vec![Vec::new(); 2 * nvertex],
+            bestedge: vec![-1; 2 * nvertex],
+            blossombestedges: vec![None; 2 * nvertex],
+            unusedblossoms: (nvertex..2 * nvertex).collect(),
+            dualvar,
+            allowedge: vec![false; nedge],
+            queue: Vec::new(),
+        }
+    }
+
+    fn slack(&self, k: usize) -> i64 {
+        let (i, j, wt) = self.edges[k];
+        self.dualvar[i] + self.dualvar[j] - 2 * wt
+    }
+
+    fn blossom_leaves(&self, b: usize) -> Vec<usize> {
+        let mut leaves = Vec::new();
+        let mut stack = vec![b];
+        while let Some(t) = stack.pop() {
+            if t < self.nvertex {
+                leaves.push(t);
+            } else {
+                // Push in reverse so leaves come out in child order
+                for &c in self.blossomchilds[t].iter().rev() {
+                    stack.push(c);
+                }
+            }
+        }
+        leaves
+    }
+
+    fn assign_label(&mut self, w: usize, t: u8, p: isize) {
+        let mut w = w;
+        let mut t = t;
+        let mut p = p;
+        loop {
+            let b = self.inblossom[w];
+            self.label[w] = t;
+            self.label[b] = t;
+            self.labelend[w] = p;
+            self.labelend[b] = p;
+            self.bestedge[w] = -1;
+            self.bestedge[b] = -1;
+            if t == 1 {
+                let leaves = self.blossom_leaves(b);
+                self.queue.extend(leaves);
+                return;
+            }
+            // t == 2: label the mate of the base as S
+            let base = self.blossombase[b] as usize;
+            let mate_base = self.mate[base];
+            w = self.endpoint[mate_base as usize];
+            t = 1;
+            p = mate_base ^ 1;
+        }
+    }
+
+    fn scan_blossom(&mut self, v: usize, w: usize) -> isize {
+        let mut path = Vec::new();
+        let mut base: isize = -1;
+        let mut v = v as isize;
+        let mut w = w as isize;
+        while v != -1 || w != -1 {
+            let mut b = self.inblossom[v as usize];
+            if self.label[b] & 4 != 0 {
+                base = self.blossombase[b];
+                break;
+            }
+            path.push(b);
+            self.label[b] = 5;
+            if self.labelend[b] == -1 {
+                v = -1;
+            } else {
+                v = self.endpoint[self.labelend[b] as usize] as isize;
+                b = self.inblossom[v as usize];
+                v = self.endpoint[self.labelend[b] as usize] as isize;
+            }
+            if w != -1 {
+                std::mem::swap(&mut v, &mut w);
+            }
+        }
+        for b in path {
+            self.label[b] = 1;
+        }
+        base
+    }
+
+    fn add_blossom(&mut self, base: usize, k: usize) {
+        let (mut v, mut w, _) = self.edges[k];
+        let bb = self.inblossom[base];
+        let mut bv = self.inblossom[v];
+        let mut bw = self.inblossom[w];
+        let b = self.unusedblossoms.pop().expect("blossom pool exhausted");
+        self.blossombase[b] = base as isize;
+        self.blossomparent[b] = -1;
+        self.blossomparent[bb] = b as isize;
+
+        let mut path = Vec::new();
+        let mut endps = Vec::new();
+        while bv != bb {
+            self.blossomparent[bv] = b as isize;
+            path.push(bv);
+            endps.push(self.labelend[bv] as usize);
+            v = self.endpoint[self.labelend[bv] as usize];
+            bv = self.inblossom[v];
+        }
+        path.push(bb);
+        path.reverse();
+        endps.reverse();
+        endps.push(2 * k);
+        while bw != bb {
+            self.blossomparent[bw] = b as isize;
+            path.push(bw);
+            endps.push((self.labelend[bw] ^ 1) as usize);
+            w = self.endpoint[self.labelend[bw] as usize];
+            bw = self.inblossom[w];
+        }
+
+        self.label[b] = 1;
+        self.labelend[b] = self.labelend[bb];
+        self.dualvar[b] = 0;
+        self.blossomchilds[b] = path.clone();
+        self.blossomendps[b] = endps;
+
+        for leaf in self.blossom_leaves(b) {
+            if self.label[self.inblossom[leaf]] == 2 {
+                self.queue.push(leaf);
+            }
+            self.inblossom[leaf] = b;
+        }
+
+        // Compute the least-slack edges from the new blossom to neighbouring S-blossoms
+        let mut bestedgeto: Vec<isize> = vec![-1; 2 * self.nvertex];
+        for &bv in &path {
+            let nblists: Vec<Vec<usize>> = match self.blossombestedges[bv].take() {
+                Some(list) => vec![list],
+                None => self
+                    .blossom_leaves(bv)
+                    .into_iter()
+                    .map(|leaf| self.neighbend[leaf].iter().map(|p| p / 2).collect())
+                    .collect(),
+            };
+            for nblist in nblists {
+                for k in nblist {
+                    let (i, j, _) = self.edges[k];
+                    let j = if self.inblossom[j] == b { i } else { j };
+                    let bj = self.inblossom[j];
+                    if bj != b
+                        && self.label[bj] == 1
+                        && (bestedgeto[bj] == -1 || self.slack(k) < self.slack(bestedgeto[bj] as usize))
+                    {
+                        bestedgeto[bj] = k as isize;
+                    }
+                }
+            }
+            self.bestedge[bv] = -1;
+        }
+
+        let best: Vec<usize> = bestedgeto.into_iter().filter(|&k| k != -1).map(|k| k as usize).collect();
+        self.bestedge[b] = -1;
+        for &k in &best {
+            if self.bestedge[b] == -1 || self.slack(k) < self.slack(self.bestedge[b] as usize) {
+                self.bestedge[b] = k as isize;
+            }
+        }
+        self.blossombestedges[b] = Some(best);
+    }
+
+    fn expand_blossom(&mut self, b: usize, endstage: bool) {
+        let childs = self.blossomchilds[b].clone();
+        for &s in &childs {
+            self.blossomparent[s] = -1;
+            if s < self.nvertex {
+                self.inblossom[s] = s;
+            } else if endstage && self.dualvar[s] == 0 {
+                self.expand_blossom(s, endstage);
+            } else {
+                for leaf in self.blossom_leaves(s) {
+                    self.inblossom[leaf] = s;
+                }
+            }
+        }
+
+        if !endstage && self.label[b] == 2 {
+            let len = childs.len();
+            let entrychild = self.inblossom[self.endpoint[(self.labelend[b] ^ 1) as usize]];
+            let mut j = childs.iter().position(|&c| c == entrychild).unwrap() as isize;
+            let (jstep, endptrick): (isize, usize) = if j & 1 != 0 {
+                j -= len as isize;
+                (1, 0)
+            } else {
+                (-1, 1)
+            };
+            let endps = self.blossomendps[b].clone();
+            let mut p = self.labelend[b] as usize;
+            while j != 0 {
+                let q = endps[wrap(j - endptrick as isize, len)];
+                self.label[self.endpoint[p ^ 1]] = 0;
+                self.label[self.endpoint[q ^ endptrick ^ 1]] = 0;
+                self.assign_label(self.endpoint[p ^ 1], 2, p as isize);
+                self.allowedge[q / 2] = true;
+                j += jstep;
+                p = endps[wrap(j - endptrick as isize, len)] ^ endptrick;
+                self.allowedge[p / 2] = true;
+                j += jstep;
+            }
+            let bv = childs[wrap(j, len)];
+            self.label[self.endpoint[p ^ 1]] = 2;
+            self.label[bv] = 2;
+            self.labelend[self.endpoint[p ^ 1]] = p as isize;
+            self.labelend[bv] = p as isize;
+            self.bestedge[bv] = -1;
+            j += jstep;
+            while childs[wrap(j, len)] != entrychild {
+                let bv = childs[wrap(j, len)];
+                if self.label[bv] == 1 {
+                    j += jstep;
+                    continue;
+                }
+                let labelled = self.blossom_leaves(bv).into_iter().find(|&v| self.label[v] != 0);
+                if let Some(v) = labelled {
+                    self.label[v] = 0;
+                    let base = self.blossombase[bv] as usize;
+                    self.label[self.endpoint[self.mate[base] as usize]] = 0;
+                    self.assign_label(v, 2, self.labelend[v]);
+                }
+                j += jstep;
+            }
+        }
+
+        self.label[b] = 0;
+        self.labelend[b] = -1;
+        self.blossomchilds[b] = Vec::new();
+        self.blossomendps[b] = Vec::new();
+        self.blossombase[b] = -1;
+        self.blossombestedges[b] = None;
+        self.bestedge[b] = -1;
+        self.unusedblossoms.push(b);
+    }
+
+    fn augment_blossom(&mut self, b: usize, v: usize) {
+        let mut t = v;
+        while self.blossomparent[t] != b as isize {
+            t = self.blossomparent[t] as usize;
+        }
+        if t >= self.nvertex {
+            self.augment_blossom(t, v);
+        }
+
+        let len = self.blossomchilds[b].len();
+        let i = self.blossomchilds[b].iter().position(|&c| c == t).unwrap();
+        let mut j = i as isize;
+        let (jstep, endptrick): (isize, usize) = if i & 1 != 0 {
+            j -= len as isize;
+            (1, 0)
+        } else {
+            (-1, 1)
+        };
+        while j != 0 {
+            j += jstep;
+            let t = self.blossomchilds[b][wrap(j, len)];
+            let p = self.blossomendps[b][wrap(j - endptrick as isize, len)] ^ endptrick;
+            if t >= self.nvertex {
+                self.augment_blossom(t, self.endpoint[p]);
+            }
+            j += jstep;
+            let t = self.blossomchilds[b][wrap(j, len)];
+            if t >= self.nvertex {
+                self.augment_blossom(t, self.endpoint[p ^ 1]);
+            }
+            self.mate[self.endpoint[p]] = (p ^ 1) as isize;
+            self.mate[self.endpoint[p ^ 1]] = p as isize;
+        }
+
+        self.blossomchilds[b].rotate_left(i);
+        self.blossomendps[b].rotate_left(i);
+        self.blossombase[b] = self.blossombase[self.blossomchilds[b][0]];
+    }
+
+    fn augment_matching(&mut self, k: usize) {
+        let (v, w, _) = self.edges[k];
+        for (s, p) in [(v, 2 * k + 1), (w, 2 * k)] {
+            let mut s = s;
+            let mut p = p;
+            loop {
+                let bs = self.inblossom[s];
+                if bs >= self.nvertex {
+                    self.augment_blossom(bs, s);
+                }
+                self.mate[s] = p as isize;
+                if self.labelend[bs] == -1 {
+                    break;
+                }
+                let t = self.endpoint[self.labelend[bs] as usize];
+                let bt = self.inblossom[t];
+                let labelend_bt = self.labelend[bt] as usize;
+                s = self.endpoint[labelend_bt];
+                let j = self.endpoint[labelend_bt ^ 1];
+                if bt >= self.nvertex {
+                    self.augment_blossom(bt, j);
+                }
+                self.mate[j] = labelend_bt as isize;
+                p = labelend_bt ^ 1;
+            }
+        }
+    }
+
+    fn solve(&mut self, max_cardinality: bool) {
+        let nvertex = self.nvertex;
+
+        for _ in 0..nvertex {
+            // Start a new stage
+            self.label.iter_mut().for_each(|l| *l = 0);
+            self.bestedge.iter_mut().for_each(|e| *e = -1);
+            for b in nvertex..2 * nvertex {
+                self.blossombestedges[b] = None;
+            }
+            self.allowedge.iter_mut().for_each(|a| *a = false);
+            self.queue.clear();
+
+            for v in 0..nvertex {
+                if self.mate[v] == -1 && self.label[self.inblossom[v]] == 0 {
+                    self.assign_label(v, 1, -1);
+                }
+            }
+
+            let mut augmented = false;
+            loop {
+                while !augmented {
+                    let Some(v) = self.queue.pop() else { break };
+                    for idx in 0..self.neighbend[v].len() {
+                        let p = self.neighbend[v][idx];
+                        let k = p / 2;
+                        let w = self.endpoint[p];
+                        if self.inblossom[v] == self.inblossom[w] {
+                            continue;
+                        }
+                        let mut kslack = 0;
+                        if !self.allowedge[k] {
+                            kslack = self.slack(k);
+                            if kslack <= 0 {
+                                self.allowedge[k] = true;
+                            }
+                        }
+                        if self.allowedge[k] {
+                            if self.label[self.inblossom[w]] == 0 {
+                                self.assign_label(w, 2, (p ^ 1) as isize);
+                            } else if self.label[self.inblossom[w]] == 1 {
+                                let base = self.scan_blossom(v, w);
+                                if base >= 0 {
+                                    self.add_blossom(base as usize, k);
+                                } else {
+                                    self.augment_matching(k);
+                                    augmented = true;
+                                    break;
+                                }
+                            } else if self.label[w] == 0 {
+                                self.label[w] = 2;
+                                self.labelend[w] = (p ^ 1) as isize;
+                            }
+                        } else if self.label[self.inblossom[w]] == 1 {
+                            let b = self.inblossom[v];
+                            if self.bestedge[b] == -1 || kslack < self.slack(self.bestedge[b] as usize) {
+                                self.bestedge[b] = k as isize;
+                            }
+                        } else if self.label[w] == 0
+                            && (self.bestedge[w] == -1 || kslack < self.slack(self.bestedge[w] as usize))
+                        {
+                            self.bestedge[w] = k as isize;
+                        }
+                    }
+                }
+
+                if augmented {
+                    break;
+                }
+
+                // No augmenting path found: update the dual variables
+                let mut deltatype = -1;
+                let mut delta: i64 = 0;
+                let mut deltaedge: usize = 0;
+                let mut deltablossom: usize = 0;
+
+                if !max_cardinality {
+                    deltatype = 1;
+                    delta = *self.dualvar[..nvertex].iter().min().unwrap();
+                }
+
+                for v in 0..nvertex {
+                    if self.label[self.inblossom[v]] == 0 && self.bestedge[v] != -1 {
+                        let d = self.slack(self.bestedge[v] as usize);
+                        if deltatype == -1 || d < delta {
+                            delta = d;
+                            deltatype = 2;
+                            deltaedge = self.bestedge[v] as usize;
+                        }
+                    }
+                }
+
+                for b in 0..2 * nvertex {
+                    if self.blossomparent[b] == -1 && self.label[b] == 1 && self.bestedge[b] != -1 {
+                        let d = self.slack(self.bestedge[b] as usize) / 2;
+                        if deltatype == -1 || d < delta {
+                            delta = d;
+                            deltatype = 3;
+                            deltaedge = self.bestedge[b] as usize;
+                        }
+                    }
+                }
+
+                for b in nvertex..2 * nvertex {
+                    if self.blossombase[b] >= 0
+                        && self.blossomparent[b] == -1
+                        && self.label[b] == 2
+                        && (deltatype == -1 || self.dualvar[b] < delta)
+                    {
+                        delta = self.dualvar[b];
+                        deltatype = 4;
+                        deltablossom = b;
+                    }
+                }
+
+                if deltatype == -1 {
+                    // No further improvement possible; max-cardinality optimum reached
+                    deltatype = 1;
+                    delta = (*self.dualvar[..nvertex].iter().min().unwrap()).max(0);
+                }
+
+                for v in 0..nvertex {
+                    match self.label[self.inblossom[v]] {
+                        1 => self.dualvar[v] -= delta,
+                        2 => self.dualvar[v] += delta,
+                        _ => {}
+                    }
+                }
+                for b in nvertex..2 * nvertex {
+                    if self.blossombase[b] >= 0 && self.blossomparent[b] == -1 {
+                        match self.label[b] {
+                            1 => self.dualvar[b] += delta,
+                            2 => self.dualvar[b] -= delta,
+                            _ => {}
+                        }
+                    }
+                }
+
+                match deltatype {
+                    1 => break,
+                    2 => {
+                        self.allowedge[deltaedge] = true;
+                        let (mut i, j, _) = self.edges[deltaedge];
+                        if self.label[self.inblossom[i]] == 0 {
+                            i = j;
+                        }
+                        self.queue.push(i);
+                    }
+                    3 => {
+                        self.allowedge[deltaedge] = true;
+                        let (i, _, _) = self.edges[deltaedge];
+                        self.queue.push(i);
+                    }
+                    _ => self.expand_blossom(deltablossom, false),
+                }
+            }
+
+            if !augmented {
+                break;
+            }
+
+            // End of stage: expand S-blossoms with zero dual
+            for b in nvertex..2 * nvertex {
+                if self.blossomparent[b] == -1
+                    && self.blossombase[b] >= 0
+                    && self.label[b] == 1
+                    && self.dualvar[b] == 0
+                {
+                    self.expand_blossom(b, true);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    /// Same weight as a rematch in the Swiss pairing
+    const REPEAT_COST: i64 = 1_000_000;
+
+    type Edge = (usize, usize, i64);
+
+    /// Cheapest perfect matching cost by trying every pairing
+    fn brute_force_min_cost(n: usize, cost: &dyn Fn(usize, usize) -> i64) -> i64 {
+        fn go(free: &mut Vec<usize>, cost: &dyn Fn(usize, usize) -> i64) -> i64 {
+            if free.is_empty() {
+                return 0;
+            }
+            let first = free.remove(0);
+            let mut best = i64::MAX;
+            for k in 0..free.len() {
+                let other = free.remove(k);
+                best = best.min(cost(first, other) + go(free, cost));
+                free.insert(k, other);
+            }
+            free.insert(0, first);
+            best
+        }
+        go(&mut (0..n).collect(), cost)
+    }
+
+    /// Heaviest matching weight by trying every subset of edges
+    fn brute_force_max_weight(edges: &[Edge]) -> i64 {
+        fn go(edges: &[Edge], used: &mut Vec<usize>) -> i64 {
+            let Some((&(i, j, w), rest)) = edges.split_first() else {
+                return 0;
+            };
+            let mut best = go(rest, used);
+            if !used.contains(&i) && !used.contains(&j) {
+                used.extend([i, j]);
+                best = best.max(w + go(rest, used));
+                used.truncate(used.len() - 2);
+            }
+            best
+        }
+        go(edges, &mut Vec::new())
+    }
+
+    fn matching_weight(edges: &[Edge], mate: &[Option<usize>]) -> i64 {
+        edges
+            .iter()
+            .filter(|&&(i, j, _)| mate[i] == Some(j))
+            .map(|&(_, _, w)| w)
+            .sum()
+    }
+
+    fn check_perfect(n: usize, pairs: &[(usize, usize)]) {
+        assert_eq!(pairs.len(), n / 2);
+        let mut seen = vec![false; n];
+        for &(i, j) in pairs {
+            assert!(i < j && !seen[i] && !seen[j]);
+            seen[i] = true;
+            seen[j] = true;
+        }
+    }
+
+    fn mates(expected: &[isize]) -> Vec<Option<usize>> {
+        expected.iter().map(|&m| (m >= 0).then_some(m as usize)).collect()
+    }
+
+    #[test]
+    fn min_cost_matches_brute_force() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        for n in [2, 4, 6, 8, 10] {
+            for _ in 0..40 {
+                // Few distinct costs, so that ties are common
+                let table: Vec<Vec<i64>> = (0..n).map(|_| (0..n).map(|_| rng.gen_range(0..5)).collect()).collect();
+                let cost = |i: usize, j: usize| table[i.min(j)][i.max(j)];
+                let pairs = min_cost_perfect_matching(n, cost);
+                check_perfect(n, &pairs);
+                let total: i64 = pairs.iter().map(|&(i, j)| cost(i, j)).sum();
+                assert_eq!(total, brute_force_min_cost(n, &cost));
+            }
+        }
+    }
+
+    #[test]
+    fn repeat_cost_forces_the_only_fresh_pairing() {
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        for n in [4, 6, 8, 10] {
+            for _ in 0..40 {
+                // Every pair is a rematch except those of one hidden perfect matching
+                let mut order: Vec<usize> = (0..n).collect();
+                for k in (1..n).rev() {
+                    order.swap(k, rng.gen_range(0..=k));
+                }
+                let fresh: Vec<(usize, usize)> = order.chunks(2).map(|p| (p[0].min(p[1]), p[0].max(p[1]))).collect();
+                let noise: Vec<Vec<i64>> = (0..n).map(|_| (0..n).map(|_| rng.gen_range(0..3000)).collect()).collect();
+                let cost = |i: usize, j: usize| {
+                    let repeat = if fresh.contains(&(i.min(j), i.max(j))) { 0 } else { REPEAT_COST };
+                    repeat + noise[i.min(j)][i.max(j)]
+                };
+
+                let mut pairs = min_cost_perfect_matching(n, cost);
+                pairs.sort();
+                let mut expected = fresh.clone();
+                expected.sort();
+                assert_eq!(pairs, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn unavoidable_repeats_are_kept_to_a_minimum() {
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        for n in [4, 6, 8, 10] {
+            for _ in 0..40 {
+                let table: Vec<Vec<i64>> = (0..n)
+                    .map(|_| {
+                        (0..n)
+                            .map(|_| if rng.gen_bool(0.7) { REPEAT_COST } else { 0 } + rng.gen_range(0..2000))
+                            .collect()
+                    })
+                    .collect();
+                let cost = |i: usize, j: usize| table[i.min(j)][i.max(j)];
+                let pairs = min_cost_perfect_matching(n, cost);
+                check_perfect(n, &pairs);
+                let total: i64 = pairs.iter().map(|&(i, j)| cost(i, j)).sum();
+                assert_eq!(total, brute_force_min_cost(n, &cost));
+            }
+        }
+    }
+
+    #[test]
+    fn odd_cycles_and_blossoms() {
+        // Graphs from the test suite of the original mwmatching.py; vertex 0 is unused
+        let cases: Vec<(Vec<Edge>, Vec<isize>)> = vec![
+            // S-blossom, then augmenting it
+            (vec![(1, 2, 8), (1, 3, 9), (2, 3, 10), (3, 4, 7)], vec![-1, 2, 1, 4, 3]),
+            (
+                vec![(1, 2, 8), (1, 3, 9), (2, 3, 10), (3, 4, 7), (1, 6, 5), (4, 5, 6)],
+                vec![-1, 6, 3, 2, 5, 4, 1],
+            ),
+            // T-blossom
+            (
+                vec![(1, 2, 9), (1, 3, 8), (2, 3, 10), (1, 4, 5), (4, 5, 4), (1, 6, 3)],
+                vec![-1, 6, 3, 2, 5, 4, 1],
+            ),
+            // Nested S-blossom
+            (
+                vec![(1, 2, 9), (1, 3, 9), (2, 3, 10), (2, 4, 8), (3, 5, 8), (4, 5, 10), (5, 6, 6)],
+                vec![-1, 3, 4, 1, 2, 6, 5],
+            ),
+            // S-blossom relabeled as T-blossom, then expanded
+            (
+                vec![(1, 2, 23), (1, 5, 22), (1, 6, 15), (2, 3, 25), (3, 4, 22), (4, 5, 25), (4, 8, 14), (5, 7, 13)],
+                vec![-1, 6, 3, 2, 8, 7, 1, 5, 4],
+            ),
+            // Nested S-blossom relabeled as T-blossom, then expanded
+            (
+                vec![(1, 2, 19), (1, 3, 20), (1, 8, 8), (2, 3, 25), (2, 4, 18), (3, 5, 18), (4, 5, 13), (4, 7, 7), (5, 6, 7)],
+                vec![-1, 8, 3, 2, 7, 6, 5, 4, 1],
+            ),
+            // Blossom expanded while its base is augmented
+            (
+                vec![
+                    (1, 2, 45), (1, 5, 45), (2, 3, 50), (3, 4, 45), (4, 5, 50),
+                    (1, 6, 30), (3, 9, 35), (4, 8, 35), (5, 7, 26), (9, 10, 5),
+                ],
+                vec![-1, 6, 3, 2, 8, 7, 1, 5, 4, 10, 9],
+            ),
+        ];
+
+        for (edges, expected) in cases {
+            let mate = max_weight_matching(&edges, false);
+            assert_eq!(mate, mates(&expected), "{:?}", edges);
+            assert_eq!(matching_weight(&edges, &mate), brute_force_max_weight(&edges));
+        }
+    }
+
+    #[test]
+    fn max_weight_matches_brute_force_on_sparse_graphs() {
+        let mut rng = ChaCha8Rng::seed_from_u64(4);
+        for _ in 0..300 {
+            let n = rng.gen_range(3..9);
+            let mut edges: Vec<Edge> = Vec::new();
+            for i in 0..n {
+                for j in (i + 1)..n {
+                    if rng.gen_bool(0.5) {
+                        edges.push((i, j, rng.gen_range(1..20)));
+                    }
+                }
+            }
+            if edges.is_empty() {
+                continue;
+            }
+            let mate = max_weight_matching(&edges, false);
+            for (v, m) in mate.iter().enumerate() {
+                if let Some(u) = *m {
+                    assert_eq!(mate[u], Some(v));
+                }
+            }
+            assert_eq!(matching_weight(&edges, &mate), brute_force_max_weight(&edges), "{:?}", edges);
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! Forming new teams every round of a mêlée.
+
+use std::collections::HashMap;
+
+/// Cost of playing again with a former teammate, per squared number of times
+/// they already played together. Partners matter most to players.
+const REPEAT_TEAMMATE_COST: i64 = 10;
+/// Cost of facing a former opponent again, per squared number of meetings
+const REPEAT_OPPONENT_COST: i64 = 1;
+/// Upper bound on the improvement passes over the whole field
+const MAX_PASSES: usize = 50;
+
+/// Sizes of the two teams of every game of a round with `player_count`
+/// players in teams of `team_size`, and the number of players who sit out.
+///
+/// Tête-à-tête plays one against one. Otherwise teams have 2 or 3 players:
+/// a doublette mêlée completes with triplettes and a triplette mêlée with
+/// doublettes, keeping as many teams of the chosen size as possible. Players
+/// only sit out when the field cannot be split at all.
+pub fn game_sizes(player_count: usize, team_size: usize) -> (Vec<(usize, usize)>, usize) {
+    for sitting_out in 0..player_count {
+        if let Some(sizes) = team_sizes(player_count - sitting_out, team_size) {
+            let games = sizes.chunks(2).map(|pair| (pair[0], pair[1])).collect();
+            return (games, sitting_out);
+        }
+    }
+    (Vec::new(), player_count)
+}
+
+/// Team sizes, largest first, for an even number of teams holding `n` players
+fn team_sizes(n: usize, team_size: usize) -> Option<Vec<usize>> {
+    if team_size <= 1 {
+        return (n >= 2 && n.is_multiple_of(2)).then(|| vec![1; n]);
+    }
+
+    (0..=n / 3)
+        .filter_map(|triples| {
+            let rest = n - 3 * triples;
+            let doubles = rest / 2;
+            let teams = triples + doubles;
+            (rest.is_multiple_of(2) && teams >= 2 && teams.is_multiple_of(2)).then_some((triples, doubles))
+        })
+        .max_by_key(|&(triples, doubles)| if team_size >= 3 { triples } else { doubles })
+        .map(|(triples, doubles)| {
+            let mut sizes = vec![3; triples];
+            sizes.extend(vec![2; doubles]);
+            sizes
+        })
+}
+
+/// Seats the players in the games of a round.
+///
+/// Players are seated in the order given (shuffle them for a random draw),
+/// then two players are swapped whenever that lowers the cost of repeated
+/// teammates and repeated opponents, until no swap helps. `teammates` and
+/// `opponents` count how often each pair of players already met, in both
+/// orders.
+pub fn form_games(
+    players: &[String],
+    sizes: &[(usize, usize)],
+    teammates: &HashMap<(String, String), i64>,
+    opponents: &HashMap<(String, String), i64>,
+) -> Vec<(Vec<String>, Vec<String>)> {
+    let n = players.len();
+    let pair_costs = |history: &HashMap<(String, String), i64>, weight: i64| -> Vec<Vec<i64>> {
+        (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| {
+                        let met = history
+                            .get(&(players[i].clone(), players[j].clone()))
+                            .copied()
+                            .unwrap_or(0);
+                        met * met * weight
+                    })
+                    .collect()
+            })
+            .collect()
+    };
+    let teammate_cost = pair_costs(teammates, REPEAT_TEAMMATE_COST);
+    let opponent_cost = pair_costs(opponents, REPEAT_OPPONENT_COST);
+
+    // Seat positions: the game and the side (0 or 1) of each one
+    let mut positions: Vec<(usize, usize)> = Vec::with_capacity(n);
+    let mut game_positions: Vec<[Vec<usize>; 2]> = Vec::with_capacity(sizes.len());
+    for (game, &(size1, size2)) in sizes.iter().enumerate() {
+        let mut sides = [Vec::new(), Vec::new()];
+        for (side, size) in [size1, size2].into_iter().enumerate() {
+            for _ in 0..size {
+                sides[side].push(positions.len());
+                positions.push((game, side));
+            }
+        }
+        game_positions.push(sides);
+    }
+    let seated = positions.len().min(n);
+
+    let mut seating: Vec<usize> = (0..seated).collect();
+    let game_cost = |seating: &[usize], game: usize| -> i64 {
+        let [side1, side2] = &game_positions[game];
+        let mut cost = 0;
+        for side in [side1, side2] {
+            for (a, &p) in side.iter().enumerate() {
+                for &q in &side[a + 1..] {
+                    cost += teammate_cost[seating[p]][seating[q]];
+                }
+            }
+        }
+        for &p in side1 {
+            for &q in side2 {
+                cost += opponent_cost[seating[p]][seating[q]];
+            }
+        }
+        cost
+    };
+
+    for _ in 0..MAX_PASSES {
+        let mut improved = false;
+        for p in 0..seated {
+            for q in (p + 1)..seated {
+                if positions[p] == positions[q] {
+                    continue;
+                }
+                let (game_p, game_q) = (positions[p].0, positions[q].0);
+                let cost = |seating: &[usize]| {
+                    game_cost(seating, game_p)
+                        + if game_q != game_p {
+                            game_cost(seating, game_q)
+                        } else {
+                            0
+                        }
+                };
+
+                let before = cost(&seating);
+                seating.swap(p, q);
+                if cost(&seating) < before {
+                    improved = true;
+                } else {
+                    seating.swap(p, q);
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+
+    game_positions
+        .iter()
+        .map(|[side1, side2]| {
+            let team = |side: &[usize]| -> Vec<String> {
+                side.iter().map(|&p| players[seating[p]].clone()).collect()
+            };
+            (team(side1), team(side2))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn players(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    /// Pairs met once, in both orders
+    fn met(pairs: &[(&str, &str)]) -> HashMap<(String, String), i64> {
+        pairs
+            .iter()
+            .flat_map(|&(a, b)| [((a.to_string(), b.to_string()), 1), ((b.to_string(), a.to_string()), 1)])
+            .collect()
+    }
+
+    #[test]
+    fn doublette_melee_completes_with_triplettes() {
+        assert_eq!(game_sizes(8, 2), (vec![(2, 2), (2, 2)], 0));
+        assert_eq!(game_sizes(9, 2), (vec![(3, 2), (2, 2)], 0));
+        assert_eq!(game_sizes(10, 2), (vec![(3, 3), (2, 2)], 0));
+        // Seven players cannot make an even number of teams: one sits out
+        assert_eq!(game_sizes(7, 2), (vec![(3, 3)], 1));
+    }
+
+    #[test]
+    fn triplette_melee_completes_with_doublettes() {
+        assert_eq!(game_sizes(12, 3), (vec![(3, 3), (3, 3)], 0));
+        assert_eq!(game_sizes(10, 3), (vec![(3, 3), (2, 2)], 0));
+        assert_eq!(game_sizes(9, 3), (vec![(3, 2), (2, 2)], 0));
+        assert_eq!(game_sizes(7, 3), (vec![(3, 3)], 1));
+    }
+
+    #[test]
+    fn tete_a_tete_sits_out_the_odd_player() {
+        assert_eq!(game_sizes(6, 1), (vec![(1, 1); 3], 0));
+        assert_eq!(game_sizes(7, 1), (vec![(1, 1); 3], 1));
+        assert_eq!(game_sizes(1, 1), (Vec::new(), 1));
+    }
+
+    #[test]
+    fn former_teammates_are_split_up() {
+        let field = players(&["a", "b", "c", "d"]);
+        let games = form_games(&field, &[(2, 2)], &met(&[("a", "b"), ("c", "d")]), &HashMap::new());
+
+        let (team1, team2) = &games[0];
+        for team in [team1, team2] {
+            assert_eq!(team.len(), 2);
+            assert!(!(team.contains(&"a".to_string()) && team.contains(&"b".to_string())));
+            assert!(!(team.contains(&"c".to_string()) && team.contains(&"d".to_string())));
+        }
+    }
+
+    #[test]
+    fn every_player_is_seated_once_without_repeating_a_teammate() {
+        let field = players(&["a", "b", "c", "d", "e", "f", "g", "h", "i"]);
+        let (sizes, sitting_out) = game_sizes(field.len(), 2);
+        assert_eq!(sitting_out, 0);
+        // Last round's teams, seated in the same order again
+        let teammates = met(&[("a", "b"), ("a", "c"), ("b", "c"), ("d", "e"), ("f", "g"), ("h", "i")]);
+        let games = form_games(&field, &sizes, &teammates, &HashMap::new());
+
+        let mut seated: Vec<&String> = games.iter().flat_map(|(t1, t2)| t1.iter().chain(t2)).collect();
+        seated.sort();
+        assert_eq!(seated, field.iter().collect::<Vec<_>>());
+        for (team1, team2) in &games {
+            for team in [team1, team2] {
+                for (i, p) in team.iter().enumerate() {
+                    for q in &team[i + 1..] {
+                        assert!(!teammates.contains_key(&(p.clone(), q.clone())), "{} and {} played together again", p, q);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod assignment;
+pub mod avoidance;
+pub mod matching;
+pub mod melee;
@@ -13,7 +13,8 @@ import {
   DialogFooter,
 } from '../../components/ui';
 import type { BracketMatch } from '../../types';
-import { formatTeamName } from '../../lib/utils';
+import { formatCommandError, formatTeamName } from '../../lib/utils';
+import { EndsDialog } from '../pairing/EndsDialog';
 
 interface BracketDisplayProps {
   bracketId: string;
@@ -33,12 +34,18 @@ export function BracketDisplay({ bracketId, bracketSize }: BracketDisplayProps)
     loading,
     fetchMatchesForBracket,
     updateMatchScore,
+    startMatch,
+    fetchMatchEnds,
+    recordMatchEnd,
+    undoLastMatchEnd,
   } = useTournamentStore();
 
   const [selectedMatch, setSelectedMatch] = useState<BracketMatch | null>(null);
   const [scoreDialogOpen, setScoreDialogOpen] = useState(false);
   const [team1Score, setTeam1Score] = useState('');
   const [team2Score, setTeam2Score] = useState('');
+  const [scoreError, setScoreError] = useState<string | null>(null);
+  const [endsOpen, setEndsOpen] = useState(false);
   const containerRef = useRef<HTMLDivElement>(null);
 
   useEffect(() => {
@@ -78,8 +85,8 @@ export function BracketDisplay({ bracketId, bracketSize }: BracketDisplayProps)
     // Must have both teams to enter a score
     if (!match.team1Id || !match.team2Id) return false;
 
-    // BYE matches can't be edited
-    if (match.isBye) return false;
+    // BYE matches and walkovers can't be edited
+    if (match.isBye || match.isForfeit) return false;
 
     // If there's a next match, check if it has been scored
     if (match.nextMatchId) {
@@ -98,6 +105,7 @@ export function BracketDisplay({ bracketId, bracketSize }: BracketDisplayProps)
     setSelectedMatch(match);
     setTeam1Score(match.team1Score?.toString() || '');
     setTeam2Score(match.team2Score?.toString() || '');
+    setScoreError(null);
     setScoreDialogOpen(true);
   };
 
@@ -111,21 +119,32 @@ export function BracketDisplay({ bracketId, bracketSize }: BracketDisplayProps)
       return;
     }
 
-    if (s1 === s2) {
-      alert('Scores cannot be tied. One team must win.');
-      return;
-    }
-
     try {
       await updateMatchScore(selectedMatch.id, s1, s2);
       await fetchMatchesForBracket(bracketId);
       setScoreDialogOpen(false);
       setSelectedMatch(null);
     } catch (error) {
-      console.error('Failed to save score:', error);
+      setScoreError(formatCommandError(error, t));
     }
   };
 
+  const handleStartMatch = async () => {
+    if (!selectedMatch) return;
+    try {
+      await startMatch(selectedMatch.id);
+      setSelectedMatch({ ...selectedMatch, startedAt: new Date().toISOString() });
+    } catch (error) {
+      setScoreError(formatCommandError(error, t));
+    }
+  };
+
+  // Ends are recorded in their own dialog, which replaces the score entry
+  const openEnds = () => {
+    setScoreDialogOpen(false);
+    setEndsOpen(true);
+  };
+
   // Calculate the vertical spacing for a round based on the number of matches
   const getMatchSpacing = (roundNumber: number) => {
     // Each round has half the matches of the previous round
@@ -179,7 +198,9 @@ export function BracketDisplay({ bracketId, bracketSize }: BracketDisplayProps)
                         <CardContent className="p-2 h-full flex flex-col justify-between">
                           {/* Court number */}
                           <div className="text-xs text-gray-400 text-center">
-                            {match.courtNumber ? `${t('pairing.court')} ${match.courtNumber}` : ''}
+                            {match.isForfeit
+                              ? t('brackets.walkover')
+                              : match.courtNumber ? `${t('pairing.court')} ${match.courtNumber}` : ''}
                           </div>
 
                           {/* Team 1 */}
@@ -361,9 +382,23 @@ export function BracketDisplay({ bracketId, bracketSize }: BracketDisplayProps)
                   />
                 </div>
               </div>
+              {scoreError && (
+                <div className="rounded-md bg-red-50 p-3 text-sm text-red-700">{scoreError}</div>
+              )}
             </div>
           )}
           <DialogFooter>
+            {selectedMatch && selectedMatch.winnerId === null && (
+              selectedMatch.startedAt ? (
+                <Button variant="ghost" onClick={openEnds}>
+                  {t('ends.title')}
+                </Button>
+              ) : (
+                <Button variant="ghost" onClick={handleStartMatch}>
+                  {t('brackets.startMatch')}
+                </Button>
+              )
+            )}
             <Button variant="secondary" onClick={() => setScoreDialogOpen(false)}>
               {t('common.cancel')}
             </Button>
@@ -371,6 +406,16 @@ export function BracketDisplay({ bracketId, bracketSize }: BracketDisplayProps)
           </DialogFooter>
         </DialogContent>
       </Dialog>
+
+      <EndsDialog
+        open={endsOpen}
+        onOpenChange={setEndsOpen}
+        team1Name={getTeamName(selectedMatch?.team1Id)}
+        team2Name={getTeamName(selectedMatch?.team2Id)}
+        load={() => fetchMatchEnds(selectedMatch!.id)}
+        record={(team1Points, team2Points) => recordMatchEnd(selectedMatch!.id, team1Points, team2Points)}
+        undo={() => undoLastMatchEnd(selectedMatch!.id)}
+      />
     </div>
   );
 }
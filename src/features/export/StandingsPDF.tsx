@@ -125,7 +125,10 @@ export function StandingsPDF({ tournament, teams, standings, translations: t }:
                 key={standing.id}
                 style={[styles.tableRow, isTopTeam ? styles.topRow : {}]}
               >
-                <Text style={[styles.rankCol, styles.bold]}>{standing.rank}</Text>
+                <Text style={[styles.rankCol, styles.bold]}>
+                  {standing.rank}
+                  {standing.isTied ? '=' : ''}
+                </Text>
                 <Text style={styles.teamCol}>{getTeamName(standing.teamId)}</Text>
                 <Text style={styles.statCol}>{standing.wins}</Text>
                 <Text style={styles.statCol}>{standing.losses}</Text>
@@ -0,0 +1,189 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useTournamentStore } from '../../stores/tournamentStore';
+import {
+  Button,
+  Card,
+  CardContent,
+  Input,
+} from '../../components/ui';
+import type { Player } from '../../types';
+import { formatCommandError } from '../../lib/utils';
+
+interface MeleeGamesProps {
+  roundId: string;
+  tournamentId: string;
+  isComplete: boolean;
+}
+
+export function MeleeGames({ roundId, tournamentId, isComplete }: MeleeGamesProps) {
+  const { t } = useTranslation();
+  const {
+    meleeGames,
+    fetchMeleeGames,
+    updateMeleeGameScore,
+    completeRound,
+    fetchPlayerStandings,
+    fetchQualifyingRounds,
+  } = useTournamentStore();
+
+  const [scores, setScores] = useState<Record<string, { team1: string; team2: string }>>({});
+  const [initialLoading, setInitialLoading] = useState(true);
+  const [scoreErrors, setScoreErrors] = useState<Record<string, string>>({});
+
+  useEffect(() => {
+    // Reset scores when switching rounds
+    setScores({});
+    setScoreErrors({});
+    setInitialLoading(true);
+    fetchMeleeGames(roundId).finally(() => setInitialLoading(false));
+  }, [roundId, fetchMeleeGames]);
+
+  useEffect(() => {
+    setScores((prevScores) => {
+      const newScores: Record<string, { team1: string; team2: string }> = {};
+      meleeGames.forEach((game) => {
+        // Keep existing local scores to preserve user edits
+        newScores[game.id] = prevScores[game.id] || {
+          team1: game.team1Score?.toString() || '',
+          team2: game.team2Score?.toString() || '',
+        };
+      });
+      return newScores;
+    });
+  }, [meleeGames]);
+
+  const teamNames = (players: Player[]) => players.map((p) => p.name).join(' · ');
+
+  const handleScoreChange = (gameId: string, team: 'team1' | 'team2', value: string) => {
+    setScores((prev) => ({
+      ...prev,
+      [gameId]: {
+        ...prev[gameId],
+        [team]: value,
+      },
+    }));
+  };
+
+  // Returns whether the score was accepted
+  const handleSaveScore = async (gameId: string) => {
+    const gameScores = scores[gameId];
+    if (!gameScores) return false;
+
+    const team1Score = parseInt(gameScores.team1);
+    const team2Score = parseInt(gameScores.team2);
+
+    if (isNaN(team1Score) || isNaN(team2Score)) {
+      return false;
+    }
+
+    try {
+      await updateMeleeGameScore(gameId, team1Score, team2Score);
+      setScoreErrors((prev) => {
+        const { [gameId]: _, ...rest } = prev;
+        return rest;
+      });
+      return true;
+    } catch (error) {
+      setScoreErrors((prev) => ({ ...prev, [gameId]: formatCommandError(error, t) }));
+      return false;
+    }
+  };
+
+  const handleCompleteRound = async () => {
+    const allGamesScored = meleeGames.every((game) => {
+      if (game.isBye) return true;
+      const gameScores = scores[game.id];
+      if (!gameScores) return false;
+      return gameScores.team1 !== '' && gameScores.team2 !== '';
+    });
+
+    if (!allGamesScored) {
+      alert('Please enter scores for all games before completing the round.');
+      return;
+    }
+
+    let allScoresValid = true;
+    for (const game of meleeGames) {
+      if (!game.isBye) {
+        allScoresValid = (await handleSaveScore(game.id)) && allScoresValid;
+      }
+    }
+
+    // Every rejected score is shown on its game
+    if (!allScoresValid) return;
+
+    try {
+      await completeRound(roundId);
+      await fetchPlayerStandings(tournamentId);
+      await fetchQualifyingRounds(tournamentId);
+    } catch (error) {
+      console.error('Failed to complete round:', error);
+    }
+  };
+
+  const hasWaves = meleeGames.some((g) => g.slotNumber > 1);
+
+  if (initialLoading) {
+    return <div className="text-center py-4 text-gray-500">{t('common.loading')}</div>;
+  }
+
+  return (
+    <div className="space-y-4">
+      <div className="grid gap-4 sm:grid-cols-2 lg:grid-cols-3">
+        {meleeGames.map((game) => (
+          <Card key={game.id}>
+            <CardContent className="py-4">
+              {game.isBye ? (
+                <div className="text-center">
+                  <div className="font-medium">{teamNames(game.team1)}</div>
+                  <div className="text-sm text-gray-500 mt-2">{t('players.sittingOut')}</div>
+                </div>
+              ) : (
+                <>
+                  <div className="text-xs text-gray-500 mb-2">
+                    {t('pairing.court')} {game.courtNumber}
+                    {hasWaves && ` · ${t('pairing.wave')} ${game.slotNumber}`}
+                  </div>
+                  <div className="space-y-3">
+                    {(['team1', 'team2'] as const).map((side, index) => (
+                      <div key={side}>
+                        {index === 1 && (
+                          <div className="text-center text-xs text-gray-400 mb-3">
+                            {t('pairing.vs')}
+                          </div>
+                        )}
+                        <div className="flex items-center gap-2">
+                          <div className="flex-1 font-medium">{teamNames(game[side])}</div>
+                          <Input
+                            type="number"
+                            min={0}
+                            max={13}
+                            value={scores[game.id]?.[side] || ''}
+                            onChange={(e) => handleScoreChange(game.id, side, e.target.value)}
+                            onBlur={() => handleSaveScore(game.id)}
+                            disabled={isComplete}
+                            className="w-16 text-center"
+                          />
+                        </div>
+                      </div>
+                    ))}
+                    {scoreErrors[game.id] && (
+                      <div className="text-xs text-red-600">{scoreErrors[game.id]}</div>
+                    )}
+                  </div>
+                </>
+              )}
+            </CardContent>
+          </Card>
+        ))}
+      </div>
+
+      {!isComplete && (
+        <div className="flex justify-end">
+          <Button onClick={handleCompleteRound}>{t('pairing.completeRound')}</Button>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,90 @@
+import { useEffect } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useTournamentStore } from '../../stores/tournamentStore';
+import {
+  Card,
+  Table,
+  TableHeader,
+  TableBody,
+  TableRow,
+  TableHead,
+  TableCell,
+} from '../../components/ui';
+
+interface PlayerStandingsTableProps {
+  tournamentId: string;
+}
+
+export function PlayerStandingsTable({ tournamentId }: PlayerStandingsTableProps) {
+  const { t } = useTranslation();
+  const { playerStandings, players, loading, fetchPlayerStandings, fetchPlayers } = useTournamentStore();
+
+  useEffect(() => {
+    fetchPlayers(tournamentId);
+    fetchPlayerStandings(tournamentId);
+  }, [tournamentId, fetchPlayers, fetchPlayerStandings]);
+
+  const getPlayerName = (playerId: string) =>
+    players.find((p) => p.id === playerId)?.name || '';
+
+  if (loading) {
+    return <div className="text-center py-4 text-gray-500">{t('common.loading')}</div>;
+  }
+
+  if (playerStandings.length === 0) {
+    return (
+      <Card>
+        <div className="py-8 text-center text-gray-500">
+          No standings yet. Complete a qualifying round to see standings.
+        </div>
+      </Card>
+    );
+  }
+
+  // Sort standings by rank
+  const sortedStandings = [...playerStandings].sort((a, b) => a.rank - b.rank);
+
+  return (
+    <Card>
+      <Table>
+        <TableHeader>
+          <TableRow>
+            <TableHead className="w-16">{t('pairing.rank')}</TableHead>
+            <TableHead>{t('players.player')}</TableHead>
+            <TableHead className="text-center">{t('pairing.wins')}</TableHead>
+            <TableHead className="text-center">{t('pairing.losses')}</TableHead>
+            <TableHead className="text-center">{t('pairing.pointsFor')}</TableHead>
+            <TableHead className="text-center">{t('pairing.pointsAgainst')}</TableHead>
+            <TableHead className="text-center">{t('pairing.differential')}</TableHead>
+          </TableRow>
+        </TableHeader>
+        <TableBody>
+          {sortedStandings.map((standing) => (
+            <TableRow key={standing.id}>
+              <TableCell className="font-medium">{standing.rank}</TableCell>
+              <TableCell className="font-medium">{getPlayerName(standing.playerId)}</TableCell>
+              <TableCell className="text-center">{standing.wins}</TableCell>
+              <TableCell className="text-center">{standing.losses}</TableCell>
+              <TableCell className="text-center">{standing.pointsFor}</TableCell>
+              <TableCell className="text-center">{standing.pointsAgainst}</TableCell>
+              <TableCell className="text-center">
+                <span
+                  className={
+                    standing.differential > 0
+                      ? 'text-green-600'
+                      : standing.differential < 0
+                      ? 'text-red-600'
+                      : ''
+                  }
+                >
+                  {standing.differential > 0 ? '+' : ''}
+                  {standing.differential}
+                </span>
+              </TableCell>
+            </TableRow>
+          ))}
+        </TableBody>
+      </Table>
+    </Card>
+  );
+}
@@ -0,0 +1,168 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useForm } from 'react-hook-form';
+import { useTournamentStore } from '../../stores/tournamentStore';
+import {
+  Button,
+  Card,
+  CardContent,
+  Input,
+  Table,
+  TableHeader,
+  TableBody,
+  TableRow,
+  TableHead,
+  TableCell,
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogFooter,
+} from '../../components/ui';
+import type { Player, PlayerFormData } from '../../types';
+
+interface PlayersListProps {
+  tournamentId: string;
+}
+
+export function PlayersList({ tournamentId }: PlayersListProps) {
+  const { t } = useTranslation();
+  const { players, loading, fetchPlayers, createPlayer, deletePlayer } = useTournamentStore();
+
+  const [deleteDialogOpen, setDeleteDialogOpen] = useState(false);
+  const [selectedPlayer, setSelectedPlayer] = useState<Player | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const {
+    register,
+    handleSubmit,
+    reset,
+    formState: { errors },
+  } = useForm<PlayerFormData>({
+    defaultValues: { name: '', club: '' },
+  });
+
+  useEffect(() => {
+    fetchPlayers(tournamentId);
+  }, [tournamentId, fetchPlayers]);
+
+  const handleAddPlayer = async (data: PlayerFormData) => {
+    setError(null);
+    try {
+      await createPlayer({
+        tournamentId,
+        name: data.name,
+        club: data.club || null,
+      });
+      reset();
+    } catch (error) {
+      setError(String(error));
+    }
+  };
+
+  const handleDeletePlayer = async () => {
+    if (!selectedPlayer) return;
+    setError(null);
+    try {
+      await deletePlayer(selectedPlayer.id);
+    } catch (error) {
+      setError(String(error));
+    }
+    setDeleteDialogOpen(false);
+    setSelectedPlayer(null);
+  };
+
+  return (
+    <div className="space-y-4">
+      <h2 className="text-lg font-semibold text-gray-900">
+        {t('players.title')} ({players.length})
+      </h2>
+
+      <form onSubmit={handleSubmit(handleAddPlayer)} className="flex items-end gap-2">
+        <div className="flex-1">
+          <Input
+            label={t('players.name')}
+            {...register('name', {
+              validate: (value) => value.trim() !== '' || t('validation.required'),
+            })}
+            error={errors.name?.message}
+          />
+        </div>
+        <div className="flex-1">
+          <Input label={t('players.club')} {...register('club')} />
+        </div>
+        <Button type="submit" disabled={loading}>
+          {t('players.add')}
+        </Button>
+      </form>
+
+      {error && (
+        <div className="rounded-md bg-red-50 p-4 text-sm text-red-700">{error}</div>
+      )}
+
+      {players.length === 0 ? (
+        <Card>
+          <CardContent className="py-12 text-center">
+            <p className="text-gray-500">{t('players.noPlayers')}</p>
+          </CardContent>
+        </Card>
+      ) : (
+        <Card>
+          <Table>
+            <TableHeader>
+              <TableRow>
+                <TableHead>{t('players.name')}</TableHead>
+                <TableHead>{t('players.club')}</TableHead>
+                <TableHead className="w-24">{t('common.actions')}</TableHead>
+              </TableRow>
+            </TableHeader>
+            <TableBody>
+              {players.map((player) => (
+                <TableRow key={player.id}>
+                  <TableCell className="font-medium">{player.name}</TableCell>
+                  <TableCell>{player.club || '-'}</TableCell>
+                  <TableCell>
+                    <Button
+                      variant="ghost"
+                      size="sm"
+                      onClick={() => {
+                        setSelectedPlayer(player);
+                        setDeleteDialogOpen(true);
+                      }}
+                    >
+                      {t('common.delete')}
+                    </Button>
+                  </TableCell>
+                </TableRow>
+              ))}
+            </TableBody>
+          </Table>
+        </Card>
+      )}
+
+      {/* Delete Confirmation Dialog */}
+      <Dialog open={deleteDialogOpen} onOpenChange={setDeleteDialogOpen}>
+        <DialogContent>
+          <DialogHeader>
+            <DialogTitle>{t('common.delete')}</DialogTitle>
+          </DialogHeader>
+          <p className="text-sm text-gray-500">{t('players.deleteConfirm')}</p>
+          <DialogFooter>
+            <Button
+              variant="secondary"
+              onClick={() => {
+                setDeleteDialogOpen(false);
+                setSelectedPlayer(null);
+              }}
+            >
+              {t('common.cancel')}
+            </Button>
+            <Button variant="danger" onClick={handleDeletePlayer}>
+              {t('common.delete')}
+            </Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+    </div>
+  );
+}
@@ -0,0 +1,3 @@
+export { PlayersList } from './PlayersList';
+export { MeleeGames } from './MeleeGames';
+export { PlayerStandingsTable } from './PlayerStandingsTable';
@@ -0,0 +1,61 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useTournamentStore } from '../../stores/tournamentStore';
+import {
+  Card,
+  Table,
+  TableHeader,
+  TableBody,
+  TableRow,
+  TableHead,
+  TableCell,
+} from '../../components/ui';
+import type { CourtDuration } from '../../types';
+
+interface CourtDurationsTableProps {
+  tournamentId: string;
+}
+
+export function CourtDurationsTable({ tournamentId }: CourtDurationsTableProps) {
+  const { t } = useTranslation();
+  const { fetchCourtDurations } = useTournamentStore();
+
+  const [durations, setDurations] = useState<CourtDuration[]>([]);
+
+  useEffect(() => {
+    fetchCourtDurations(tournamentId).then(setDurations).catch(() => setDurations([]));
+  }, [tournamentId, fetchCourtDurations]);
+
+  if (durations.length === 0) {
+    return (
+      <Card>
+        <div className="py-8 text-center text-gray-500">{t('pairing.noCourtTimes')}</div>
+      </Card>
+    );
+  }
+
+  return (
+    <Card>
+      <Table>
+        <TableHeader>
+          <TableRow>
+            <TableHead>{t('pairing.court')}</TableHead>
+            <TableHead className="text-center">{t('pairing.gamesPlayed')}</TableHead>
+            <TableHead className="text-center">{t('pairing.averageMinutes')}</TableHead>
+            <TableHead className="text-center">{t('pairing.longestMinutes')}</TableHead>
+          </TableRow>
+        </TableHeader>
+        <TableBody>
+          {durations.map((court) => (
+            <TableRow key={court.courtNumber}>
+              <TableCell className="font-medium">{court.courtNumber}</TableCell>
+              <TableCell className="text-center">{court.gamesPlayed}</TableCell>
+              <TableCell className="text-center">{Math.round(court.averageMinutes)}</TableCell>
+              <TableCell className="text-center">{Math.round(court.longestMinutes)}</TableCell>
+            </TableRow>
+          ))}
+        </TableBody>
+      </Table>
+    </Card>
+  );
+}
@@ -0,0 +1,167 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useTournamentStore } from '../../stores/tournamentStore';
+import {
+  Button,
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogFooter,
+  Table,
+  TableHeader,
+  TableBody,
+  TableRow,
+  TableHead,
+  TableCell,
+} from '../../components/ui';
+import type { EndsSummary } from '../../types';
+
+interface EndsDialogProps {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+  team1Name: string;
+  team2Name: string;
+  load: () => Promise<EndsSummary>;
+  record: (team1Points: number, team2Points: number) => Promise<EndsSummary>;
+  undo: () => Promise<EndsSummary>;
+}
+
+const formatTime = (value: string) =>
+  new Date(value).toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' });
+
+// End-by-end score sheet of a qualifying game or a bracket match
+export function EndsDialog({ open, onOpenChange, team1Name, team2Name, load, record, undo }: EndsDialogProps) {
+  const { t } = useTranslation();
+  const { currentTournament } = useTournamentStore();
+
+  const [summary, setSummary] = useState<EndsSummary | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  // Load the ends each time the dialog opens
+  useEffect(() => {
+    if (!open) return;
+    setSummary(null);
+    setError(null);
+    load()
+      .then(setSummary)
+      .catch((e) => setError(String(e)));
+  }, [open]);
+
+  // Three boules each in singles, six a side otherwise
+  const maxPoints = currentTournament?.format === 'single' ? 3 : 6;
+  const extraEnds = currentTournament?.extraEnds ?? 1;
+  const targetScore = currentTournament?.targetScore ?? 13;
+
+  const run = async (action: () => Promise<EndsSummary>) => {
+    setError(null);
+    try {
+      setSummary(await action());
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const timeCalled = summary?.timeCalledAt && new Date(summary.timeCalledAt) <= new Date();
+
+  let team1Running = 0;
+  let team2Running = 0;
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>{t('ends.title')}</DialogTitle>
+        </DialogHeader>
+
+        {summary && (
+          <div className="space-y-4">
+            {summary.timeCalledAt && (
+              <div className={`text-sm ${timeCalled ? 'text-amber-700' : 'text-gray-500'}`}>
+                {timeCalled
+                  ? t('ends.timeCalled', {
+                      time: formatTime(summary.timeCalledAt),
+                      played: summary.endsAfterTime,
+                      required: extraEnds + 1,
+                    })
+                  : t('ends.timeCalledAt', { time: formatTime(summary.timeCalledAt) })}
+              </div>
+            )}
+
+            <Table>
+              <TableHeader>
+                <TableRow>
+                  <TableHead className="w-12">#</TableHead>
+                  <TableHead className="text-center">{team1Name}</TableHead>
+                  <TableHead className="text-center">{team2Name}</TableHead>
+                  <TableHead className="text-right">{t('ends.recordedAt')}</TableHead>
+                </TableRow>
+              </TableHeader>
+              <TableBody>
+                {summary.ends.map((end) => {
+                  team1Running += end.team1Points;
+                  team2Running += end.team2Points;
+                  return (
+                    <TableRow key={end.id}>
+                      <TableCell>{end.endNumber}</TableCell>
+                      <TableCell className="text-center">
+                        {end.team1Points > 0 ? `+${end.team1Points}` : ''} ({team1Running})
+                      </TableCell>
+                      <TableCell className="text-center">
+                        {end.team2Points > 0 ? `+${end.team2Points}` : ''} ({team2Running})
+                      </TableCell>
+                      <TableCell className="text-right text-gray-500">{formatTime(end.recordedAt)}</TableCell>
+                    </TableRow>
+                  );
+                })}
+              </TableBody>
+            </Table>
+
+            {summary.isDecided ? (
+              <div className="text-sm text-green-700">
+                {t('ends.decided', {
+                  team1: Math.min(summary.team1Total, targetScore),
+                  team2: Math.min(summary.team2Total, targetScore),
+                })}
+              </div>
+            ) : (
+              <div className="space-y-2">
+                {[team1Name, team2Name].map((name, index) => (
+                  <div key={index} className="flex items-center gap-2">
+                    <div className="flex-1 font-medium truncate">{name}</div>
+                    {Array.from({ length: maxPoints }, (_, i) => i + 1).map((points) => (
+                      <Button
+                        key={points}
+                        variant="secondary"
+                        size="sm"
+                        onClick={() => run(() => (index === 0 ? record(points, 0) : record(0, points)))}
+                      >
+                        +{points}
+                      </Button>
+                    ))}
+                  </div>
+                ))}
+                <Button variant="ghost" size="sm" onClick={() => run(() => record(0, 0))}>
+                  {t('ends.deadEnd')}
+                </Button>
+              </div>
+            )}
+          </div>
+        )}
+
+        {error && <div className="rounded-md bg-red-50 p-3 text-sm text-red-700">{error}</div>}
+
+        <DialogFooter>
+          <Button
+            variant="secondary"
+            onClick={() => run(undo)}
+            disabled={!summary || summary.ends.length === 0}
+          >
+            {t('ends.undo')}
+          </Button>
+          <Button onClick={() => onOpenChange(false)}>{t('common.close')}</Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -0,0 +1,207 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useTournamentStore } from '../../stores/tournamentStore';
+import {
+  Button,
+  Input,
+  Select,
+  SelectItem,
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogFooter,
+} from '../../components/ui';
+import { formatCommandError } from '../../lib/utils';
+import type { OverrideResult, OverrideWarning, QualifyingGame } from '../../types';
+
+interface OverrideDialogProps {
+  game: QualifyingGame | null;
+  onClose: () => void;
+  getTeamName: (teamId: string | null | undefined) => string;
+}
+
+const isOpen = (game: QualifyingGame) => game.isBye || (game.team1Score === null && !game.isForfeit);
+
+// Swaps a team with one of another game of the round, or moves the game to another court
+export function OverrideDialog({ game, onClose, getTeamName }: OverrideDialogProps) {
+  const { t } = useTranslation();
+  const { qualifyingGames, courts, currentTournament, fetchCourts, swapTeams, moveGameToCourt } =
+    useTournamentStore();
+
+  const [mode, setMode] = useState<'swap' | 'court'>('swap');
+  const [teamId, setTeamId] = useState('');
+  const [target, setTarget] = useState('');
+  const [courtNumber, setCourtNumber] = useState('');
+  const [reason, setReason] = useState('');
+  const [warnings, setWarnings] = useState<OverrideWarning[] | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (!game) return;
+    setMode('swap');
+    setTeamId(game.team1Id || '');
+    setTarget('');
+    setCourtNumber('');
+    setReason('');
+    setWarnings(null);
+    setError(null);
+    if (currentTournament) {
+      fetchCourts(currentTournament.id);
+    }
+  }, [game]);
+
+  // Teams of the other games still to be played, as "gameId:teamId"
+  const targets = qualifyingGames
+    .filter((g) => game && g.id !== game.id && isOpen(g) && !(game.isBye && g.isBye))
+    .flatMap((g) => [g.team1Id, g.team2Id].filter(Boolean).map((id) => ({ gameId: g.id, teamId: id! })));
+
+  const activeCourts = courts.filter((c) => c.isActive && c.courtNumber !== game?.courtNumber);
+
+  const warningText = (warning: OverrideWarning) =>
+    t(`overrides.warnings.${warning.kind}`, {
+      team1: getTeamName(warning.teamIds[0]),
+      team2: getTeamName(warning.teamIds[1]),
+      court: warning.courtNumber,
+    });
+
+  const submit = async (confirm: boolean) => {
+    if (!game) return;
+
+    setError(null);
+    try {
+      let result: OverrideResult;
+      if (mode === 'swap') {
+        const [targetGameId, targetTeamId] = target.split(':');
+        result = await swapTeams(game.id, teamId, targetGameId, targetTeamId, reason || null, confirm);
+      } else {
+        result = await moveGameToCourt(game.id, parseInt(courtNumber), reason || null, confirm);
+      }
+
+      if (result.applied) {
+        onClose();
+      } else {
+        setWarnings(result.warnings);
+      }
+    } catch (e) {
+      setError(formatCommandError(e, t));
+    }
+  };
+
+  const canSubmit = mode === 'swap' ? teamId !== '' && target !== '' : courtNumber !== '';
+
+  return (
+    <Dialog open={game !== null} onOpenChange={(open) => !open && onClose()}>
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>{t('overrides.title')}</DialogTitle>
+        </DialogHeader>
+
+        {game && (
+          <div className="space-y-3">
+            {!game.isBye && (
+              <div className="flex gap-2">
+                <Button
+                  variant={mode === 'swap' ? 'primary' : 'secondary'}
+                  size="sm"
+                  onClick={() => {
+                    setMode('swap');
+                    setWarnings(null);
+                  }}
+                >
+                  {t('overrides.swapTeams')}
+                </Button>
+                <Button
+                  variant={mode === 'court' ? 'primary' : 'secondary'}
+                  size="sm"
+                  onClick={() => {
+                    setMode('court');
+                    setWarnings(null);
+                  }}
+                >
+                  {t('overrides.moveCourt')}
+                </Button>
+              </div>
+            )}
+
+            {mode === 'swap' ? (
+              <>
+                <Select
+                  label={t('overrides.team')}
+                  value={teamId}
+                  onValueChange={(v) => {
+                    setTeamId(v);
+                    setWarnings(null);
+                  }}
+                >
+                  {[game.team1Id, game.team2Id].filter(Boolean).map((id) => (
+                    <SelectItem key={id} value={id!}>
+                      {getTeamName(id)}
+                    </SelectItem>
+                  ))}
+                </Select>
+                <Select
+                  label={t('overrides.swapWith')}
+                  value={target}
+                  onValueChange={(v) => {
+                    setTarget(v);
+                    setWarnings(null);
+                  }}
+                >
+                  {targets.map((option) => (
+                    <SelectItem key={option.teamId} value={`${option.gameId}:${option.teamId}`}>
+                      {getTeamName(option.teamId)}
+                    </SelectItem>
+                  ))}
+                </Select>
+              </>
+            ) : (
+              <Select
+                label={t('pairing.court')}
+                value={courtNumber}
+                onValueChange={(v) => {
+                  setCourtNumber(v);
+                  setWarnings(null);
+                }}
+              >
+                {activeCourts.map((court) => (
+                  <SelectItem key={court.courtNumber} value={court.courtNumber.toString()}>
+                    {court.label || court.courtNumber}
+                  </SelectItem>
+                ))}
+              </Select>
+            )}
+
+            <Input label={t('overrides.reason')} value={reason} onChange={(e) => setReason(e.target.value)} />
+
+            {warnings && (
+              <div className="rounded-md bg-amber-50 p-3 text-sm text-amber-800">
+                <p className="font-medium">{t('overrides.warningsTitle')}</p>
+                <ul className="list-disc pl-5">
+                  {warnings.map((warning, index) => (
+                    <li key={index}>{warningText(warning)}</li>
+                  ))}
+                </ul>
+              </div>
+            )}
+
+            {error && <div className="rounded-md bg-red-50 p-3 text-sm text-red-700">{error}</div>}
+          </div>
+        )}
+
+        <DialogFooter>
+          <Button variant="secondary" onClick={onClose}>
+            {t('common.cancel')}
+          </Button>
+          {warnings ? (
+            <Button onClick={() => submit(true)}>{t('overrides.applyAnyway')}</Button>
+          ) : (
+            <Button onClick={() => submit(false)} disabled={!canSubmit}>
+              {t('overrides.apply')}
+            </Button>
+          )}
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
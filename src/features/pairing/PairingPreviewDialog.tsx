@@ -0,0 +1,157 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useTournamentStore } from '../../stores/tournamentStore';
+import {
+  Button,
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogFooter,
+  Table,
+  TableHeader,
+  TableBody,
+  TableRow,
+  TableHead,
+  TableCell,
+} from '../../components/ui';
+import { formatTeamName } from '../../lib/utils';
+import type { PairingPreview, QualifyingRound } from '../../types';
+
+interface PairingPreviewDialogProps {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+  tournamentId: string;
+  onAccepted: (round: QualifyingRound) => void;
+}
+
+// The next round as it would be drawn, to accept or reroll
+export function PairingPreviewDialog({ open, onOpenChange, tournamentId, onAccepted }: PairingPreviewDialogProps) {
+  const { t } = useTranslation();
+  const { teams, previewPairings, acceptPairingPreview } = useTournamentStore();
+
+  const [preview, setPreview] = useState<PairingPreview | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  const reroll = async () => {
+    setBusy(true);
+    setError(null);
+    try {
+      setPreview(await previewPairings(tournamentId));
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  useEffect(() => {
+    if (!open) return;
+    setPreview(null);
+    reroll();
+  }, [open]);
+
+  const accept = async () => {
+    if (!preview) return;
+    setBusy(true);
+    setError(null);
+    try {
+      const round = await acceptPairingPreview(preview);
+      onAccepted(round);
+      onOpenChange(false);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const getTeamName = (teamId: string | null) => {
+    const team = teams.find((t) => t.id === teamId);
+    return formatTeamName(team?.captain);
+  };
+
+  const hasWaves = preview?.games.some((g) => g.slotNumber > 1);
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>
+            {preview ? t('preview.title', { number: preview.roundNumber }) : t('pairing.previewRound')}
+          </DialogTitle>
+        </DialogHeader>
+
+        {preview && (
+          <div className="space-y-4">
+            <div className="grid grid-cols-2 gap-2 text-sm sm:grid-cols-4">
+              <div className={preview.report.repeats > 0 ? 'text-red-700' : 'text-gray-700'}>
+                {t('preview.repeats', { count: preview.report.repeats })}
+              </div>
+              <div className={preview.report.sameRegionPairs > 0 ? 'text-amber-700' : 'text-gray-700'}>
+                {t('preview.sameRegionPairs', { count: preview.report.sameRegionPairs })}
+              </div>
+              <div className="text-gray-700">
+                {t('preview.winsGap', { max: preview.report.maxWinsGap, total: preview.report.totalWinsGap })}
+              </div>
+              <div className="text-gray-700">
+                {t('preview.bye')}:{' '}
+                {preview.report.byeTeamId ? getTeamName(preview.report.byeTeamId) : t('preview.noBye')}
+              </div>
+            </div>
+
+            <div className="max-h-96 overflow-y-auto">
+              <Table>
+                <TableHeader>
+                  <TableRow>
+                    <TableHead>{t('pairing.court')}</TableHead>
+                    <TableHead>{t('preview.team1')}</TableHead>
+                    <TableHead>{t('preview.team2')}</TableHead>
+                    <TableHead className="text-center">{t('preview.winsGapColumn')}</TableHead>
+                    <TableHead />
+                  </TableRow>
+                </TableHeader>
+                <TableBody>
+                  {preview.games.map((game) => (
+                    <TableRow key={game.team1Id}>
+                      <TableCell>
+                        {game.team2Id ? game.courtNumber : '-'}
+                        {hasWaves && game.team2Id && ` · ${t('pairing.wave')} ${game.slotNumber}`}
+                      </TableCell>
+                      <TableCell className="font-medium">{getTeamName(game.team1Id)}</TableCell>
+                      <TableCell className="font-medium">
+                        {game.team2Id ? getTeamName(game.team2Id) : t('pairing.bye')}
+                      </TableCell>
+                      <TableCell className="text-center">{game.team2Id ? game.winsGap : ''}</TableCell>
+                      <TableCell className="text-xs">
+                        {game.isRepeat && <span className="mr-2 text-red-700">{t('preview.repeat')}</span>}
+                        {game.sameRegion && <span className="text-amber-700">{t('preview.sameRegion')}</span>}
+                      </TableCell>
+                    </TableRow>
+                  ))}
+                </TableBody>
+              </Table>
+            </div>
+          </div>
+        )}
+
+        {!preview && !error && <div className="py-4 text-center text-gray-500">{t('common.loading')}</div>}
+
+        {error && <div className="rounded-md bg-red-50 p-3 text-sm text-red-700">{error}</div>}
+
+        <DialogFooter>
+          <Button variant="secondary" onClick={() => onOpenChange(false)}>
+            {t('common.cancel')}
+          </Button>
+          <Button variant="secondary" onClick={reroll} disabled={busy}>
+            {t('preview.reroll')}
+          </Button>
+          <Button onClick={accept} disabled={busy || !preview}>
+            {t('preview.accept')}
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
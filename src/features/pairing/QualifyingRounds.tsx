@@ -16,7 +16,11 @@ import {
   DialogFooter,
 } from '../../components/ui';
 import { RoundGames } from './RoundGames';
+import { CourtDurationsTable } from './CourtDurationsTable';
+import { PairingPreviewDialog } from './PairingPreviewDialog';
 import { StandingsTable } from './StandingsTable';
+import { MeleeGames } from '../melee/MeleeGames';
+import { PlayerStandingsTable } from '../melee/PlayerStandingsTable';
 
 interface QualifyingRoundsProps {
   tournamentId: string;
@@ -27,24 +31,33 @@ export function QualifyingRounds({ tournamentId }: QualifyingRoundsProps) {
   const {
     qualifyingRounds,
     qualifyingGames,
+    meleeGames,
     loading,
     fetchQualifyingRounds,
     generateAllQualifyingRounds,
     generatePairings,
     deleteAllQualifyingRounds,
+    deleteLastQualifyingRound,
     fetchStandings,
+    recalculateStandings,
+    startRound,
+    fetchPlayers,
     teams,
+    players,
     currentTournament,
   } = useTournamentStore();
 
   const [selectedRoundId, setSelectedRoundId] = useState<string | null>(null);
   const [deleteDialogOpen, setDeleteDialogOpen] = useState(false);
+  const [deleteLastOnly, setDeleteLastOnly] = useState(false);
   const [deleteError, setDeleteError] = useState<string | null>(null);
+  const [previewOpen, setPreviewOpen] = useState(false);
 
   useEffect(() => {
     fetchQualifyingRounds(tournamentId);
     fetchStandings(tournamentId);
-  }, [tournamentId, fetchQualifyingRounds, fetchStandings]);
+    fetchPlayers(tournamentId);
+  }, [tournamentId, fetchQualifyingRounds, fetchStandings, fetchPlayers]);
 
   useEffect(() => {
     if (qualifyingRounds.length > 0 && !selectedRoundId) {
@@ -76,28 +89,39 @@ export function QualifyingRounds({ tournamentId }: QualifyingRoundsProps) {
   const handleDeleteRounds = async () => {
     setDeleteError(null);
     try {
-      await deleteAllQualifyingRounds(tournamentId);
-      setSelectedRoundId(null);
+      if (deleteLastOnly) {
+        await deleteLastQualifyingRound(tournamentId);
+        setSelectedRoundId(qualifyingRounds[qualifyingRounds.length - 2]?.id ?? null);
+      } else {
+        await deleteAllQualifyingRounds(tournamentId);
+        setSelectedRoundId(null);
+      }
       setDeleteDialogOpen(false);
     } catch (error) {
       setDeleteError(String(error));
     }
   };
 
-  const canGeneratePairings = teams.length >= 2;
-  const hasRounds = qualifyingRounds.length > 0;
   const pairingMethod = currentTournament?.pairingMethod || 'swiss';
+  const isMelee = pairingMethod === 'melee';
+  const canGeneratePairings = isMelee ? players.length >= 2 : teams.length >= 2;
+  const hasRounds = qualifyingRounds.length > 0;
 
   // Check if any games have scores - if so, deletion is not allowed
-  const hasScores = qualifyingGames.some(
+  const hasScores = [...qualifyingGames, ...meleeGames].some(
     (g) => g.team1Score !== null || g.team2Score !== null
   );
   const canDeleteRounds = hasRounds && !hasScores;
+  const selectedRound = qualifyingRounds.find((r) => r.id === selectedRoundId);
 
-  // For Swiss and Pool Play: can generate next round if prior round is complete
+  // For Swiss, Pool Play, Poules and Mêlée: can generate next round if prior round is complete
   const lastRound = qualifyingRounds[qualifyingRounds.length - 1];
-  const maxRounds = pairingMethod === 'poolPlay' ? 3 : (currentTournament?.numberOfQualifyingRounds || 5);
-  const requiresRoundByRound = pairingMethod === 'swiss' || pairingMethod === 'poolPlay';
+  const isThreeRoundFormat = pairingMethod === 'poolPlay' || pairingMethod === 'poules';
+  const maxRounds = isThreeRoundFormat ? 3 : (currentTournament?.numberOfQualifyingRounds || 5);
+  const requiresRoundByRound = pairingMethod === 'swiss' || isThreeRoundFormat || isMelee;
+
+  // Only the latest round, before any of its scores, can be deleted on its own
+  const canDeleteLastRound = qualifyingRounds.length > 1 && !lastRound.isComplete;
 
   const canGenerateNextRound = requiresRoundByRound &&
     canGeneratePairings &&
@@ -116,12 +140,27 @@ export function QualifyingRounds({ tournamentId }: QualifyingRoundsProps) {
           {hasRounds && canDeleteRounds && (
             <Button
               variant="danger"
-              onClick={() => setDeleteDialogOpen(true)}
+              onClick={() => {
+                setDeleteLastOnly(false);
+                setDeleteDialogOpen(true);
+              }}
               disabled={loading}
             >
               {t('pairing.deleteRounds')}
             </Button>
           )}
+          {canDeleteLastRound && (
+            <Button
+              variant="danger"
+              onClick={() => {
+                setDeleteLastOnly(true);
+                setDeleteDialogOpen(true);
+              }}
+              disabled={loading}
+            >
+              {t('pairing.deleteLastRound')}
+            </Button>
+          )}
           {showGenerateAllButton && (
             <Button
               onClick={handleGenerateAllRounds}
@@ -130,6 +169,15 @@ export function QualifyingRounds({ tournamentId }: QualifyingRoundsProps) {
               {t('pairing.generatePairings')}
             </Button>
           )}
+          {showGenerateNextButton && !isMelee && (
+            <Button
+              variant="secondary"
+              onClick={() => setPreviewOpen(true)}
+              disabled={loading}
+            >
+              {t('pairing.previewRound')}
+            </Button>
+          )}
           {showGenerateNextButton && (
             <Button
               onClick={handleGenerateNextRound}
@@ -144,7 +192,7 @@ export function QualifyingRounds({ tournamentId }: QualifyingRoundsProps) {
       {!canGeneratePairings && (
         <Card>
           <CardContent className="py-8 text-center text-gray-500">
-            {t('teams.noTeams')}
+            {isMelee ? t('players.noPlayers') : t('teams.noTeams')}
           </CardContent>
         </Card>
       )}
@@ -162,6 +210,7 @@ export function QualifyingRounds({ tournamentId }: QualifyingRoundsProps) {
           <TabsList>
             <TabsTrigger value="rounds">{t('pairing.title')}</TabsTrigger>
             <TabsTrigger value="standings">{t('pairing.standings')}</TabsTrigger>
+            <TabsTrigger value="courtTimes">{t('pairing.courtTimes')}</TabsTrigger>
           </TabsList>
 
           <TabsContent value="rounds" className="mt-4">
@@ -180,7 +229,43 @@ export function QualifyingRounds({ tournamentId }: QualifyingRoundsProps) {
                 ))}
               </div>
 
-              {selectedRoundId && (
+              {selectedRound?.drawMethod && (
+                <div className="text-sm text-gray-500">
+                  {t('pairing.drawMethod', {
+                    method: t(`tournaments.firstRoundDrawOptions.${selectedRound.drawMethod}`),
+                  })}
+                </div>
+              )}
+
+              {/* The round's clock decides when time is called in its games */}
+              {selectedRound && !selectedRound.isComplete && (
+                <div className="flex items-center justify-end gap-2 text-sm text-gray-500">
+                  {selectedRound.startedAt ? (
+                    t('pairing.startedAt', {
+                      time: new Date(selectedRound.startedAt).toLocaleTimeString([], {
+                        hour: '2-digit',
+                        minute: '2-digit',
+                      }),
+                    })
+                  ) : (
+                    <Button variant="secondary" size="sm" onClick={() => startRound(selectedRound.id)}>
+                      {t('pairing.startRound')}
+                    </Button>
+                  )}
+                </div>
+              )}
+
+              {selectedRoundId && isMelee && (
+                <MeleeGames
+                  roundId={selectedRoundId}
+                  tournamentId={tournamentId}
+                  isComplete={
+                    qualifyingRounds.find((r) => r.id === selectedRoundId)?.isComplete || false
+                  }
+                />
+              )}
+
+              {selectedRoundId && !isMelee && (
                 <RoundGames
                   roundId={selectedRoundId}
                   tournamentId={tournamentId}
@@ -192,19 +277,50 @@ export function QualifyingRounds({ tournamentId }: QualifyingRoundsProps) {
             </div>
           </TabsContent>
 
-          <TabsContent value="standings" className="mt-4">
-            <StandingsTable tournamentId={tournamentId} />
+          <TabsContent value="standings" className="mt-4 space-y-4">
+            <div className="flex justify-end">
+              <Button
+                variant="secondary"
+                size="sm"
+                onClick={() => recalculateStandings(tournamentId)}
+                disabled={loading}
+              >
+                {t('pairing.recalculateStandings')}
+              </Button>
+            </div>
+            {isMelee ? (
+              <PlayerStandingsTable tournamentId={tournamentId} />
+            ) : (
+              <StandingsTable tournamentId={tournamentId} />
+            )}
+          </TabsContent>
+
+          <TabsContent value="courtTimes" className="mt-4">
+            <CourtDurationsTable tournamentId={tournamentId} />
           </TabsContent>
         </Tabs>
       )}
 
+      <PairingPreviewDialog
+        open={previewOpen}
+        onOpenChange={setPreviewOpen}
+        tournamentId={tournamentId}
+        onAccepted={(round) => setSelectedRoundId(round.id)}
+      />
+
       {/* Delete Rounds Confirmation Dialog */}
       <Dialog open={deleteDialogOpen} onOpenChange={setDeleteDialogOpen}>
         <DialogContent>
           <DialogHeader>
-            <DialogTitle>{t('pairing.deleteRounds')}</DialogTitle>
+            <DialogTitle>
+              {deleteLastOnly ? t('pairing.deleteLastRound') : t('pairing.deleteRounds')}
+            </DialogTitle>
           </DialogHeader>
-          <p className="text-sm text-gray-500">{t('pairing.deleteRoundsConfirm')}</p>
+          <p className="text-sm text-gray-500">
+            {deleteLastOnly
+              ? t('pairing.deleteLastRoundConfirm', { number: lastRound?.roundNumber })
+              : t('pairing.deleteRoundsConfirm')}
+          </p>
           {deleteError && (
             <div className="rounded-md bg-red-50 p-3 text-sm text-red-700">
               {deleteError}
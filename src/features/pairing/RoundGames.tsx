@@ -6,8 +6,16 @@ import {
   Card,
   CardContent,
   Input,
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogFooter,
 } from '../../components/ui';
-import { formatTeamName } from '../../lib/utils';
+import { formatCommandError, formatTeamName } from '../../lib/utils';
+import type { CorrectionImpact, QualifyingGame } from '../../types';
+import { EndsDialog } from './EndsDialog';
+import { OverrideDialog } from './OverrideDialog';
 
 interface RoundGamesProps {
   roundId: string;
@@ -18,21 +26,34 @@ interface RoundGamesProps {
 export function RoundGames({ roundId, tournamentId, isComplete }: RoundGamesProps) {
   const { t } = useTranslation();
   const {
+    currentTournament,
     qualifyingGames,
     teams,
     fetchGamesForRound,
     updateGameScore,
     completeRound,
+    correctGameScore,
+    fetchGameEnds,
+    recordGameEnd,
+    undoLastGameEnd,
     fetchStandings,
     fetchQualifyingRounds,
   } = useTournamentStore();
 
   const [scores, setScores] = useState<Record<string, { team1: string; team2: string }>>({});
   const [initialLoading, setInitialLoading] = useState(true);
+  const [scoreErrors, setScoreErrors] = useState<Record<string, string>>({});
+  const [correctingGame, setCorrectingGame] = useState<QualifyingGame | null>(null);
+  const [correction, setCorrection] = useState({ team1: '', team2: '', reason: '' });
+  const [correctionError, setCorrectionError] = useState<string | null>(null);
+  const [impact, setImpact] = useState<CorrectionImpact | null>(null);
+  const [endsGame, setEndsGame] = useState<QualifyingGame | null>(null);
+  const [overrideGame, setOverrideGame] = useState<QualifyingGame | null>(null);
 
   useEffect(() => {
     // Reset scores when switching rounds
     setScores({});
+    setScoreErrors({});
     setInitialLoading(true);
     fetchGamesForRound(roundId).finally(() => setInitialLoading(false));
   }, [roundId, fetchGamesForRound]);
@@ -84,21 +105,28 @@ export function RoundGames({ roundId, tournamentId, isComplete }: RoundGamesProp
     }));
   };
 
+  // Returns whether the score was accepted
   const handleSaveScore = async (gameId: string) => {
     const gameScores = scores[gameId];
-    if (!gameScores) return;
+    if (!gameScores) return false;
 
     const team1Score = parseInt(gameScores.team1);
     const team2Score = parseInt(gameScores.team2);
 
     if (isNaN(team1Score) || isNaN(team2Score)) {
-      return;
+      return false;
     }
 
     try {
       await updateGameScore(gameId, team1Score, team2Score);
+      setScoreErrors((prev) => {
+        const { [gameId]: _, ...rest } = prev;
+        return rest;
+      });
+      return true;
     } catch (error) {
-      console.error('Failed to save score:', error);
+      setScoreErrors((prev) => ({ ...prev, [gameId]: formatCommandError(error, t) }));
+      return false;
     }
   };
 
@@ -115,12 +143,16 @@ export function RoundGames({ roundId, tournamentId, isComplete }: RoundGamesProp
       return;
     }
 
+    let allScoresValid = true;
     for (const game of qualifyingGames) {
-      if (!game.isBye) {
-        await handleSaveScore(game.id);
+      if (!game.isBye && !game.isForfeit) {
+        allScoresValid = (await handleSaveScore(game.id)) && allScoresValid;
       }
     }
 
+    // Every rejected score is shown on its game
+    if (!allScoresValid) return;
+
     try {
       await completeRound(roundId);
       await fetchStandings(tournamentId);
@@ -130,6 +162,71 @@ export function RoundGames({ roundId, tournamentId, isComplete }: RoundGamesProp
     }
   };
 
+  const openCorrection = (game: QualifyingGame) => {
+    setCorrectingGame(game);
+    setCorrection({
+      team1: game.team1Score?.toString() || '',
+      team2: game.team2Score?.toString() || '',
+      reason: '',
+    });
+    setCorrectionError(null);
+    setImpact(null);
+  };
+
+  const closeCorrection = () => {
+    setCorrectingGame(null);
+    setImpact(null);
+  };
+
+  const handleCorrectScore = async () => {
+    if (!correctingGame) return;
+
+    const team1Score = parseInt(correction.team1);
+    const team2Score = parseInt(correction.team2);
+
+    if (isNaN(team1Score) || isNaN(team2Score)) {
+      return;
+    }
+
+    setCorrectionError(null);
+    try {
+      const result = await correctGameScore(correctingGame.id, team1Score, team2Score, correction.reason);
+      setScores((prev) => ({
+        ...prev,
+        [correctingGame.id]: { team1: correction.team1, team2: correction.team2 },
+      }));
+      setImpact(result);
+      await fetchStandings(tournamentId);
+    } catch (error) {
+      setCorrectionError(formatCommandError(error, t));
+    }
+  };
+
+  // The ends may have given the game its final score, or taken it back
+  const closeEnds = () => {
+    if (endsGame) {
+      const game = qualifyingGames.find((g) => g.id === endsGame.id);
+      setScores((prev) => ({
+        ...prev,
+        [endsGame.id]: {
+          team1: game?.team1Score?.toString() || '',
+          team2: game?.team2Score?.toString() || '',
+        },
+      }));
+    }
+    setEndsGame(null);
+  };
+
+  const hasWaves = qualifyingGames.some((g) => g.slotNumber > 1);
+
+  // Poules and round robin follow a fixed schedule
+  const pairingMethod = currentTournament?.pairingMethod || 'swiss';
+  const canOverride = (game: QualifyingGame) =>
+    !isComplete &&
+    pairingMethod !== 'poules' &&
+    pairingMethod !== 'roundRobin' &&
+    (game.isBye || (game.team1Score === null && !game.isForfeit));
+
   if (initialLoading) {
     return <div className="text-center py-4 text-gray-500">{t('common.loading')}</div>;
   }
@@ -142,6 +239,10 @@ export function RoundGames({ roundId, tournamentId, isComplete }: RoundGamesProp
             <CardContent className="py-4">
               <div className="text-xs text-gray-500 mb-2">
                 {t('pairing.court')} {game.courtNumber}
+                {hasWaves && ` · ${t('pairing.wave')} ${game.slotNumber}`}
+                {game.isForfeit && (
+                  <span className="ml-2 text-red-600">{t('pairing.forfeit')}</span>
+                )}
               </div>
 
               {game.isBye ? (
@@ -149,6 +250,11 @@ export function RoundGames({ roundId, tournamentId, isComplete }: RoundGamesProp
                   <div className="font-medium">{getTeamName(game.team1Id)}</div>
                   <div className="text-sm text-gray-500 mt-2">{t('pairing.bye')}</div>
                   <div className="text-sm text-green-600 mt-1">13 - 7</div>
+                  {canOverride(game) && (
+                    <Button variant="ghost" size="sm" className="mt-2" onClick={() => setOverrideGame(game)}>
+                      {t('overrides.title')}
+                    </Button>
+                  )}
                 </div>
               ) : (
                 <div className="space-y-3">
@@ -165,7 +271,7 @@ export function RoundGames({ roundId, tournamentId, isComplete }: RoundGamesProp
                       value={scores[game.id]?.team1 || ''}
                       onChange={(e) => handleScoreChange(game.id, 'team1', e.target.value)}
                       onBlur={() => handleSaveScore(game.id)}
-                      disabled={isComplete}
+                      disabled={isComplete || game.isForfeit}
                       className="w-16 text-center"
                     />
                   </div>
@@ -187,10 +293,35 @@ export function RoundGames({ roundId, tournamentId, isComplete }: RoundGamesProp
                       value={scores[game.id]?.team2 || ''}
                       onChange={(e) => handleScoreChange(game.id, 'team2', e.target.value)}
                       onBlur={() => handleSaveScore(game.id)}
-                      disabled={isComplete}
+                      disabled={isComplete || game.isForfeit}
                       className="w-16 text-center"
                     />
                   </div>
+
+                  {scoreErrors[game.id] && (
+                    <div className="text-xs text-red-600">{scoreErrors[game.id]}</div>
+                  )}
+
+                  {isComplete ? (
+                    <div className="flex justify-end">
+                      <Button variant="ghost" size="sm" onClick={() => openCorrection(game)}>
+                        {t('pairing.correctScore')}
+                      </Button>
+                    </div>
+                  ) : (
+                    !game.isForfeit && (
+                      <div className="flex justify-end gap-2">
+                        {canOverride(game) && (
+                          <Button variant="ghost" size="sm" onClick={() => setOverrideGame(game)}>
+                            {t('overrides.title')}
+                          </Button>
+                        )}
+                        <Button variant="ghost" size="sm" onClick={() => setEndsGame(game)}>
+                          {t('ends.title')}
+                        </Button>
+                      </div>
+                    )
+                  )}
                 </div>
               )}
             </CardContent>
@@ -203,6 +334,101 @@ export function RoundGames({ roundId, tournamentId, isComplete }: RoundGamesProp
           <Button onClick={handleCompleteRound}>{t('pairing.completeRound')}</Button>
         </div>
       )}
+
+      <EndsDialog
+        open={endsGame !== null}
+        onOpenChange={(open) => !open && closeEnds()}
+        team1Name={getTeamName(endsGame?.team1Id)}
+        team2Name={getTeamName(endsGame?.team2Id)}
+        load={() => fetchGameEnds(endsGame!.id)}
+        record={(team1Points, team2Points) => recordGameEnd(endsGame!.id, team1Points, team2Points)}
+        undo={() => undoLastGameEnd(endsGame!.id)}
+      />
+
+      <OverrideDialog game={overrideGame} onClose={() => setOverrideGame(null)} getTeamName={getTeamName} />
+
+      {/* Score Correction Dialog */}
+      <Dialog open={correctingGame !== null} onOpenChange={(open) => !open && closeCorrection()}>
+        <DialogContent>
+          <DialogHeader>
+            <DialogTitle>{t('pairing.correctScore')}</DialogTitle>
+          </DialogHeader>
+          {impact ? (
+            <div className="space-y-2 text-sm text-gray-700">
+              <p>{t('pairing.correctionSaved')}</p>
+              {impact.winnerChanged && <p>{t('pairing.correctionWinnerChanged')}</p>}
+              {impact.rankChanges.length > 0 && (
+                <ul className="list-disc pl-5">
+                  {impact.rankChanges.map((change) => (
+                    <li key={change.teamId}>
+                      {getTeamName(change.teamId)}: {change.oldRank} → {change.newRank}
+                    </li>
+                  ))}
+                </ul>
+              )}
+              {impact.affectedRoundNumbers.length > 0 && (
+                <p className="text-amber-700">
+                  {t('pairing.correctionAffectsRounds', {
+                    rounds: impact.affectedRoundNumbers.join(', '),
+                  })}
+                </p>
+              )}
+              {impact.bracketsAffected && (
+                <p className="text-amber-700">{t('pairing.correctionAffectsBrackets')}</p>
+              )}
+            </div>
+          ) : (
+            correctingGame && (
+              <div className="space-y-3">
+                <div className="flex items-center gap-2">
+                  <div className="flex-1 font-medium truncate">{getTeamName(correctingGame.team1Id)}</div>
+                  <Input
+                    type="number"
+                    min={0}
+                    max={13}
+                    value={correction.team1}
+                    onChange={(e) => setCorrection({ ...correction, team1: e.target.value })}
+                    className="w-16 text-center"
+                  />
+                </div>
+                <div className="flex items-center gap-2">
+                  <div className="flex-1 font-medium truncate">{getTeamName(correctingGame.team2Id)}</div>
+                  <Input
+                    type="number"
+                    min={0}
+                    max={13}
+                    value={correction.team2}
+                    onChange={(e) => setCorrection({ ...correction, team2: e.target.value })}
+                    className="w-16 text-center"
+                  />
+                </div>
+                <Input
+                  label={t('pairing.correctionReason')}
+                  value={correction.reason}
+                  onChange={(e) => setCorrection({ ...correction, reason: e.target.value })}
+                />
+                {correctionError && (
+                  <div className="rounded-md bg-red-50 p-3 text-sm text-red-700">{correctionError}</div>
+                )}
+              </div>
+            )
+          )}
+          <DialogFooter>
+            {impact ? (
+              <Button onClick={closeCorrection}>{t('common.close')}</Button>
+            ) : (
+              <>
+                <Button variant="secondary" onClick={closeCorrection}>
+                  {t('common.cancel')}
+                </Button>
+                <Button onClick={handleCorrectScore} disabled={correction.reason.trim() === ''}>
+                  {t('common.save')}
+                </Button>
+              </>
+            )}
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
     </div>
   );
 }
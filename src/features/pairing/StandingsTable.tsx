@@ -23,8 +23,16 @@ export function StandingsTable({ tournamentId }: StandingsTableProps) {
 
   // Determine which columns to show based on pairing method
   const showBuchholz = pairingMethod === 'swiss';
-  const showPointQuotient = pairingMethod === 'swissHotel' || pairingMethod === 'roundRobin' || pairingMethod === 'poolPlay';
-  const showStatus = pairingMethod === 'poolPlay';
+  const showPointQuotient = pairingMethod === 'swissHotel' || pairingMethod === 'roundRobin' || pairingMethod === 'poolPlay' || pairingMethod === 'poules';
+  const showPool = pairingMethod === 'poules';
+  const showStatus = pairingMethod === 'poolPlay' || showPool;
+
+  // Extra tiebreakers get a column when the tournament ranks with them
+  const tiebreakers = currentTournament?.tiebreakers || [];
+  const showMedianBuchholz = tiebreakers.includes('medianBuchholz');
+  const showCutBuchholz = tiebreakers.includes('cutBuchholz');
+  const showSonnebornBerger = tiebreakers.includes('sonnebornBerger');
+  const showHeadToHead = tiebreakers.includes('headToHead');
 
   useEffect(() => {
     fetchStandings(tournamentId);
@@ -59,6 +67,9 @@ export function StandingsTable({ tournamentId }: StandingsTableProps) {
           <TableRow>
             <TableHead className="w-16">{t('pairing.rank')}</TableHead>
             <TableHead>{t('teams.captain')}</TableHead>
+            {showPool && (
+              <TableHead className="text-center">{t('pairing.pool')}</TableHead>
+            )}
             <TableHead className="text-center">{t('pairing.wins')}</TableHead>
             <TableHead className="text-center">{t('pairing.losses')}</TableHead>
             <TableHead className="text-center">{t('pairing.pointsFor')}</TableHead>
@@ -69,6 +80,18 @@ export function StandingsTable({ tournamentId }: StandingsTableProps) {
                 <TableHead className="text-center">{t('pairing.fineBuchholz')}</TableHead>
               </>
             )}
+            {showMedianBuchholz && (
+              <TableHead className="text-center">{t('pairing.medianBuchholz')}</TableHead>
+            )}
+            {showCutBuchholz && (
+              <TableHead className="text-center">{t('pairing.cutBuchholz')}</TableHead>
+            )}
+            {showSonnebornBerger && (
+              <TableHead className="text-center">{t('pairing.sonnebornBerger')}</TableHead>
+            )}
+            {showHeadToHead && (
+              <TableHead className="text-center">{t('pairing.headToHead')}</TableHead>
+            )}
             {showPointQuotient && (
               <TableHead className="text-center">{t('pairing.pointQuotient')}</TableHead>
             )}
@@ -84,8 +107,16 @@ export function StandingsTable({ tournamentId }: StandingsTableProps) {
               key={standing.id}
               className={standing.isEliminated ? 'opacity-50 bg-gray-50' : ''}
             >
-              <TableCell className="font-medium">{standing.rank}</TableCell>
+              <TableCell className="font-medium">
+                {standing.rank}
+                {standing.isTied && '='}
+              </TableCell>
               <TableCell className="font-medium">{getTeamName(standing.teamId)}</TableCell>
+              {showPool && (
+                <TableCell className="text-center">
+                  {standing.poolNumber !== null ? String.fromCharCode(64 + standing.poolNumber) : ''}
+                </TableCell>
+              )}
               <TableCell className="text-center">{standing.wins}</TableCell>
               <TableCell className="text-center">{standing.losses}</TableCell>
               <TableCell className="text-center">{standing.pointsFor}</TableCell>
@@ -96,6 +127,18 @@ export function StandingsTable({ tournamentId }: StandingsTableProps) {
                   <TableCell className="text-center">{standing.fineBuchholzScore.toFixed(1)}</TableCell>
                 </>
               )}
+              {showMedianBuchholz && (
+                <TableCell className="text-center">{standing.medianBuchholzScore.toFixed(1)}</TableCell>
+              )}
+              {showCutBuchholz && (
+                <TableCell className="text-center">{standing.cutBuchholzScore.toFixed(1)}</TableCell>
+              )}
+              {showSonnebornBerger && (
+                <TableCell className="text-center">{standing.sonnebornBergerScore.toFixed(1)}</TableCell>
+              )}
+              {showHeadToHead && (
+                <TableCell className="text-center">{standing.headToHeadWins}</TableCell>
+              )}
               {showPointQuotient && (
                 <TableCell className="text-center">
                   {standing.pointQuotient === Infinity || standing.pointQuotient > 100
@@ -121,7 +164,7 @@ export function StandingsTable({ tournamentId }: StandingsTableProps) {
                 <TableCell className="text-center">
                   {standing.isEliminated ? (
                     <span className="text-red-600">{t('pairing.eliminated')}</span>
-                  ) : standing.wins >= 2 ? (
+                  ) : standing.wins >= 2 || standing.poolRank !== null ? (
                     <span className="text-green-600">{t('pairing.qualified')}</span>
                   ) : null}
                 </TableCell>
@@ -8,6 +8,7 @@ interface TeamFormData {
   player3: string;
   region: string;
   club: string;
+  seed: string;
 }
 
 interface TeamFormProps {
@@ -30,6 +31,7 @@ export function TeamForm({ defaultValues, onSubmit, onCancel }: TeamFormProps) {
       player3: '',
       region: '',
       club: '',
+      seed: '',
       ...defaultValues,
     },
   });
@@ -70,6 +72,13 @@ export function TeamForm({ defaultValues, onSubmit, onCancel }: TeamFormProps) {
         {...register('club')}
       />
 
+      <Input
+        label={t('teams.seed')}
+        type="number"
+        min={1}
+        {...register('seed')}
+      />
+
       <div className="flex justify-end gap-2 pt-4">
         <Button type="button" variant="secondary" onClick={onCancel}>
           {t('common.cancel')}
@@ -28,11 +28,12 @@ interface TeamsListProps {
 export function TeamsList({ tournamentId }: TeamsListProps) {
   const { t } = useTranslation();
   const fileInputRef = useRef<HTMLInputElement>(null);
-  const { teams, qualifyingRounds, loading, fetchTeams, createTeam, updateTeam, deleteTeam, deleteAllTeams, importTeams, fetchQualifyingRounds } = useTournamentStore();
+  const { teams, qualifyingRounds, loading, fetchTeams, createTeam, registerLateTeam, updateTeam, deleteTeam, withdrawTeam, deleteAllTeams, importTeams, fetchQualifyingRounds } = useTournamentStore();
 
   const [addDialogOpen, setAddDialogOpen] = useState(false);
   const [editDialogOpen, setEditDialogOpen] = useState(false);
   const [deleteDialogOpen, setDeleteDialogOpen] = useState(false);
+  const [withdrawDialogOpen, setWithdrawDialogOpen] = useState(false);
   const [deleteAllDialogOpen, setDeleteAllDialogOpen] = useState(false);
   const [selectedTeam, setSelectedTeam] = useState<Team | null>(null);
   const [importError, setImportError] = useState<string | null>(null);
@@ -48,14 +49,17 @@ export function TeamsList({ tournamentId }: TeamsListProps) {
   const canDeleteAllTeams = teams.length > 0 && !hasRounds;
 
   const handleAddTeam = async (data: TeamFormData) => {
+    // Once qualifying has started, new teams join as late entries
+    const addTeam = hasRounds ? registerLateTeam : createTeam;
     try {
-      await createTeam({
+      await addTeam({
         tournamentId,
         captain: data.captain,
         player2: data.player2,
         player3: data.player3 || null,
         region: data.region || null,
         club: data.club || null,
+        seed: data.seed ? parseInt(data.seed) : null,
       });
       setAddDialogOpen(false);
     } catch (error) {
@@ -73,6 +77,7 @@ export function TeamsList({ tournamentId }: TeamsListProps) {
         player3: data.player3 || null,
         region: data.region || null,
         club: data.club || null,
+        seed: data.seed ? parseInt(data.seed) : null,
       });
       setEditDialogOpen(false);
       setSelectedTeam(null);
@@ -92,6 +97,17 @@ export function TeamsList({ tournamentId }: TeamsListProps) {
     }
   };
 
+  const handleWithdrawTeam = async () => {
+    if (!selectedTeam) return;
+    try {
+      await withdrawTeam(selectedTeam.id);
+      setWithdrawDialogOpen(false);
+      setSelectedTeam(null);
+    } catch (error) {
+      console.error('Failed to withdraw team:', error);
+    }
+  };
+
   const handleDeleteAllTeams = async () => {
     setDeleteAllError(null);
     try {
@@ -121,6 +137,7 @@ export function TeamsList({ tournamentId }: TeamsListProps) {
             player3: row.player3 || null,
             region: row.region || null,
             club: row.club || null,
+            seed: row.seed ? parseInt(row.seed) : null,
           }));
 
           // Validate required fields
@@ -148,7 +165,7 @@ export function TeamsList({ tournamentId }: TeamsListProps) {
   };
 
   const downloadTemplate = () => {
-    const template = 'captain,player2,player3,region,club\nJohn Doe,Jane Smith,Bob Wilson,North,Club A\n';
+    const template = 'captain,player2,player3,region,club,seed\nJohn Doe,Jane Smith,Bob Wilson,North,Club A,1\n';
     const blob = new Blob([template], { type: 'text/csv' });
     const url = URL.createObjectURL(blob);
     const a = document.createElement('a');
@@ -190,7 +207,7 @@ export function TeamsList({ tournamentId }: TeamsListProps) {
             {t('teams.importCSV')}
           </Button>
           <Button size="sm" onClick={() => setAddDialogOpen(true)}>
-            {t('teams.add')}
+            {hasRounds ? t('teams.addLate') : t('teams.add')}
           </Button>
         </div>
       </div>
@@ -225,17 +242,24 @@ export function TeamsList({ tournamentId }: TeamsListProps) {
                 <TableHead>{t('teams.player3')}</TableHead>
                 <TableHead>{t('teams.region')}</TableHead>
                 <TableHead>{t('teams.club')}</TableHead>
+                <TableHead className="text-center">{t('teams.seed')}</TableHead>
                 <TableHead className="w-24">{t('common.actions')}</TableHead>
               </TableRow>
             </TableHeader>
             <TableBody>
               {teams.map((team) => (
-                <TableRow key={team.id}>
-                  <TableCell className="font-medium">{team.captain}</TableCell>
+                <TableRow key={team.id} className={team.isActive ? '' : 'opacity-50 bg-gray-50'}>
+                  <TableCell className="font-medium">
+                    {team.captain}
+                    {!team.isActive && (
+                      <span className="ml-2 text-xs text-red-600">{t('teams.withdrawn')}</span>
+                    )}
+                  </TableCell>
                   <TableCell>{team.player2}</TableCell>
                   <TableCell>{team.player3 || '-'}</TableCell>
                   <TableCell>{team.region || '-'}</TableCell>
                   <TableCell>{team.club || '-'}</TableCell>
+                  <TableCell className="text-center">{team.seed ?? '-'}</TableCell>
                   <TableCell>
                     <div className="flex gap-1">
                       <Button
@@ -260,6 +284,18 @@ export function TeamsList({ tournamentId }: TeamsListProps) {
                           {t('common.delete')}
                         </Button>
                       )}
+                      {hasRounds && team.isActive && (
+                        <Button
+                          variant="ghost"
+                          size="sm"
+                          onClick={() => {
+                            setSelectedTeam(team);
+                            setWithdrawDialogOpen(true);
+                          }}
+                        >
+                          {t('teams.withdraw')}
+                        </Button>
+                      )}
                     </div>
                   </TableCell>
                 </TableRow>
@@ -273,7 +309,7 @@ export function TeamsList({ tournamentId }: TeamsListProps) {
       <Dialog open={addDialogOpen} onOpenChange={setAddDialogOpen}>
         <DialogContent>
           <DialogHeader>
-            <DialogTitle>{t('teams.add')}</DialogTitle>
+            <DialogTitle>{hasRounds ? t('teams.addLate') : t('teams.add')}</DialogTitle>
           </DialogHeader>
           <TeamForm
             onSubmit={handleAddTeam}
@@ -296,6 +332,7 @@ export function TeamsList({ tournamentId }: TeamsListProps) {
                 player3: selectedTeam.player3 || '',
                 region: selectedTeam.region || '',
                 club: selectedTeam.club || '',
+                seed: selectedTeam.seed?.toString() || '',
               }}
               onSubmit={handleEditTeam}
               onCancel={() => {
@@ -331,6 +368,30 @@ export function TeamsList({ tournamentId }: TeamsListProps) {
         </DialogContent>
       </Dialog>
 
+      {/* Withdraw Confirmation Dialog */}
+      <Dialog open={withdrawDialogOpen} onOpenChange={setWithdrawDialogOpen}>
+        <DialogContent>
+          <DialogHeader>
+            <DialogTitle>{t('teams.withdraw')}</DialogTitle>
+          </DialogHeader>
+          <p className="text-sm text-gray-500">{t('teams.withdrawConfirm')}</p>
+          <DialogFooter>
+            <Button
+              variant="secondary"
+              onClick={() => {
+                setWithdrawDialogOpen(false);
+                setSelectedTeam(null);
+              }}
+            >
+              {t('common.cancel')}
+            </Button>
+            <Button variant="danger" onClick={handleWithdrawTeam} disabled={loading}>
+              {t('teams.withdraw')}
+            </Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+
       {/* Delete All Teams Confirmation Dialog */}
       <Dialog open={deleteAllDialogOpen} onOpenChange={setDeleteAllDialogOpen}>
         <DialogContent>
@@ -27,7 +27,16 @@ export function TournamentCreate() {
         advanceCount: data.advanceCount,
         bracketSize: data.bracketSize,
         pairingMethod: data.pairingMethod,
-        regionAvoidance: data.regionAvoidance,
+        avoidancePolicy: data.avoidancePolicy,
+        avoidanceRounds: data.avoidanceRounds,
+        tiebreakers: data.tiebreakers,
+        maxTeams: data.maxTeams,
+        lateEntryPolicy: data.lateEntryPolicy,
+        firstRoundDraw: data.firstRoundDraw,
+        bracketSeeding: data.bracketSeeding,
+        targetScore: data.targetScore,
+        timeLimitMinutes: data.timeLimitMinutes,
+        extraEnds: data.extraEnds,
       } as any);
       navigate(`/tournaments/${tournament.id}`);
     } catch (error) {
@@ -18,6 +18,7 @@ import {
   DialogFooter,
 } from '../../components/ui';
 import { TeamsList } from '../teams/TeamsList';
+import { PlayersList } from '../melee/PlayersList';
 import { QualifyingRounds } from '../pairing/QualifyingRounds';
 import { BracketView } from '../brackets/BracketView';
 import { ExportView } from '../export/ExportView';
@@ -148,7 +149,11 @@ export function TournamentDetail() {
         </TabsList>
 
         <TabsContent value="teams" className="mt-4">
-          <TeamsList tournamentId={id!} />
+          {currentTournament.pairingMethod === 'melee' ? (
+            <PlayersList tournamentId={id!} />
+          ) : (
+            <TeamsList tournamentId={id!} />
+          )}
         </TabsContent>
 
         <TabsContent value="qualifying" className="mt-4">
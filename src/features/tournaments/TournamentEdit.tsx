@@ -51,7 +51,16 @@ export function TournamentEdit() {
         advanceCount: data.advanceCount,
         bracketSize: data.bracketSize,
         pairingMethod: data.pairingMethod,
-        regionAvoidance: data.regionAvoidance,
+        avoidancePolicy: data.avoidancePolicy,
+        avoidanceRounds: data.avoidanceRounds ?? 0,
+        tiebreakers: data.tiebreakers,
+        maxTeams: data.maxTeams ?? 0,
+        lateEntryPolicy: data.lateEntryPolicy,
+        firstRoundDraw: data.firstRoundDraw,
+        bracketSeeding: data.bracketSeeding,
+        targetScore: data.targetScore,
+        timeLimitMinutes: data.timeLimitMinutes ?? 0,
+        extraEnds: data.extraEnds,
       } as any);
       navigate(`/tournaments/${id}`);
     } catch (error) {
@@ -109,7 +118,16 @@ export function TournamentEdit() {
           advanceCount: currentTournament.advanceCount,
           bracketSize: currentTournament.bracketSize,
           pairingMethod: currentTournament.pairingMethod,
-          regionAvoidance: currentTournament.regionAvoidance,
+          avoidancePolicy: currentTournament.avoidancePolicy,
+          avoidanceRounds: currentTournament.avoidanceRounds,
+          tiebreakers: currentTournament.tiebreakers,
+          maxTeams: currentTournament.maxTeams,
+          lateEntryPolicy: currentTournament.lateEntryPolicy,
+          firstRoundDraw: currentTournament.firstRoundDraw,
+          bracketSeeding: currentTournament.bracketSeeding,
+          targetScore: currentTournament.targetScore,
+          timeLimitMinutes: currentTournament.timeLimitMinutes,
+          extraEnds: currentTournament.extraEnds,
         }}
         onSubmit={handleSubmit}
         onCancel={() => navigate(`/tournaments/${id}`)}
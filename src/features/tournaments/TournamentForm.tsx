@@ -1,7 +1,31 @@
 import { useForm, useFieldArray } from 'react-hook-form';
 import { useTranslation } from 'react-i18next';
 import { Button, Input, Select, SelectItem, Card, CardContent, CardFooter } from '../../components/ui';
-import type { TournamentFormData } from '../../types';
+import type { PairingMethod, Tiebreaker, TournamentFormData } from '../../types';
+
+const ALL_TIEBREAKERS: Tiebreaker[] = [
+  'wins',
+  'buchholz',
+  'medianBuchholz',
+  'cutBuchholz',
+  'fineBuchholz',
+  'sonnebornBerger',
+  'differential',
+  'quotient',
+  'pointsFor',
+  'headToHead',
+  'random',
+];
+
+// Same defaults as the backend ranking engine
+const defaultTiebreakers = (pairingMethod: PairingMethod): Tiebreaker[] =>
+  pairingMethod === 'swiss'
+    ? ['wins', 'buchholz', 'fineBuchholz', 'differential', 'random']
+    : ['wins', 'differential', 'quotient', 'random'];
+
+// Nothing is applied after the random draw, so the backend only accepts it last
+const keepRandomLast = (chain: Tiebreaker[]): Tiebreaker[] =>
+  chain.includes('random') ? [...chain.filter((tb) => tb !== 'random'), 'random'] : chain;
 
 interface TournamentFormProps {
   defaultValues?: Partial<TournamentFormData>;
@@ -41,7 +65,16 @@ export function TournamentForm({ defaultValues, onSubmit, onCancel, isLoading, h
       advanceCount: null,
       bracketSize: 16,
       pairingMethod: 'swiss',
-      regionAvoidance: false,
+      avoidancePolicy: 'none',
+      avoidanceRounds: null,
+      lateEntryPolicy: 'zeroWins',
+      targetScore: 13,
+      timeLimitMinutes: null,
+      extraEnds: 1,
+      firstRoundDraw: 'random',
+      bracketSeeding: 'classic',
+      tiebreakers: defaultTiebreakers('swiss'),
+      maxTeams: null,
       ...defaultValues,
     },
   });
@@ -51,6 +84,13 @@ export function TournamentForm({ defaultValues, onSubmit, onCancel, isLoading, h
     name: 'additionalUmpires',
   });
 
+  const tiebreakers = watch('tiebreakers');
+  const moveTiebreaker = (index: number, offset: number) => {
+    const next = [...tiebreakers];
+    [next[index], next[index + offset]] = [next[index + offset], next[index]];
+    setValue('tiebreakers', keepRandomLast(next));
+  };
+
   const validateRequired = (value: string) => {
     if (!value || value.trim() === '') {
       return t('validation.required');
@@ -129,7 +169,7 @@ export function TournamentForm({ defaultValues, onSubmit, onCancel, isLoading, h
               label={t('tournaments.numberOfQualifyingRounds')}
               {...register('numberOfQualifyingRounds', { valueAsNumber: true })}
               error={errors.numberOfQualifyingRounds?.message}
-              disabled={watch('pairingMethod') === 'poolPlay'}
+              disabled={watch('pairingMethod') === 'poolPlay' || watch('pairingMethod') === 'poules'}
             />
 
             <Input
@@ -139,6 +179,43 @@ export function TournamentForm({ defaultValues, onSubmit, onCancel, isLoading, h
               {...register('numberOfCourts', { valueAsNumber: true })}
               error={errors.numberOfCourts?.message}
             />
+
+            <Input
+              type="number"
+              min={2}
+              label={t('tournaments.maxTeams')}
+              {...register('maxTeams', {
+                setValueAs: (value) => (value === '' || value === null ? null : Number(value)),
+              })}
+              error={errors.maxTeams?.message}
+            />
+
+            <Select
+              label={t('tournaments.targetScore')}
+              value={String(watch('targetScore') ?? 13)}
+              onValueChange={(v) => setValue('targetScore', Number(v))}
+            >
+              <SelectItem value="13">13</SelectItem>
+              <SelectItem value="11">11</SelectItem>
+            </Select>
+
+            <Input
+              type="number"
+              min={1}
+              label={t('tournaments.timeLimitMinutes')}
+              {...register('timeLimitMinutes', {
+                setValueAs: (value) => (value === '' || value === null ? null : Number(value)),
+              })}
+            />
+
+            {watch('timeLimitMinutes') != null && (
+              <Input
+                type="number"
+                min={0}
+                label={t('tournaments.extraEnds')}
+                {...register('extraEnds', { valueAsNumber: true })}
+              />
+            )}
           </div>
 
           {/* Umpire Information */}
@@ -200,8 +277,9 @@ export function TournamentForm({ defaultValues, onSubmit, onCancel, isLoading, h
               value={watch('pairingMethod')}
               onValueChange={(v) => {
                 setValue('pairingMethod', v as TournamentFormData['pairingMethod']);
-                // Pool Play is fixed at 3 rounds
-                if (v === 'poolPlay') {
+                setValue('tiebreakers', defaultTiebreakers(v as PairingMethod));
+                // Pool Play and Poules are fixed at 3 rounds
+                if (v === 'poolPlay' || v === 'poules') {
                   setValue('numberOfQualifyingRounds', 3);
                 }
               }}
@@ -211,6 +289,8 @@ export function TournamentForm({ defaultValues, onSubmit, onCancel, isLoading, h
               <SelectItem value="swissHotel">{t('tournaments.pairingMethodOptions.swissHotel')}</SelectItem>
               <SelectItem value="roundRobin">{t('tournaments.pairingMethodOptions.roundRobin')}</SelectItem>
               <SelectItem value="poolPlay">{t('tournaments.pairingMethodOptions.poolPlay')}</SelectItem>
+              <SelectItem value="poules">{t('tournaments.pairingMethodOptions.poules')}</SelectItem>
+              <SelectItem value="melee">{t('tournaments.pairingMethodOptions.melee')}</SelectItem>
             </Select>
 
             <Select
@@ -237,24 +317,106 @@ export function TournamentForm({ defaultValues, onSubmit, onCancel, isLoading, h
                 {t('tournaments.consolante')}
               </label>
             </div>
+
+            <Select
+              label={t('tournaments.firstRoundDraw')}
+              value={watch('firstRoundDraw')}
+              onValueChange={(v) => setValue('firstRoundDraw', v as TournamentFormData['firstRoundDraw'])}
+              disabled={hasQualifyingRounds || watch('pairingMethod') !== 'swiss'}
+            >
+              <SelectItem value="random">{t('tournaments.firstRoundDrawOptions.random')}</SelectItem>
+              <SelectItem value="seeded">{t('tournaments.firstRoundDrawOptions.seeded')}</SelectItem>
+            </Select>
+
+            <Select
+              label={t('tournaments.bracketSeeding')}
+              value={watch('bracketSeeding')}
+              onValueChange={(v) => setValue('bracketSeeding', v as TournamentFormData['bracketSeeding'])}
+              disabled={hasBrackets}
+            >
+              <SelectItem value="classic">{t('tournaments.bracketSeedingOptions.classic')}</SelectItem>
+              <SelectItem value="standard">{t('tournaments.bracketSeedingOptions.standard')}</SelectItem>
+              <SelectItem value="random">{t('tournaments.bracketSeedingOptions.random')}</SelectItem>
+            </Select>
+          </div>
+
+          <div className="grid gap-4 grid-cols-2 sm:grid-cols-4 items-end">
+            <Select
+              label={t('tournaments.avoidancePolicy')}
+              value={watch('avoidancePolicy')}
+              onValueChange={(v) => setValue('avoidancePolicy', v as TournamentFormData['avoidancePolicy'])}
+              disabled={hasQualifyingRounds}
+            >
+              <SelectItem value="none">{t('tournaments.avoidancePolicyOptions.none')}</SelectItem>
+              <SelectItem value="region">{t('tournaments.avoidancePolicyOptions.region')}</SelectItem>
+              <SelectItem value="club">{t('tournaments.avoidancePolicyOptions.club')}</SelectItem>
+              <SelectItem value="regionFirst">{t('tournaments.avoidancePolicyOptions.regionFirst')}</SelectItem>
+              <SelectItem value="clubFirst">{t('tournaments.avoidancePolicyOptions.clubFirst')}</SelectItem>
+            </Select>
+
+            <Input
+              type="number"
+              min={1}
+              label={t('tournaments.avoidanceRounds')}
+              {...register('avoidanceRounds', {
+                setValueAs: (value) => (value === '' || value === null ? null : Number(value)),
+              })}
+              disabled={hasQualifyingRounds || watch('avoidancePolicy') === 'none'}
+            />
+
+            <Select
+              label={t('tournaments.lateEntryPolicy')}
+              value={watch('lateEntryPolicy')}
+              onValueChange={(v) => setValue('lateEntryPolicy', v as TournamentFormData['lateEntryPolicy'])}
+            >
+              <SelectItem value="zeroWins">{t('tournaments.lateEntryPolicyOptions.zeroWins')}</SelectItem>
+              <SelectItem value="creditedByes">{t('tournaments.lateEntryPolicyOptions.creditedByes')}</SelectItem>
+            </Select>
+          </div>
+
+          <div className="space-y-2">
+            <h4 className="text-sm font-medium text-gray-700">{t('tournaments.tiebreakers')}</h4>
+            <ol className="space-y-1">
+              {tiebreakers.map((tiebreaker, index) => (
+                <li key={tiebreaker} className="flex items-center gap-2 text-sm">
+                  <span className="w-5 text-gray-500">{index + 1}.</span>
+                  <span className="flex-1">{t(`tournaments.tiebreakerOptions.${tiebreaker}`)}</span>
+                  <Button type="button" variant="ghost" size="sm" disabled={index === 0} onClick={() => moveTiebreaker(index, -1)}>
+                    ↑
+                  </Button>
+                  <Button type="button" variant="ghost" size="sm" disabled={index === tiebreakers.length - 1} onClick={() => moveTiebreaker(index, 1)}>
+                    ↓
+                  </Button>
+                  <Button
+                    type="button"
+                    variant="ghost"
+                    size="sm"
+                    disabled={tiebreakers.length === 1}
+                    onClick={() => setValue('tiebreakers', tiebreakers.filter((tb) => tb !== tiebreaker))}
+                  >
+                    ×
+                  </Button>
+                </li>
+              ))}
+            </ol>
+            {tiebreakers.length < ALL_TIEBREAKERS.length && (
+              <div className="max-w-xs">
+                <Select
+                  placeholder={t('tournaments.addTiebreaker')}
+                  value=""
+                  onValueChange={(v) => setValue('tiebreakers', keepRandomLast([...tiebreakers, v as Tiebreaker]))}
+                >
+                  {ALL_TIEBREAKERS.filter((tb) => !tiebreakers.includes(tb)).map((tb) => (
+                    <SelectItem key={tb} value={tb}>{t(`tournaments.tiebreakerOptions.${tb}`)}</SelectItem>
+                  ))}
+                </Select>
+              </div>
+            )}
           </div>
 
           <div className="pt-4">
             <h4 className={`text-sm font-medium mb-3 ${hasQualifyingRounds ? 'text-gray-400' : 'text-gray-700'}`}>{t('tournaments.ameliaIslandOptions')}</h4>
             <div className="flex flex-wrap gap-x-8 gap-y-2">
-              <div className="flex items-center gap-2">
-                <input
-                  type="checkbox"
-                  id="regionAvoidance"
-                  {...register('regionAvoidance')}
-                  className="h-4 w-4 rounded border-gray-300 text-primary-600 focus:ring-primary-500 disabled:opacity-50"
-                  disabled={hasQualifyingRounds}
-                />
-                <label htmlFor="regionAvoidance" className={`text-sm ${hasQualifyingRounds ? 'text-gray-400' : 'text-gray-700'}`}>
-                  {t('tournaments.regionAvoidance')}
-                </label>
-              </div>
-
               <div className="flex items-center gap-2">
                 <input
                   type="checkbox"
@@ -1,3 +1,6 @@
+import type { TFunction } from 'i18next';
+import type { ScoreError } from '../types';
+
 /**
  * Formats a team captain's name as "LASTNAME F."
  * e.g., "John Doe" -> "DOE J."
@@ -20,3 +23,17 @@ export function formatTeamName(captainName: string | null | undefined): string {
 
   return `${lastName.toUpperCase()} ${firstInitial}.`;
 }
+
+function isScoreError(error: unknown): error is ScoreError {
+  return typeof error === 'object' && error !== null && 'kind' in error && 'message' in error;
+}
+
+/**
+ * Message of an error returned by a command. Scores rejected by the game
+ * rules are explained in the current language when `t` is given.
+ */
+export function formatCommandError(error: unknown, t?: TFunction): string {
+  if (!isScoreError(error)) return String(error);
+  if (!t || error.kind === 'other') return error.message;
+  return t(`scoreErrors.${error.kind}`, { target: error.targetScore });
+}
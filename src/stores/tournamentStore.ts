@@ -1,16 +1,23 @@
 import { create } from 'zustand';
-import type { Tournament, Team, QualifyingRound, QualifyingGame, TeamStanding, Bracket, BracketMatch } from '../types';
+import type { Tournament, Court, UpdateCourtData, Team, QualifyingRound, QualifyingGame, TeamStanding, Bracket, BracketMatch, Draw, DrawReplay, ScoreCorrection, CorrectionImpact, EndsSummary, CourtDuration, OverrideResult, PairingOverride, PairingPreview, Player, PlayerStanding, MeleeGame } from '../types';
 import { invoke } from '@tauri-apps/api/core';
+import { formatCommandError } from '../lib/utils';
 
 interface TournamentState {
   tournaments: Tournament[];
   currentTournament: Tournament | null;
+  courts: Court[];
   teams: Team[];
   qualifyingRounds: QualifyingRound[];
   qualifyingGames: QualifyingGame[];
   standings: TeamStanding[];
   brackets: Bracket[];
   bracketMatches: BracketMatch[];
+  draws: Draw[];
+  scoreCorrections: ScoreCorrection[];
+  players: Player[];
+  playerStandings: PlayerStanding[];
+  meleeGames: MeleeGame[];
   loading: boolean;
   error: string | null;
 
@@ -22,25 +29,37 @@ interface TournamentState {
   deleteTournament: (id: string) => Promise<void>;
   setCurrentTournament: (tournament: Tournament | null) => void;
 
+  // Court actions
+  fetchCourts: (tournamentId: string) => Promise<void>;
+  updateCourt: (id: string, data: UpdateCourtData) => Promise<void>;
+
   // Team actions
   fetchTeams: (tournamentId: string) => Promise<void>;
   createTeam: (data: Partial<Team>) => Promise<Team>;
+  registerLateTeam: (data: Partial<Team>) => Promise<Team>;
   updateTeam: (id: string, data: Partial<Team>) => Promise<void>;
   deleteTeam: (id: string) => Promise<void>;
+  withdrawTeam: (id: string) => Promise<void>;
   importTeams: (tournamentId: string, teams: Partial<Team>[]) => Promise<number>;
   deleteAllTeams: (tournamentId: string) => Promise<void>;
 
   // Qualifying round actions
   fetchQualifyingRounds: (tournamentId: string) => Promise<void>;
   generatePairings: (tournamentId: string) => Promise<QualifyingRound>;
+  previewPairings: (tournamentId: string) => Promise<PairingPreview>;
+  acceptPairingPreview: (preview: PairingPreview) => Promise<QualifyingRound>;
   generateAllQualifyingRounds: (tournamentId: string) => Promise<QualifyingRound[]>;
   deleteAllQualifyingRounds: (tournamentId: string) => Promise<void>;
+  deleteLastQualifyingRound: (tournamentId: string) => Promise<void>;
   fetchGamesForRound: (roundId: string) => Promise<void>;
   updateGameScore: (gameId: string, team1Score: number, team2Score: number) => Promise<void>;
   completeRound: (roundId: string) => Promise<void>;
+  correctGameScore: (gameId: string, team1Score: number, team2Score: number, reason: string) => Promise<CorrectionImpact>;
+  fetchScoreCorrections: (tournamentId: string) => Promise<void>;
 
   // Standings actions
   fetchStandings: (tournamentId: string) => Promise<void>;
+  recalculateStandings: (tournamentId: string) => Promise<void>;
 
   // Bracket actions
   fetchBrackets: (tournamentId: string) => Promise<void>;
@@ -48,6 +67,41 @@ interface TournamentState {
   fetchMatchesForBracket: (bracketId: string) => Promise<void>;
   updateMatchScore: (matchId: string, team1Score: number, team2Score: number) => Promise<void>;
 
+  // End-by-end scoring and timing actions
+  startRound: (roundId: string) => Promise<void>;
+  startMatch: (matchId: string) => Promise<void>;
+  fetchGameEnds: (gameId: string) => Promise<EndsSummary>;
+  recordGameEnd: (gameId: string, team1Points: number, team2Points: number) => Promise<EndsSummary>;
+  undoLastGameEnd: (gameId: string) => Promise<EndsSummary>;
+  fetchMatchEnds: (matchId: string) => Promise<EndsSummary>;
+  recordMatchEnd: (matchId: string, team1Points: number, team2Points: number) => Promise<EndsSummary>;
+  undoLastMatchEnd: (matchId: string) => Promise<EndsSummary>;
+  fetchCourtDurations: (tournamentId: string) => Promise<CourtDuration[]>;
+
+  // Pairing override actions
+  swapTeams: (
+    game1Id: string,
+    team1Id: string,
+    game2Id: string,
+    team2Id: string,
+    reason: string | null,
+    confirm: boolean
+  ) => Promise<OverrideResult>;
+  moveGameToCourt: (gameId: string, courtNumber: number, reason: string | null, confirm: boolean) => Promise<OverrideResult>;
+  fetchPairingOverrides: (tournamentId: string) => Promise<PairingOverride[]>;
+
+  // Draw actions
+  fetchDraws: (tournamentId: string) => Promise<void>;
+  replayDraw: (drawId: string) => Promise<DrawReplay>;
+
+  // Mêlée actions
+  fetchPlayers: (tournamentId: string) => Promise<void>;
+  createPlayer: (data: Partial<Player>) => Promise<Player>;
+  deletePlayer: (id: string) => Promise<void>;
+  fetchPlayerStandings: (tournamentId: string) => Promise<void>;
+  fetchMeleeGames: (roundId: string) => Promise<void>;
+  updateMeleeGameScore: (gameId: string, team1Score: number, team2Score: number) => Promise<void>;
+
   // Utility
   clearError: () => void;
 }
@@ -55,12 +109,18 @@ interface TournamentState {
 export const useTournamentStore = create<TournamentState>((set, get) => ({
   tournaments: [],
   currentTournament: null,
+  courts: [],
   teams: [],
   qualifyingRounds: [],
   qualifyingGames: [],
   standings: [],
   brackets: [],
   bracketMatches: [],
+  draws: [],
+  scoreCorrections: [],
+  players: [],
+  playerStandings: [],
+  meleeGames: [],
   loading: false,
   error: null,
 
@@ -140,6 +200,31 @@ export const useTournamentStore = create<TournamentState>((set, get) => ({
     set({ currentTournament: tournament });
   },
 
+  // Court actions
+  fetchCourts: async (tournamentId: string) => {
+    set({ loading: true, error: null });
+    try {
+      const courts = await invoke<Court[]>('get_courts', { tournamentId });
+      set({ courts, loading: false });
+    } catch (error) {
+      set({ error: String(error), loading: false });
+    }
+  },
+
+  updateCourt: async (id: string, data: UpdateCourtData) => {
+    set({ loading: true, error: null });
+    try {
+      await invoke('update_court', { id, data });
+      set((state) => ({
+        courts: state.courts.map((c) => (c.id === id ? { ...c, ...data } : c)),
+        loading: false,
+      }));
+    } catch (error) {
+      set({ error: String(error), loading: false });
+      throw error;
+    }
+  },
+
   // Team actions
   fetchTeams: async (tournamentId: string) => {
     set({ loading: true, error: null });
@@ -166,6 +251,21 @@ export const useTournamentStore = create<TournamentState>((set, get) => ({
     }
   },
 
+  registerLateTeam: async (data: Partial<Team>) => {
+    set({ loading: true, error: null });
+    try {
+      const team = await invoke<Team>('register_late_team', { data });
+      set((state) => ({
+        teams: [...state.teams, team],
+        loading: false,
+      }));
+      return team;
+    } catch (error) {
+      set({ error: String(error), loading: false });
+      throw error;
+    }
+  },
+
   updateTeam: async (id: string, data: Partial<Team>) => {
     set({ loading: true, error: null });
     try {
@@ -194,6 +294,22 @@ export const useTournamentStore = create<TournamentState>((set, get) => ({
     }
   },
 
+  withdrawTeam: async (id: string) => {
+    set({ loading: true, error: null });
+    try {
+      await invoke('withdraw_team', { id });
+      set((state) => ({
+        teams: state.teams.map((t) =>
+          t.id === id ? { ...t, isActive: false, withdrawnAt: new Date().toISOString() } : t
+        ),
+        loading: false,
+      }));
+    } catch (error) {
+      set({ error: String(error), loading: false });
+      throw error;
+    }
+  },
+
   importTeams: async (tournamentId: string, teams: Partial<Team>[]) => {
     set({ loading: true, error: null });
     try {
@@ -248,6 +364,30 @@ export const useTournamentStore = create<TournamentState>((set, get) => ({
     }
   },
 
+  previewPairings: async (tournamentId: string) => {
+    try {
+      return await invoke<PairingPreview>('preview_pairings', { tournamentId });
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  acceptPairingPreview: async (preview: PairingPreview) => {
+    set({ loading: true, error: null });
+    try {
+      const round = await invoke<QualifyingRound>('accept_pairing_preview', { preview });
+      set((state) => ({
+        qualifyingRounds: [...state.qualifyingRounds, round],
+        loading: false,
+      }));
+      return round;
+    } catch (error) {
+      set({ error: String(error), loading: false });
+      throw error;
+    }
+  },
+
   generateAllQualifyingRounds: async (tournamentId: string) => {
     set({ loading: true, error: null });
     try {
@@ -276,6 +416,25 @@ export const useTournamentStore = create<TournamentState>((set, get) => ({
     }
   },
 
+  deleteLastQualifyingRound: async (tournamentId: string) => {
+    set({ loading: true, error: null });
+    try {
+      await invoke('delete_last_qualifying_round', { tournamentId });
+      set((state) => {
+        const lastRound = state.qualifyingRounds[state.qualifyingRounds.length - 1];
+        return {
+          qualifyingRounds: state.qualifyingRounds.slice(0, -1),
+          qualifyingGames: state.qualifyingGames.filter((g) => g.roundId !== lastRound?.id),
+          meleeGames: state.meleeGames.filter((g) => g.roundId !== lastRound?.id),
+          loading: false,
+        };
+      });
+    } catch (error) {
+      set({ error: String(error), loading: false });
+      throw error;
+    }
+  },
+
   fetchGamesForRound: async (roundId: string) => {
     set({ loading: true, error: null });
     try {
@@ -303,11 +462,51 @@ export const useTournamentStore = create<TournamentState>((set, get) => ({
         loading: false,
       }));
     } catch (error) {
-      set({ error: String(error), loading: false });
+      set({ error: formatCommandError(error), loading: false });
+      throw error;
+    }
+  },
+
+  correctGameScore: async (
+    gameId: string,
+    team1Score: number,
+    team2Score: number,
+    reason: string
+  ) => {
+    set({ loading: true, error: null });
+    try {
+      const impact = await invoke<CorrectionImpact>('correct_game_score', {
+        gameId,
+        team1Score,
+        team2Score,
+        reason,
+      });
+      set((state) => ({
+        qualifyingGames: state.qualifyingGames.map((g) =>
+          g.id === gameId ? { ...g, team1Score, team2Score } : g
+        ),
+        scoreCorrections: [...state.scoreCorrections, impact.correction],
+        loading: false,
+      }));
+      return impact;
+    } catch (error) {
+      set({ error: formatCommandError(error), loading: false });
       throw error;
     }
   },
 
+  fetchScoreCorrections: async (tournamentId: string) => {
+    set({ loading: true, error: null });
+    try {
+      const scoreCorrections = await invoke<ScoreCorrection[]>('get_score_corrections', {
+        tournamentId,
+      });
+      set({ scoreCorrections, loading: false });
+    } catch (error) {
+      set({ error: String(error), loading: false });
+    }
+  },
+
   completeRound: async (roundId: string) => {
     set({ loading: true, error: null });
     try {
@@ -337,6 +536,22 @@ export const useTournamentStore = create<TournamentState>((set, get) => ({
     }
   },
 
+  recalculateStandings: async (tournamentId: string) => {
+    set({ loading: true, error: null });
+    try {
+      await invoke('recalculate_standings', { tournamentId });
+      // A mêlée ranks its players, every other format its teams
+      if (get().currentTournament?.pairingMethod === 'melee') {
+        await get().fetchPlayerStandings(tournamentId);
+      } else {
+        await get().fetchStandings(tournamentId);
+      }
+    } catch (error) {
+      set({ error: String(error), loading: false });
+      throw error;
+    }
+  },
+
   // Bracket actions
   fetchBrackets: async (tournamentId: string) => {
     set({ loading: true, error: null });
@@ -398,8 +613,298 @@ export const useTournamentStore = create<TournamentState>((set, get) => ({
       if (currentTournament) {
         await get().fetchBrackets(currentTournament.id);
       }
+    } catch (error) {
+      set({ error: formatCommandError(error), loading: false });
+      throw error;
+    }
+  },
+
+  // The clock is started on the backend; the local time is close enough to show
+  startRound: async (roundId: string) => {
+    try {
+      await invoke('start_round', { roundId });
+      const startedAt = new Date().toISOString();
+      set((state) => ({
+        qualifyingRounds: state.qualifyingRounds.map((r) =>
+          r.id === roundId ? { ...r, startedAt } : r
+        ),
+      }));
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  startMatch: async (matchId: string) => {
+    try {
+      await invoke('start_match', { matchId });
+      const startedAt = new Date().toISOString();
+      set((state) => ({
+        bracketMatches: state.bracketMatches.map((m) =>
+          m.id === matchId ? { ...m, startedAt } : m
+        ),
+      }));
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  fetchGameEnds: async (gameId: string) => {
+    try {
+      return await invoke<EndsSummary>('get_game_ends', { gameId });
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  recordGameEnd: async (gameId: string, team1Points: number, team2Points: number) => {
+    try {
+      const summary = await invoke<EndsSummary>('record_game_end', {
+        gameId,
+        team1Points,
+        team2Points,
+      });
+      // The last end decided the game: its final score has been saved
+      const game = get().qualifyingGames.find((g) => g.id === gameId);
+      if (summary.isDecided && game) {
+        await get().fetchGamesForRound(game.roundId);
+      }
+      return summary;
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  undoLastGameEnd: async (gameId: string) => {
+    try {
+      const summary = await invoke<EndsSummary>('undo_last_game_end', { gameId });
+      const game = get().qualifyingGames.find((g) => g.id === gameId);
+      if (game) {
+        await get().fetchGamesForRound(game.roundId);
+      }
+      return summary;
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  fetchMatchEnds: async (matchId: string) => {
+    try {
+      return await invoke<EndsSummary>('get_match_ends', { matchId });
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  recordMatchEnd: async (matchId: string, team1Points: number, team2Points: number) => {
+    try {
+      const summary = await invoke<EndsSummary>('record_match_end', {
+        matchId,
+        team1Points,
+        team2Points,
+      });
+      // The winner has moved on, and a consolante may have been created
+      const match = get().bracketMatches.find((m) => m.id === matchId);
+      const currentTournament = get().currentTournament;
+      if (summary.isDecided && match) {
+        await get().fetchMatchesForBracket(match.bracketId);
+        if (currentTournament) {
+          await get().fetchBrackets(currentTournament.id);
+        }
+      }
+      return summary;
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  undoLastMatchEnd: async (matchId: string) => {
+    try {
+      return await invoke<EndsSummary>('undo_last_match_end', { matchId });
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  fetchCourtDurations: async (tournamentId: string) => {
+    try {
+      return await invoke<CourtDuration[]>('get_court_durations', { tournamentId });
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  swapTeams: async (
+    game1Id: string,
+    team1Id: string,
+    game2Id: string,
+    team2Id: string,
+    reason: string | null,
+    confirm: boolean
+  ) => {
+    try {
+      const result = await invoke<OverrideResult>('swap_teams', {
+        game1Id,
+        team1Id,
+        game2Id,
+        team2Id,
+        reason,
+        confirm,
+      });
+      if (result.applied) {
+        set((state) => ({
+          qualifyingGames: state.qualifyingGames.map((g) => {
+            if (g.id !== game1Id && g.id !== game2Id) return g;
+            const [from, to] = g.id === game1Id ? [team1Id, team2Id] : [team2Id, team1Id];
+            return {
+              ...g,
+              team1Id: g.team1Id === from ? to : g.team1Id,
+              team2Id: g.team2Id === from ? to : g.team2Id,
+            };
+          }),
+        }));
+      }
+      return result;
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  moveGameToCourt: async (gameId: string, courtNumber: number, reason: string | null, confirm: boolean) => {
+    try {
+      const result = await invoke<OverrideResult>('move_game_to_court', {
+        gameId,
+        courtNumber,
+        reason,
+        confirm,
+      });
+      // A game already on the court has taken the one left
+      const game = get().qualifyingGames.find((g) => g.id === gameId);
+      if (result.applied && game) {
+        await get().fetchGamesForRound(game.roundId);
+      }
+      return result;
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  fetchPairingOverrides: async (tournamentId: string) => {
+    try {
+      return await invoke<PairingOverride[]>('get_pairing_overrides', { tournamentId });
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  fetchDraws: async (tournamentId: string) => {
+    set({ loading: true, error: null });
+    try {
+      const draws = await invoke<Draw[]>('get_draws', { tournamentId });
+      set({ draws, loading: false });
+    } catch (error) {
+      set({ error: String(error), loading: false });
+    }
+  },
+
+  replayDraw: async (drawId: string) => {
+    try {
+      return await invoke<DrawReplay>('replay_draw', { drawId });
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  // Mêlée actions
+  fetchPlayers: async (tournamentId: string) => {
+    set({ loading: true, error: null });
+    try {
+      const players = await invoke<Player[]>('get_players', { tournamentId });
+      set({ players, loading: false });
+    } catch (error) {
+      set({ error: String(error), loading: false });
+    }
+  },
+
+  createPlayer: async (data: Partial<Player>) => {
+    set({ loading: true, error: null });
+    try {
+      const player = await invoke<Player>('create_player', { data });
+      set((state) => ({
+        players: [...state.players, player],
+        loading: false,
+      }));
+      return player;
+    } catch (error) {
+      set({ error: String(error), loading: false });
+      throw error;
+    }
+  },
+
+  deletePlayer: async (id: string) => {
+    set({ loading: true, error: null });
+    try {
+      await invoke('delete_player', { id });
+      set((state) => ({
+        players: state.players.filter((p) => p.id !== id),
+        loading: false,
+      }));
+    } catch (error) {
+      set({ error: String(error), loading: false });
+      throw error;
+    }
+  },
+
+  fetchPlayerStandings: async (tournamentId: string) => {
+    set({ loading: true, error: null });
+    try {
+      const playerStandings = await invoke<PlayerStanding[]>('get_player_standings', {
+        tournamentId,
+      });
+      set({ playerStandings, loading: false });
+    } catch (error) {
+      set({ error: String(error), loading: false });
+    }
+  },
+
+  fetchMeleeGames: async (roundId: string) => {
+    set({ loading: true, error: null });
+    try {
+      const meleeGames = await invoke<MeleeGame[]>('get_melee_games', { roundId });
+      set({ meleeGames, loading: false });
     } catch (error) {
       set({ error: String(error), loading: false });
+    }
+  },
+
+  updateMeleeGameScore: async (
+    gameId: string,
+    team1Score: number,
+    team2Score: number
+  ) => {
+    set({ loading: true, error: null });
+    try {
+      await invoke('update_melee_game_score', { gameId, team1Score, team2Score });
+      set((state) => ({
+        meleeGames: state.meleeGames.map((g) =>
+          g.id === gameId ? { ...g, team1Score, team2Score } : g
+        ),
+        loading: false,
+      }));
+    } catch (error) {
+      set({ error: formatCommandError(error), loading: false });
       throw error;
     }
   },
@@ -1,7 +1,23 @@
 export type TeamComposition = 'men' | 'women' | 'mixed' | 'select';
 export type TournamentType = 'regional' | 'national' | 'open' | 'club';
 export type TournamentFormat = 'single' | 'double' | 'triple';
-export type PairingMethod = 'swiss' | 'swissHotel' | 'roundRobin' | 'poolPlay';
+export type AvoidancePolicy = 'none' | 'region' | 'club' | 'regionFirst' | 'clubFirst';
+export type LateEntryPolicy = 'zeroWins' | 'creditedByes';
+export type FirstRoundDraw = 'random' | 'seeded';
+export type BracketSeeding = 'classic' | 'standard' | 'random';
+export type Tiebreaker =
+  | 'wins'
+  | 'buchholz'
+  | 'medianBuchholz'
+  | 'cutBuchholz'
+  | 'fineBuchholz'
+  | 'sonnebornBerger'
+  | 'differential'
+  | 'quotient'
+  | 'pointsFor'
+  | 'headToHead'
+  | 'random';
+export type PairingMethod = 'swiss' | 'swissHotel' | 'roundRobin' | 'poolPlay' | 'poules' | 'melee';
 export type BracketSize = 4 | 8 | 16 | 32;
 
 export interface Tournament {
@@ -21,7 +37,21 @@ export interface Tournament {
   advanceCount: BracketSize | null;
   bracketSize: BracketSize;
   pairingMethod: PairingMethod;
-  regionAvoidance: boolean;
+  avoidancePolicy: AvoidancePolicy;
+  avoidanceRounds: number | null;
+  tiebreakers: Tiebreaker[];
+  maxTeams: number | null;
+  byeWinnerScore: number;
+  byeLoserScore: number;
+  byeCountsInTiebreaks: boolean;
+  forfeitWinnerScore: number;
+  forfeitLoserScore: number;
+  lateEntryPolicy: LateEntryPolicy;
+  targetScore: number;
+  timeLimitMinutes: number | null;
+  extraEnds: number;
+  firstRoundDraw: FirstRoundDraw;
+  bracketSeeding: BracketSeeding;
   createdAt: string;
   updatedAt: string;
 }
@@ -32,6 +62,21 @@ export interface Umpire {
   name: string;
 }
 
+export interface Court {
+  id: string;
+  tournamentId: string;
+  courtNumber: number;
+  label: string | null;
+  isActive: boolean;
+  isReserved: boolean;
+}
+
+export interface UpdateCourtData {
+  label: string | null;
+  isActive: boolean;
+  isReserved: boolean;
+}
+
 export interface Team {
   id: string;
   tournamentId: string;
@@ -40,7 +85,10 @@ export interface Team {
   player3: string | null;
   region: string | null;
   club: string | null;
+  seed: number | null;
   createdAt: string;
+  isActive: boolean;
+  withdrawnAt: string | null;
 }
 
 export interface QualifyingRound {
@@ -48,6 +96,9 @@ export interface QualifyingRound {
   tournamentId: string;
   roundNumber: number;
   isComplete: boolean;
+  startedAt: string | null;
+  endedAt: string | null;
+  drawMethod: FirstRoundDraw | null;
   createdAt: string;
 }
 
@@ -60,6 +111,9 @@ export interface QualifyingGame {
   team1Score: number | null;
   team2Score: number | null;
   isBye: boolean;
+  isForfeit: boolean;
+  slotNumber: number;
+  endedAt: string | null;
 }
 
 export interface TeamStanding {
@@ -72,10 +126,196 @@ export interface TeamStanding {
   pointsAgainst: number;
   differential: number;
   buchholzScore: number;
+  medianBuchholzScore: number;
+  cutBuchholzScore: number;
   fineBuchholzScore: number;
+  sonnebornBergerScore: number;
   pointQuotient: number;
+  headToHeadWins: number;
   isEliminated: boolean;
   rank: number;
+  isTied: boolean;
+  poolNumber: number | null;
+  poolRank: number | null;
+}
+
+export interface PoolAssignment {
+  id: string;
+  tournamentId: string;
+  teamId: string;
+  poolNumber: number;
+  position: number;
+  poolRank: number | null;
+}
+
+export interface Player {
+  id: string;
+  tournamentId: string;
+  name: string;
+  club: string | null;
+  createdAt: string;
+}
+
+export interface PlayerStanding {
+  id: string;
+  tournamentId: string;
+  playerId: string;
+  wins: number;
+  losses: number;
+  pointsFor: number;
+  pointsAgainst: number;
+  differential: number;
+  rank: number;
+}
+
+export interface MeleeGame {
+  id: string;
+  roundId: string;
+  courtNumber: number | null;
+  slotNumber: number;
+  team1Id: string;
+  team2Id: string | null;
+  team1Score: number | null;
+  team2Score: number | null;
+  isBye: boolean;
+  team1: Player[];
+  team2: Player[];
+}
+
+export type ScoreErrorKind = 'negativeScore' | 'aboveTarget' | 'draw' | 'targetNotReached' | 'other';
+
+export interface ScoreError {
+  kind: ScoreErrorKind;
+  message: string;
+  targetScore: number | null;
+}
+
+export interface ScoreCorrection {
+  id: string;
+  tournamentId: string;
+  gameId: string;
+  roundNumber: number;
+  oldTeam1Score: number | null;
+  oldTeam2Score: number | null;
+  newTeam1Score: number;
+  newTeam2Score: number;
+  reason: string;
+  createdAt: string;
+}
+
+export interface RankChange {
+  teamId: string;
+  oldRank: number;
+  newRank: number;
+}
+
+export interface CorrectionImpact {
+  correction: ScoreCorrection;
+  winnerChanged: boolean;
+  rankChanges: RankChange[];
+  affectedRoundNumbers: number[];
+  bracketsAffected: boolean;
+}
+
+export interface GameEnd {
+  id: string;
+  endNumber: number;
+  team1Points: number;
+  team2Points: number;
+  recordedAt: string;
+}
+
+export interface EndsSummary {
+  ends: GameEnd[];
+  team1Total: number;
+  team2Total: number;
+  startedAt: string | null;
+  timeCalledAt: string | null;
+  endsAfterTime: number;
+  isDecided: boolean;
+}
+
+export interface CourtDuration {
+  courtNumber: number;
+  gamesPlayed: number;
+  averageMinutes: number;
+  longestMinutes: number;
+}
+
+export interface PreviewGame {
+  slotNumber: number;
+  courtNumber: number;
+  team1Id: string;
+  team2Id: string | null;
+  isRepeat: boolean;
+  sameRegion: boolean;
+  winsGap: number;
+}
+
+export interface PairingQualityReport {
+  repeats: number;
+  sameRegionPairs: number;
+  maxWinsGap: number;
+  totalWinsGap: number;
+  byeTeamId: string | null;
+}
+
+export interface PairingPreview {
+  tournamentId: string;
+  roundNumber: number;
+  seed: string;
+  games: PreviewGame[];
+  report: PairingQualityReport;
+}
+
+export type OverrideWarningKind = 'rematch' | 'sameAffiliation' | 'repeatBye' | 'courtRepeat' | 'courtTaken';
+
+export interface OverrideWarning {
+  kind: OverrideWarningKind;
+  teamIds: string[];
+  courtNumber: number | null;
+  message: string;
+}
+
+export interface OverrideResult {
+  applied: boolean;
+  warnings: OverrideWarning[];
+}
+
+export interface PairingOverride {
+  id: string;
+  tournamentId: string;
+  roundId: string;
+  roundNumber: number;
+  kind: 'swap' | 'court';
+  game1Id: string;
+  game2Id: string | null;
+  team1Id: string | null;
+  team2Id: string | null;
+  oldCourt: number | null;
+  newCourt: number | null;
+  reason: string | null;
+  warnings: OverrideWarning[];
+  createdAt: string;
+}
+
+export type DrawKind = 'round' | 'bracket' | 'tiebreak';
+
+export interface Draw {
+  id: string;
+  tournamentId: string;
+  kind: DrawKind;
+  referenceId: string | null;
+  seed: string;
+  inputs: unknown;
+  result: unknown;
+  createdAt: string;
+}
+
+export interface DrawReplay {
+  draw: Draw;
+  result: unknown;
+  matches: boolean;
 }
 
 export interface Bracket {
@@ -101,6 +341,10 @@ export interface BracketMatch {
   winnerId: string | null;
   nextMatchId: string | null;
   isBye: boolean;
+  isForfeit: boolean;
+  slotNumber: number;
+  startedAt: string | null;
+  endedAt: string | null;
 }
 
 export interface PairingHistory {
@@ -137,7 +381,26 @@ export interface TournamentFormData {
   advanceCount: number | null;
   bracketSize: number;
   pairingMethod: PairingMethod;
-  regionAvoidance: boolean;
+  avoidancePolicy: AvoidancePolicy;
+  avoidanceRounds: number | null;
+  tiebreakers: Tiebreaker[];
+  maxTeams: number | null;
+  byeWinnerScore?: number;
+  byeLoserScore?: number;
+  byeCountsInTiebreaks?: boolean;
+  forfeitWinnerScore?: number;
+  forfeitLoserScore?: number;
+  lateEntryPolicy?: LateEntryPolicy;
+  targetScore?: number;
+  timeLimitMinutes: number | null;
+  extraEnds?: number;
+  firstRoundDraw?: FirstRoundDraw;
+  bracketSeeding?: BracketSeeding;
+}
+
+export interface PlayerFormData {
+  name: string;
+  club: string;
 }
 
 export interface TeamFormData {
@@ -146,6 +409,7 @@ export interface TeamFormData {
   player3: string;
   region: string;
   club: string;
+  seed: string;
 }
 
 // CSV Import
@@ -155,6 +419,7 @@ export interface CSVTeamRow {
   player3?: string;
   region?: string;
   club?: string;
+  seed?: string;
 }
 
 // Standings with team details